@@ -0,0 +1,29 @@
+@ noname.0.7.0
+
+DoubleGeneric<1>
+DoubleGeneric<1>
+DoubleGeneric<-1,0,0,1>
+DoubleGeneric<-1,0,0,1>
+DoubleGeneric<1,2,-1>
+DoubleGeneric<-1,0,0,1>
+DoubleGeneric<1,4,-1>
+DoubleGeneric<1,-1>
+DoubleGeneric<1,0,0,0,-1>
+DoubleGeneric<1,0,-1>
+DoubleGeneric<2,0,-1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<4,0,-1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<1,-1>
+(0,0) -> (14,0)
+(1,0) -> (7,1)
+(2,0) -> (2,1) -> (4,0) -> (8,0) -> (9,0)
+(3,0) -> (3,1) -> (4,1) -> (10,0)
+(4,2) -> (6,0)
+(5,0) -> (5,1) -> (6,1) -> (12,0)
+(6,2) -> (7,0)
+(9,2) -> (11,0)
+(10,2) -> (11,1)
+(11,2) -> (13,0)
+(12,2) -> (13,1)
+(13,2) -> (14,1)
@@ -0,0 +1,43 @@
+@ noname.0.7.0
+
+c0 = 18446744073709551360
+DoubleGeneric<1>
+DoubleGeneric<1>
+RangeCheck0
+DoubleGeneric<1,0,-1,0,c0>
+RangeCheck0
+RangeCheck0
+DoubleGeneric<1,0,-1,0,c0>
+RangeCheck0
+DoubleGeneric<1,1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<1,0,-1,0,256>
+DoubleGeneric<1,0,-1,0,-1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1>
+DoubleGeneric<256,0,-1>
+DoubleGeneric<1,1>
+DoubleGeneric<1,1,-1>
+RangeCheck0
+DoubleGeneric<1,0,-1,0,c0>
+RangeCheck0
+DoubleGeneric<1,1>
+DoubleGeneric<1,0,-1,0,1>
+DoubleGeneric<1,0,0,0,-1>
+(0,0) -> (2,0) -> (3,0) -> (9,0)
+(1,0) -> (2,1) -> (2,2) -> (4,1) -> (4,2) -> (5,1) -> (5,2) -> (7,1) -> (7,2) -> (17,1) -> (17,2) -> (19,1) -> (19,2)
+(3,2) -> (4,0)
+(5,0) -> (6,0) -> (8,0)
+(6,2) -> (7,0)
+(8,1) -> (9,1)
+(9,2) -> (10,0)
+(10,2) -> (16,0)
+(11,0) -> (12,0) -> (14,0) -> (20,0)
+(11,2) -> (12,1)
+(12,2) -> (13,0)
+(14,2) -> (15,0)
+(15,1) -> (16,1)
+(16,2) -> (17,0) -> (18,0)
+(18,2) -> (19,0)
+(20,1) -> (21,0)
+(21,2) -> (22,0)
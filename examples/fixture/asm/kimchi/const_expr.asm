@@ -0,0 +1,25 @@
+@ noname.0.7.0
+
+DoubleGeneric<1>
+DoubleGeneric<1>
+DoubleGeneric<1>
+DoubleGeneric<1>
+DoubleGeneric<1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<1,-1>
+(0,0) -> (11,0)
+(1,0) -> (5,0)
+(2,0) -> (5,1)
+(3,0) -> (6,0)
+(4,0) -> (6,1)
+(5,2) -> (7,0)
+(6,2) -> (7,1)
+(7,2) -> (8,0)
+(8,2) -> (9,0)
+(9,2) -> (10,0)
+(10,2) -> (11,1)
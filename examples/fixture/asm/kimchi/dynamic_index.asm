@@ -0,0 +1,43 @@
+@ noname.0.7.0
+
+DoubleGeneric<1>
+DoubleGeneric<1>
+DoubleGeneric<1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1,1>
+DoubleGeneric<1,0,-1,0,-1>
+DoubleGeneric<1,-1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1,0,-1,0,-1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1,1>
+DoubleGeneric<1,0,-1,0,-1>
+DoubleGeneric<1,-1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<1,0,0,0,-1>
+DoubleGeneric<1,-1>
+(0,0) -> (21,0)
+(1,0) -> (9,1)
+(2,0) -> (18,1)
+(3,0) -> (7,0) -> (10,0)
+(3,2) -> (4,0)
+(4,1) -> (6,0)
+(5,0) -> (7,1) -> (9,0) -> (17,0)
+(5,2) -> (6,1)
+(7,2) -> (8,0)
+(9,2) -> (19,0)
+(10,2) -> (11,0) -> (15,0)
+(11,2) -> (12,0)
+(12,1) -> (14,0)
+(13,0) -> (15,1) -> (17,1) -> (18,0)
+(13,2) -> (14,1)
+(15,2) -> (16,0)
+(17,2) -> (20,0)
+(18,2) -> (19,1)
+(19,2) -> (21,1)
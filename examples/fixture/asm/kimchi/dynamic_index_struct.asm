@@ -0,0 +1,82 @@
+@ noname.0.7.0
+
+DoubleGeneric<1>
+DoubleGeneric<1>
+DoubleGeneric<1>
+DoubleGeneric<1>
+DoubleGeneric<1>
+DoubleGeneric<1>
+DoubleGeneric<1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1,1>
+DoubleGeneric<1,0,-1,0,-1>
+DoubleGeneric<1,-1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1,0,-1,0,-1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1,1>
+DoubleGeneric<1,0,-1,0,-1>
+DoubleGeneric<1,-1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<1,0,-1,0,-2>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1,1>
+DoubleGeneric<1,0,-1,0,-1>
+DoubleGeneric<1,-1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<1,0,0,0,-1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<1,-1>
+(0,0) -> (41,0)
+(1,0) -> (13,1)
+(2,0) -> (14,1)
+(3,0) -> (23,1)
+(4,0) -> (25,1)
+(5,0) -> (35,1)
+(6,0) -> (37,1)
+(7,0) -> (11,0) -> (15,0) -> (27,0)
+(7,2) -> (8,0)
+(8,1) -> (10,0)
+(9,0) -> (11,1) -> (13,0) -> (14,0) -> (22,0)
+(9,2) -> (10,1)
+(11,2) -> (12,0)
+(13,2) -> (24,0)
+(14,2) -> (26,0)
+(15,2) -> (16,0) -> (20,0)
+(16,2) -> (17,0)
+(17,1) -> (19,0)
+(18,0) -> (20,1) -> (22,1) -> (23,0) -> (25,0)
+(18,2) -> (19,1)
+(20,2) -> (21,0)
+(22,2) -> (34,0)
+(23,2) -> (24,1)
+(24,2) -> (36,0)
+(25,2) -> (26,1)
+(26,2) -> (38,0)
+(27,2) -> (28,0) -> (32,0)
+(28,2) -> (29,0)
+(29,1) -> (31,0)
+(30,0) -> (32,1) -> (34,1) -> (35,0) -> (37,0)
+(30,2) -> (31,1)
+(32,2) -> (33,0)
+(34,2) -> (39,0)
+(35,2) -> (36,1)
+(36,2) -> (40,0)
+(37,2) -> (38,1)
+(38,2) -> (40,1)
+(40,2) -> (41,1)
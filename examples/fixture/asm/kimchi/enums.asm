@@ -0,0 +1,14 @@
+@ noname.0.7.0
+
+DoubleGeneric<1>
+DoubleGeneric<1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<1,-1>
+(0,0) -> (6,0)
+(1,0) -> (2,0) -> (2,1) -> (3,0) -> (3,1) -> (4,0)
+(2,2) -> (5,0)
+(4,2) -> (5,1)
+(5,2) -> (6,1)
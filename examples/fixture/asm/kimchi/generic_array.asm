@@ -0,0 +1,21 @@
+@ noname.0.7.0
+
+DoubleGeneric<1>
+DoubleGeneric<1>
+DoubleGeneric<1>
+DoubleGeneric<1>
+DoubleGeneric<1,0,-1,0,1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<1,-1>
+(0,0) -> (9,0)
+(1,0) -> (4,0)
+(2,0) -> (5,1)
+(3,0) -> (6,1)
+(4,2) -> (5,0)
+(5,2) -> (6,0)
+(6,2) -> (8,0)
+(7,2) -> (8,1)
+(8,2) -> (9,1)
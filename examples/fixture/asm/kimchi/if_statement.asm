@@ -0,0 +1,35 @@
+@ noname.0.7.0
+
+DoubleGeneric<1>
+DoubleGeneric<1>
+DoubleGeneric<1,0,-1,0,-1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1>
+DoubleGeneric<1,0,-1,0,-2>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1>
+DoubleGeneric<1,0,-1,0,1>
+DoubleGeneric<1,1>
+DoubleGeneric<1,0,-1,0,1>
+DoubleGeneric<3,0,-1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1,1>
+DoubleGeneric<1,0,-1,0,1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<1,-1>
+(0,0) -> (17,0)
+(1,0) -> (5,0) -> (8,0) -> (11,0)
+(2,0) -> (3,0) -> (6,0) -> (9,0) -> (12,1) -> (13,0)
+(2,2) -> (3,1)
+(3,2) -> (4,0)
+(5,2) -> (6,1)
+(6,2) -> (7,0)
+(8,2) -> (12,0)
+(9,1) -> (10,0)
+(11,2) -> (15,1)
+(12,2) -> (16,0)
+(13,1) -> (14,0)
+(14,2) -> (15,0)
+(15,2) -> (16,1)
+(16,2) -> (17,1)
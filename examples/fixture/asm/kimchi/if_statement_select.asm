@@ -0,0 +1,42 @@
+@ noname.0.7.0
+
+DoubleGeneric<1>
+DoubleGeneric<1>
+DoubleGeneric<1>
+DoubleGeneric<1,0,-1,0,-1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1>
+DoubleGeneric<1,1>
+DoubleGeneric<1,0,-1,0,1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1,1>
+DoubleGeneric<1,0,-1,0,1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1,1>
+DoubleGeneric<1,0,-1,0,1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<1,-1>
+DoubleGeneric<1,-1>
+(0,0) -> (19,0)
+(1,0) -> (20,0)
+(2,0) -> (8,0) -> (12,1) -> (14,0)
+(3,0) -> (4,0) -> (6,0) -> (9,1) -> (10,0) -> (14,1) -> (15,0)
+(3,2) -> (4,1)
+(4,2) -> (5,0)
+(6,1) -> (7,0)
+(8,1) -> (9,0)
+(8,2) -> (17,1)
+(9,2) -> (13,0)
+(10,1) -> (11,0)
+(11,2) -> (12,0)
+(12,2) -> (13,1)
+(13,2) -> (19,1)
+(14,2) -> (18,0)
+(15,1) -> (16,0)
+(16,2) -> (17,0)
+(17,2) -> (18,1)
+(18,2) -> (20,1)
@@ -0,0 +1,12 @@
+@ noname.0.7.0
+
+DoubleGeneric<1>
+DoubleGeneric<1,1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<1>
+DoubleGeneric<1,0,-1,0,-2>
+DoubleGeneric<1>
+(0,0) -> (2,1) -> (4,0)
+(1,1) -> (2,0)
+(2,2) -> (3,0)
+(4,2) -> (5,0)
@@ -0,0 +1,19 @@
+@ noname.0.7.0
+
+DoubleGeneric<1>
+DoubleGeneric<1>
+DoubleGeneric<1>
+DoubleGeneric<1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<0,0,-1,1>
+DoubleGeneric<1,1,-1>
+DoubleGeneric<1,-1>
+DoubleGeneric<1,-1>
+(0,0) -> (7,0)
+(1,0) -> (8,0)
+(2,0) -> (4,0) -> (5,0)
+(3,0) -> (8,1)
+(4,1) -> (5,1)
+(4,2) -> (6,1)
+(5,2) -> (6,0)
+(6,2) -> (7,1)
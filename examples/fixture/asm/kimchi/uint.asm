@@ -0,0 +1,39 @@
+@ noname.0.7.0
+
+c0 = 18446744073709551360
+DoubleGeneric<1>
+DoubleGeneric<1>
+DoubleGeneric<1>
+RangeCheck0
+DoubleGeneric<1,0,-1,0,c0>
+RangeCheck0
+RangeCheck0
+DoubleGeneric<1,0,-1,0,c0>
+RangeCheck0
+DoubleGeneric<1,1,-1>
+RangeCheck0
+DoubleGeneric<1,0,-1,0,c0>
+RangeCheck0
+DoubleGeneric<2,0,-1>
+RangeCheck0
+DoubleGeneric<1,0,-1,0,c0>
+RangeCheck0
+DoubleGeneric<1,1>
+DoubleGeneric<1,1,-1>
+RangeCheck0
+DoubleGeneric<1,0,-1,0,c0>
+RangeCheck0
+DoubleGeneric<1,-1>
+(0,0) -> (22,0)
+(1,0) -> (3,0) -> (4,0) -> (9,0)
+(2,0) -> (3,1) -> (3,2) -> (5,1) -> (5,2) -> (6,1) -> (6,2) -> (8,1) -> (8,2) -> (10,1) -> (10,2) -> (12,1) -> (12,2) -> (14,1) -> (14,2) -> (16,1) -> (16,2) -> (19,1) -> (19,2) -> (21,1) -> (21,2)
+(4,2) -> (5,0)
+(6,0) -> (7,0) -> (9,1) -> (17,0)
+(7,2) -> (8,0)
+(9,2) -> (10,0) -> (11,0) -> (13,0)
+(11,2) -> (12,0)
+(13,2) -> (14,0) -> (15,0) -> (18,0)
+(15,2) -> (16,0)
+(17,1) -> (18,1)
+(18,2) -> (19,0) -> (20,0) -> (22,1)
+(20,2) -> (21,0)
@@ -0,0 +1,8 @@
+@ noname.0.7.0
+
+v_3 == (v_3) * (v_3)
+v_4 == (v_4) * (v_4)
+v_5 == (v_5) * (v_5)
+v_2 == (v_3 + 2 * v_4 + 4 * v_5) * (1)
+1 == (v_3) * (1)
+v_3 + 2 * v_4 + 4 * v_5 == (v_1) * (1)
@@ -0,0 +1,32 @@
+@ noname.0.7.0
+
+v_2 == (v_2) * (v_2)
+v_3 == (v_3) * (v_3)
+v_4 == (v_4) * (v_4)
+v_5 == (v_5) * (v_5)
+v_6 == (v_6) * (v_6)
+v_7 == (v_7) * (v_7)
+v_8 == (v_8) * (v_8)
+v_9 == (v_9) * (v_9)
+v_1 == (v_2 + 2 * v_3 + 4 * v_4 + 8 * v_5 + 16 * v_6 + 32 * v_7 + 64 * v_8 + 128 * v_9) * (1)
+v_11 == (v_11) * (v_11)
+v_12 == (v_12) * (v_12)
+v_13 == (v_13) * (v_13)
+v_14 == (v_14) * (v_14)
+v_15 == (v_15) * (v_15)
+v_16 == (v_16) * (v_16)
+v_17 == (v_17) * (v_17)
+v_18 == (v_18) * (v_18)
+v_10 == (v_11 + 2 * v_12 + 4 * v_13 + 8 * v_14 + 16 * v_15 + 32 * v_16 + 64 * v_17 + 128 * v_18) * (1)
+v_20 == (v_19) * (v_19 + -1)
+0 == (v_20) * (1)
+v_21 == (v_21) * (v_21)
+v_22 == (v_22) * (v_22)
+v_23 == (v_23) * (v_23)
+v_24 == (v_24) * (v_24)
+v_25 == (v_25) * (v_25)
+v_26 == (v_26) * (v_26)
+v_27 == (v_27) * (v_27)
+v_28 == (v_28) * (v_28)
+v_1 + -1 * v_10 + -256 * v_19 + 256 == (v_21 + 2 * v_22 + 4 * v_23 + 8 * v_24 + 16 * v_25 + 32 * v_26 + 64 * v_27 + 128 * v_28) * (1)
+1 == (-1 * v_19 + 1) * (1)
@@ -0,0 +1,6 @@
+@ noname.0.7.0
+
+v_4 == (v_1) * (v_3)
+1 == (v_4) * (1)
+v_5 == (v_2) * (v_3)
+3 == (v_5) * (1)
@@ -0,0 +1,14 @@
+@ noname.0.7.0
+
+v_6 == (v_4) * (v_5)
+v_7 + -1 == (-1 * v_6) * (1)
+v_8 == (v_4) * (v_7)
+0 == (v_8) * (1)
+v_9 == (v_7) * (v_2)
+v_11 == (v_4 + -1) * (v_10)
+v_12 + -1 == (-1 * v_11) * (1)
+v_13 == (v_4 + -1) * (v_12)
+0 == (v_13) * (1)
+v_14 == (v_12) * (v_3)
+1 == (v_7 + v_12) * (1)
+v_9 + v_14 == (v_1) * (1)
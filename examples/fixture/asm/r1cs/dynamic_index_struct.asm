@@ -0,0 +1,22 @@
+@ noname.0.7.0
+
+v_10 == (v_8) * (v_9)
+v_11 + -1 == (-1 * v_10) * (1)
+v_12 == (v_8) * (v_11)
+0 == (v_12) * (1)
+v_13 == (v_11) * (v_2)
+v_14 == (v_11) * (v_3)
+v_16 == (v_8 + -1) * (v_15)
+v_17 + -1 == (-1 * v_16) * (1)
+v_18 == (v_8 + -1) * (v_17)
+0 == (v_18) * (1)
+v_19 == (v_17) * (v_4)
+v_20 == (v_17) * (v_5)
+v_22 == (v_8 + -2) * (v_21)
+v_23 + -1 == (-1 * v_22) * (1)
+v_24 == (v_8 + -2) * (v_23)
+0 == (v_24) * (1)
+v_25 == (v_23) * (v_6)
+v_26 == (v_23) * (v_7)
+1 == (v_11 + v_17 + v_23) * (1)
+v_13 + v_14 + v_19 + v_20 + v_25 + v_26 == (v_1) * (1)
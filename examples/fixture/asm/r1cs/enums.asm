@@ -0,0 +1,6 @@
+@ noname.0.7.0
+
+v_4 == (v_2) * (v_2)
+v_5 == (v_2) * (v_2)
+v_6 == (v_2) * (v_3)
+v_4 + v_6 == (v_1) * (1)
@@ -0,0 +1,3 @@
+@ noname.0.7.0
+
+v_2 + v_3 + v_4 + v_5 + v_6 + 1 == (v_1) * (1)
@@ -0,0 +1,6 @@
+@ noname.0.7.0
+
+v_5 == (v_4) * (v_2)
+1 == (v_5) * (1)
+v_3 == (v_6 + 16 * v_7) * (1)
+v_7 == (v_1) * (1)
@@ -0,0 +1,9 @@
+@ noname.0.7.0
+
+v_4 == (v_3) * (v_3 + -1)
+0 == (v_4) * (1)
+v_5 == (v_3) * (v_2 + -2)
+0 == (v_5) * (1)
+v_6 == (v_2 + 1) * (v_3)
+v_7 == (-1 * v_3 + 1) * (3 * v_2)
+v_6 + v_7 == (v_1) * (1)
@@ -0,0 +1,10 @@
+@ noname.0.7.0
+
+v_6 == (v_5) * (v_5 + -1)
+0 == (v_6) * (1)
+v_7 == (v_4) * (v_5)
+v_8 == (-1 * v_5 + 1) * (v_3)
+v_9 == (v_3) * (v_5)
+v_10 == (-1 * v_5 + 1) * (v_3 + v_4)
+v_7 + v_8 == (v_1) * (1)
+v_9 + v_10 == (v_2) * (1)
@@ -0,0 +1,4 @@
+@ noname.0.7.0
+
+0 == (v_1 + -1 * v_2) * (1)
+0 == (v_1 + -2) * (1)
@@ -0,0 +1,48 @@
+@ noname.0.7.0
+
+v_3 == (v_3) * (v_3)
+v_4 == (v_4) * (v_4)
+v_5 == (v_5) * (v_5)
+v_6 == (v_6) * (v_6)
+v_7 == (v_7) * (v_7)
+v_8 == (v_8) * (v_8)
+v_9 == (v_9) * (v_9)
+v_10 == (v_10) * (v_10)
+v_2 == (v_3 + 2 * v_4 + 4 * v_5 + 8 * v_6 + 16 * v_7 + 32 * v_8 + 64 * v_9 + 128 * v_10) * (1)
+v_12 == (v_12) * (v_12)
+v_13 == (v_13) * (v_13)
+v_14 == (v_14) * (v_14)
+v_15 == (v_15) * (v_15)
+v_16 == (v_16) * (v_16)
+v_17 == (v_17) * (v_17)
+v_18 == (v_18) * (v_18)
+v_19 == (v_19) * (v_19)
+v_11 == (v_12 + 2 * v_13 + 4 * v_14 + 8 * v_15 + 16 * v_16 + 32 * v_17 + 64 * v_18 + 128 * v_19) * (1)
+v_20 == (v_20) * (v_20)
+v_21 == (v_21) * (v_21)
+v_22 == (v_22) * (v_22)
+v_23 == (v_23) * (v_23)
+v_24 == (v_24) * (v_24)
+v_25 == (v_25) * (v_25)
+v_26 == (v_26) * (v_26)
+v_27 == (v_27) * (v_27)
+v_2 + v_11 == (v_20 + 2 * v_21 + 4 * v_22 + 8 * v_23 + 16 * v_24 + 32 * v_25 + 64 * v_26 + 128 * v_27) * (1)
+v_28 == (v_28) * (v_28)
+v_29 == (v_29) * (v_29)
+v_30 == (v_30) * (v_30)
+v_31 == (v_31) * (v_31)
+v_32 == (v_32) * (v_32)
+v_33 == (v_33) * (v_33)
+v_34 == (v_34) * (v_34)
+v_35 == (v_35) * (v_35)
+2 * v_2 + 2 * v_11 == (v_28 + 2 * v_29 + 4 * v_30 + 8 * v_31 + 16 * v_32 + 32 * v_33 + 64 * v_34 + 128 * v_35) * (1)
+v_36 == (v_36) * (v_36)
+v_37 == (v_37) * (v_37)
+v_38 == (v_38) * (v_38)
+v_39 == (v_39) * (v_39)
+v_40 == (v_40) * (v_40)
+v_41 == (v_41) * (v_41)
+v_42 == (v_42) * (v_42)
+v_43 == (v_43) * (v_43)
+2 * v_2 + v_11 == (v_36 + 2 * v_37 + 4 * v_38 + 8 * v_39 + 16 * v_40 + 32 * v_41 + 64 * v_42 + 128 * v_43) * (1)
+2 * v_2 + v_11 == (v_1) * (1)
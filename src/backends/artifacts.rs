@@ -0,0 +1,83 @@
+//! Artifacts written to disk by `noname build` and read by `noname prove` and `noname verify`.
+//!
+//! An artifact is a MessagePack-encoded [ArtifactHeader] followed by a MessagePack-encoded body.
+//! The header allows us to reject artifacts produced by other versions of noname,
+//! or produced for a different circuit.
+
+use camino::Utf8Path;
+use miette::{Context, IntoDiagnostic};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// The header of the artifacts produced by `noname build` (e.g. `prover.nope` and `verifier.nope`).
+#[derive(Serialize, Deserialize)]
+pub struct ArtifactHeader {
+    /// The version of noname that produced the artifact.
+    pub version: String,
+
+    /// The backend the artifact was produced for.
+    pub backend: String,
+
+    /// The hash of the circuit the artifact was produced for.
+    pub circuit_hash: [u8; 32],
+}
+
+/// Writes an artifact as a MessagePack-encoded [ArtifactHeader] followed by its MessagePack-encoded body.
+pub fn write_artifact<T: Serialize>(
+    path: &Utf8Path,
+    backend: &str,
+    circuit_hash: [u8; 32],
+    body: &T,
+) -> miette::Result<()> {
+    let header = ArtifactHeader {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        backend: backend.to_string(),
+        circuit_hash,
+    };
+
+    let mut bytes = rmp_serde::to_vec(&header).into_diagnostic()?;
+    bytes.extend(rmp_serde::to_vec(body).into_diagnostic()?);
+
+    std::fs::write(path, bytes)
+        .into_diagnostic()
+        .wrap_err(format!("could not write artifact to `{path}`"))
+}
+
+/// Reads an artifact written by [write_artifact],
+/// rejecting artifacts produced by a different version of noname or for a different backend.
+pub fn read_artifact<T: DeserializeOwned>(
+    path: &Utf8Path,
+    backend: &str,
+) -> miette::Result<(ArtifactHeader, T)> {
+    let bytes = std::fs::read(path).into_diagnostic().wrap_err(format!(
+        "could not read artifact at `{path}` (did you run `noname build`?)"
+    ))?;
+
+    let mut deserializer = rmp_serde::Deserializer::new(bytes.as_slice());
+
+    let header = ArtifactHeader::deserialize(&mut deserializer)
+        .into_diagnostic()
+        .wrap_err(format!(
+            "could not deserialize the header of the artifact at `{path}`"
+        ))?;
+
+    if header.version != env!("CARGO_PKG_VERSION") {
+        miette::bail!(
+            "the artifact at `{path}` was produced by noname {}, but this is noname {}. Please run `noname build` again.",
+            header.version,
+            env!("CARGO_PKG_VERSION")
+        );
+    }
+
+    if header.backend != backend {
+        miette::bail!(
+            "the artifact at `{path}` was produced for the `{}` backend, not the `{backend}` backend",
+            header.backend
+        );
+    }
+
+    let body = T::deserialize(&mut deserializer)
+        .into_diagnostic()
+        .wrap_err(format!("could not deserialize the artifact at `{path}`"))?;
+
+    Ok((header, body))
+}
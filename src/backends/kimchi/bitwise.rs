@@ -0,0 +1,200 @@
+//! Bitwise operations on packed values, with the lookup-based gates of kimchi.
+
+use ark_ff::{One, Zero};
+use num_bigint::BigUint;
+
+use super::{range_check_slice, KimchiCellVar, KimchiVesta, VestaField, NUM_REGISTERS};
+use crate::{
+    backends::Backend,
+    circuit_writer::{CircuitWriter, GateKind},
+    constants::Span,
+    constraints::ec,
+    var::{ConstOrCell, Value},
+};
+
+/// The number of bits XORed by a [GateKind::Xor16] gate.
+const XOR_BITS: usize = 16;
+
+/// The number of bits of each value in the lookups of a [GateKind::Xor16] gate.
+const NIBBLE_BITS: usize = 4;
+
+/// Returns the value of a field element that fits in 64 bits.
+pub(crate) fn to_u64(value: VestaField) -> u64 {
+    let value: BigUint = value.into();
+    u64::try_from(value).expect("the value does not fit in 64 bits")
+}
+
+/// XORs two values of `num_bits` bits (a multiple of 16, at most 64),
+/// with a chain of [GateKind::Xor16] gates followed by a [GateKind::Zero] gate.
+///
+/// Each gate XORs the 16 least significant bits of its inputs, split in 4-bit nibbles
+/// that are looked up in the XOR table, and passes the remaining bits to the next row.
+/// As the remaining bits must be zero in the last row,
+/// this also checks that the inputs and the output fit in `num_bits` bits.
+pub fn xor(
+    compiler: &mut CircuitWriter<KimchiVesta>,
+    lhs: &ConstOrCell<VestaField, KimchiCellVar>,
+    rhs: &ConstOrCell<VestaField, KimchiCellVar>,
+    num_bits: usize,
+    span: Span,
+) -> ConstOrCell<VestaField, KimchiCellVar> {
+    assert!(num_bits % XOR_BITS == 0 && num_bits <= 64);
+
+    // constants are XORed at compile time
+    if let (ConstOrCell::Const(lhs), ConstOrCell::Const(rhs)) = (lhs, rhs) {
+        return ConstOrCell::Const(VestaField::from(to_u64(*lhs) ^ to_u64(*rhs)));
+    }
+
+    let lhs = to_cell(compiler, lhs, span);
+    let rhs = to_cell(compiler, rhs, span);
+    let out = ec::hint(
+        compiler,
+        &[ConstOrCell::Cell(lhs), ConstOrCell::Cell(rhs)],
+        |values| Ok(VestaField::from(to_u64(values[0]) ^ to_u64(values[1]))),
+        span,
+    );
+
+    for start in (0..num_bits).step_by(XOR_BITS) {
+        let mut vars = vec![];
+        for value in [lhs, rhs, out] {
+            let remaining = if start == 0 {
+                value
+            } else {
+                compiler
+                    .backend
+                    .new_internal_var(Value::Bits(value, start, num_bits - start), span)
+            };
+            vars.push(Some(remaining));
+        }
+
+        for value in [lhs, rhs, out] {
+            for nibble in (start..start + XOR_BITS).step_by(NIBBLE_BITS) {
+                let var = compiler
+                    .backend
+                    .new_internal_var(Value::Bits(value, nibble, NIBBLE_BITS), span);
+                vars.push(Some(var));
+            }
+        }
+
+        compiler.backend.add_gate(
+            "XOR 16 bits with a lookup-based XOR gate",
+            GateKind::Xor16,
+            vars,
+            vec![],
+            span,
+        );
+    }
+
+    // the bits remaining after the last XOR gate must be zero
+    let zero = compiler.backend.add_constant(
+        Some("encoding constant 0 for the end of a chain of XOR gates"),
+        VestaField::zero(),
+        span,
+    );
+    compiler.backend.add_gate(
+        "ends a chain of XOR gates",
+        GateKind::Zero,
+        vec![Some(zero), Some(zero), Some(zero)],
+        vec![],
+        span,
+    );
+
+    ConstOrCell::Cell(out)
+}
+
+/// Rotates a 64-bit value to the left with a [GateKind::Rot64] gate,
+/// followed by [GateKind::RangeCheck0] gates on the shifted value and on the excess bits.
+///
+/// The gate checks that `word * 2^amount = excess * 2^64 + shifted`,
+/// and that the rotated value is `shifted + excess`.
+/// It also checks that `excess < 2^amount`, by decomposing `excess + 2^64 - 2^amount` in 64 bits
+/// (with the layout of the limbs of a [GateKind::RangeCheck0] gate).
+/// The shifted value must be in the row that follows the gate.
+/// The value being rotated must be known to fit in 64 bits.
+pub fn rotl64(
+    compiler: &mut CircuitWriter<KimchiVesta>,
+    word: &ConstOrCell<VestaField, KimchiCellVar>,
+    amount: u32,
+    span: Span,
+) -> ConstOrCell<VestaField, KimchiCellVar> {
+    assert!(amount < 64);
+
+    // constants are rotated at compile time
+    if let ConstOrCell::Const(cst) = word {
+        return ConstOrCell::Const(VestaField::from(to_u64(*cst).rotate_left(amount)));
+    }
+    if amount == 0 {
+        return *word;
+    }
+
+    let word = to_cell(compiler, word, span);
+    let one = VestaField::one();
+    let shift = VestaField::from(1u64 << amount);
+
+    let excess = compiler.backend.new_internal_var(
+        Value::Bits(word, 64 - amount as usize, amount as usize),
+        span,
+    );
+    let shifted = ec::hint(
+        compiler,
+        &[ConstOrCell::Cell(word)],
+        move |values| Ok(VestaField::from(to_u64(values[0]) << amount)),
+        span,
+    );
+    let rotated = compiler.backend.new_internal_var(
+        Value::LinearCombination(vec![(one, shifted), (one, excess)], VestaField::zero()),
+        span,
+    );
+    // the limbs of `excess + 2^64 - 2^amount`
+    let offset = (1u128 << 64) - (1u128 << amount);
+    let mut vars = vec![Some(word), Some(rotated), Some(excess)];
+    for col in 3..NUM_REGISTERS {
+        let (start, len) = range_check_slice(col);
+        let limb = ec::hint(
+            compiler,
+            &[ConstOrCell::Cell(excess)],
+            move |values| {
+                let bound = to_u64(values[0]) as u128 + offset;
+                Ok(VestaField::from((bound >> start) & ((1 << len) - 1)))
+            },
+            span,
+        );
+        vars.push(Some(limb));
+    }
+
+    // the zero used by the range checks is created now,
+    // so that its gate doesn't end up between the rotation gate and the range check of `shifted`
+    compiler.backend.add_constant(
+        Some("hardcode zero for the limbs of a range check"),
+        VestaField::zero(),
+        span,
+    );
+
+    compiler.backend.add_gate(
+        "rotate 64 bits with a rotation gate",
+        GateKind::Rot64,
+        vars,
+        vec![shift],
+        span,
+    );
+    compiler.backend.range_check_64(&shifted, span);
+    compiler.backend.range_check_64(&excess, span);
+
+    ConstOrCell::Cell(rotated)
+}
+
+/// Returns a cell containing the given value, encoding it as a constant if needed.
+pub(crate) fn to_cell(
+    compiler: &mut CircuitWriter<KimchiVesta>,
+    const_or_cell: &ConstOrCell<VestaField, KimchiCellVar>,
+    span: Span,
+) -> KimchiCellVar {
+    match const_or_cell {
+        ConstOrCell::Const(cst) => compiler.backend.add_constant(
+            Some("encoding constant input to a bitwise gate"),
+            *cst,
+            span,
+        ),
+        ConstOrCell::Cell(cell) => *cell,
+    }
+}
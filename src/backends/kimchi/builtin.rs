@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use ark_ff::Zero;
+use ark_ff::{Field, One, Zero};
 use kimchi::circuits::polynomials::poseidon::{POS_ROWS_PER_HASH, ROUNDS_PER_ROW};
 use kimchi::mina_poseidon::constants::{PlonkSpongeConstantsKimchi, SpongeConstants};
 use kimchi::mina_poseidon::permutation::full_round;
@@ -12,8 +12,10 @@ use crate::backends::Backend;
 use crate::{
     circuit_writer::{CircuitWriter, GateKind, VarInfo},
     constants::Span,
-    error::{ErrorKind, Result},
+    constraints::{ec, field},
+    error::{Error, ErrorKind, Result},
     parser::types::TyKind,
+    stdlib::ec::point_arg,
     var::{ConstOrCell, Value, Var},
 };
 
@@ -66,12 +68,6 @@ pub fn poseidon(
         }
     }
 
-    // get constants needed for poseidon
-    let poseidon_params = kimchi::mina_poseidon::pasta::fp_kimchi::params();
-
-    let rc = &poseidon_params.round_constants;
-    let width = PlonkSpongeConstantsKimchi::SPONGE_WIDTH;
-
     // pad the input (for the capacity)
     let zero_var = compiler.backend.add_constant(
         Some("encoding constant 0 for the capacity of poseidon"),
@@ -80,7 +76,51 @@ pub fn poseidon(
     );
     cells.push(zero_var);
 
-    let mut states = vec![cells.clone()];
+    let final_state = permute(compiler, cells, span);
+
+    let vars = final_state.into_iter().map(ConstOrCell::Cell).collect();
+
+    Ok(Some(Var::new(vars, span)))
+}
+
+/// The poseidon permutation, on a state laid out as the rate followed by the capacity
+/// (the layout used by the sponge of the standard library).
+pub fn poseidon_permutation(
+    compiler: &mut CircuitWriter<KimchiVesta>,
+    state: &[ConstOrCell<VestaField, KimchiCellVar>],
+    span: Span,
+) -> Result<Vec<KimchiCellVar>> {
+    assert_eq!(state.len(), PlonkSpongeConstantsKimchi::SPONGE_WIDTH);
+
+    // the poseidon gate only works on cells
+    let cells = state
+        .iter()
+        .map(|const_or_cell| match const_or_cell {
+            ConstOrCell::Const(cst) => compiler.backend.add_constant(
+                Some("encoding constant state of poseidon"),
+                *cst,
+                span,
+            ),
+            ConstOrCell::Cell(cell) => *cell,
+        })
+        .collect();
+
+    Ok(permute(compiler, cells, span))
+}
+
+/// Constrains the poseidon permutation of a state of 3 cells, and returns the resulting state.
+fn permute(
+    compiler: &mut CircuitWriter<KimchiVesta>,
+    cells: Vec<KimchiCellVar>,
+    span: Span,
+) -> Vec<KimchiCellVar> {
+    // get constants needed for poseidon
+    let poseidon_params = kimchi::mina_poseidon::pasta::fp_kimchi::params();
+
+    let rc = &poseidon_params.round_constants;
+    let width = PlonkSpongeConstantsKimchi::SPONGE_WIDTH;
+
+    let mut states = vec![cells];
 
     // 0..11
     for row in 0..POS_ROWS_PER_HASH {
@@ -164,17 +204,413 @@ pub fn poseidon(
     compiler.backend.add_gate(
         "uses a zero gate to store the output of poseidon",
         GateKind::Zero,
-        final_row.clone(),
+        final_row,
         vec![],
         span,
     );
 
-    let vars = final_row
+    final_state.clone()
+}
+
+//
+// Elliptic curve operations
+//
+
+/// The number of bits processed by a [GateKind::VarBaseMul] gate (which spans two rows).
+const VAR_BASE_MUL_BITS: usize = 5;
+
+/// Adds two points with a [GateKind::CompleteAdd] gate.
+pub fn ec_add(
+    compiler: &mut CircuitWriter<KimchiVesta>,
+    vars: &[VarInfo<VestaField, KimchiCellVar>],
+    span: Span,
+) -> Result<Option<Var<VestaField, KimchiCellVar>>> {
+    assert_eq!(vars.len(), 2);
+    let lhs = point_arg::<KimchiVesta>(&vars[0]);
+    let rhs = point_arg::<KimchiVesta>(&vars[1]);
+    assert_on_curve(compiler, &lhs, span)?;
+    assert_on_curve(compiler, &rhs, span)?;
+
+    // constant points are added at compile time
+    if let (Some(lhs), Some(rhs)) = (
+        ec::constant::<KimchiVesta>(&lhs),
+        ec::constant::<KimchiVesta>(&rhs),
+    ) {
+        let res = ec::add_values(lhs, rhs)
+            .ok_or_else(|| compiler.error(ErrorKind::PointAtInfinity, span))?;
+        return Ok(Some(constant_point_var(res, span)));
+    }
+
+    let lhs = point_cells(compiler, &lhs, span);
+    let rhs = point_cells(compiler, &rhs, span);
+    let res = complete_add(compiler, lhs, rhs, span);
+
+    Ok(Some(point_var(res, span)))
+}
+
+/// Doubles a point with a [GateKind::CompleteAdd] gate.
+pub fn ec_double(
+    compiler: &mut CircuitWriter<KimchiVesta>,
+    vars: &[VarInfo<VestaField, KimchiCellVar>],
+    span: Span,
+) -> Result<Option<Var<VestaField, KimchiCellVar>>> {
+    assert_eq!(vars.len(), 1);
+    let point = point_arg::<KimchiVesta>(&vars[0]);
+    assert_on_curve(compiler, &point, span)?;
+
+    // a constant point is doubled at compile time
+    if let Some(point) = ec::constant::<KimchiVesta>(&point) {
+        let res = ec::add_values(point, point)
+            .ok_or_else(|| compiler.error(ErrorKind::PointAtInfinity, span))?;
+        return Ok(Some(constant_point_var(res, span)));
+    }
+
+    let point = point_cells(compiler, &point, span);
+    let res = complete_add(compiler, point, point, span);
+
+    Ok(Some(point_var(res, span)))
+}
+
+/// Multiplies a point by a scalar, given by its bits from the least significant bit,
+/// with [GateKind::VarBaseMul] gates.
+///
+/// The gates compute `acc = 2 * acc + (2 * b - 1) * P` for each bit `b`,
+/// from the most significant bit and starting from `2 * P`.
+/// With the `n` bits of `k >> 1`, this gives `(2 * (k >> 1) + 2^n + 1) * P`,
+/// which is then corrected to `k * P` with [GateKind::CompleteAdd] gates.
+pub fn ec_scale(
+    compiler: &mut CircuitWriter<KimchiVesta>,
+    vars: &[VarInfo<VestaField, KimchiCellVar>],
+    span: Span,
+) -> Result<Option<Var<VestaField, KimchiCellVar>>> {
+    assert_eq!(vars.len(), 2);
+    let point = point_arg::<KimchiVesta>(&vars[0]);
+    let bits = &vars[1].var.cvars;
+    assert_on_curve(compiler, &point, span)?;
+
+    // a constant scalar multiplication is done at compile time
+    let constant_bits: Option<Vec<_>> = bits
         .iter()
-        .flatten()
-        .cloned()
-        .map(ConstOrCell::Cell)
+        .map(|bit| bit.cst().map(|bit| bit.is_one()))
         .collect();
+    if let (Some(point), Some(bits)) = (ec::constant::<KimchiVesta>(&point), constant_bits) {
+        let res = ec::scale_values(point, &bits)
+            .ok_or_else(|| compiler.error(ErrorKind::PointAtInfinity, span))?;
+        return Ok(Some(constant_point_var(res, span)));
+    }
 
-    Ok(Some(Var::new(vars, span)))
+    // multiplying by zero gives the point at infinity
+    let (low_bit, high_bits) = bits
+        .split_first()
+        .ok_or_else(|| compiler.error(ErrorKind::PointAtInfinity, span))?;
+
+    let base = point_cells(compiler, &point, span);
+    let acc = complete_add(compiler, base, base, span);
+
+    // the gates process the bits from the most significant one, 5 at a time
+    // (with leading zeros if needed)
+    let zero = compiler.backend.add_constant(
+        Some("encoding constant 0 to pad a scalar"),
+        VestaField::zero(),
+        span,
+    );
+    let padding = (VAR_BASE_MUL_BITS - high_bits.len() % VAR_BASE_MUL_BITS) % VAR_BASE_MUL_BITS;
+    let mut msb_bits = vec![zero; padding];
+    for bit in high_bits.iter().rev() {
+        msb_bits.push(to_cell(compiler, bit, span));
+    }
+    let acc = var_base_mul(compiler, base, acc, &msb_bits, span);
+
+    // subtract 2^n * P, and P if the scalar is even
+    let mut shift = base;
+    for _ in 0..msb_bits.len() {
+        shift = complete_add(compiler, shift, shift, span);
+    }
+    let shift_plus_one = complete_add(compiler, shift, base, span);
+
+    let correction = [0, 1].map(|idx| {
+        let res = field::if_else_inner(
+            compiler,
+            low_bit,
+            &ConstOrCell::Cell(shift[idx]),
+            &ConstOrCell::Cell(shift_plus_one[idx]),
+            span,
+        );
+        to_cell(compiler, &res[0], span)
+    });
+    let neg_y = compiler.backend.neg(&correction[1], span);
+    let res = complete_add(compiler, acc, [correction[0], neg_y], span);
+
+    Ok(Some(point_var(res, span)))
+}
+
+/// Constrains the sum of two points (which can be equal) with a [GateKind::CompleteAdd] gate.
+/// The result must not be the point at infinity.
+fn complete_add(
+    compiler: &mut CircuitWriter<KimchiVesta>,
+    lhs: [KimchiCellVar; 2],
+    rhs: [KimchiCellVar; 2],
+    span: Span,
+) -> [KimchiCellVar; 2] {
+    let [x1, y1] = lhs;
+    let [x2, y2] = rhs;
+    let inputs = [x1, y1, x2, y2].map(ConstOrCell::Cell);
+
+    let same_x = ec::hint(
+        compiler,
+        &inputs,
+        |values| Ok(VestaField::from((values[0] == values[2]) as u64)),
+        span,
+    );
+    let x21_inv = ec::hint(
+        compiler,
+        &inputs,
+        |values| {
+            Ok((values[2] - values[0])
+                .inverse()
+                .unwrap_or_else(VestaField::zero))
+        },
+        span,
+    );
+    let s = ec::hint(
+        compiler,
+        &inputs,
+        move |values| {
+            ec::slope([values[0], values[1]], [values[2], values[3]])
+                .ok_or_else(|| Error::new("runtime", ErrorKind::PointAtInfinity, span))
+        },
+        span,
+    );
+    let [x3, y3] = [0, 1].map(|idx| {
+        ec::hint(
+            compiler,
+            &inputs,
+            move |values| {
+                ec::add_values([values[0], values[1]], [values[2], values[3]])
+                    .map(|res| res[idx])
+                    .ok_or_else(|| Error::new("runtime", ErrorKind::PointAtInfinity, span))
+            },
+            span,
+        )
+    });
+
+    // the flag for the point at infinity is always zero,
+    // and so is the inverse of `y2 - y1` which is only used when the flag is set
+    let zero = compiler.backend.add_constant(
+        Some("encoding constant 0 for the point at infinity flag of an addition"),
+        VestaField::zero(),
+        span,
+    );
+
+    let vars = vec![
+        Some(x1),
+        Some(y1),
+        Some(x2),
+        Some(y2),
+        Some(x3),
+        Some(y3),
+        Some(zero),
+        Some(same_x),
+        Some(s),
+        Some(zero),
+        Some(x21_inv),
+    ];
+
+    compiler.backend.add_gate(
+        "uses a complete addition gate to add two points",
+        GateKind::CompleteAdd,
+        vars,
+        vec![],
+        span,
+    );
+
+    [x3, y3]
+}
+
+/// Constrains `acc = 2 * acc + (2 * b - 1) * base` for each bit `b` (from the most significant bit)
+/// with [GateKind::VarBaseMul] gates, and returns the final accumulator.
+/// The number of bits must be a multiple of 5.
+fn var_base_mul(
+    compiler: &mut CircuitWriter<KimchiVesta>,
+    base: [KimchiCellVar; 2],
+    acc: [KimchiCellVar; 2],
+    bits: &[KimchiCellVar],
+    span: Span,
+) -> [KimchiCellVar; 2] {
+    assert_eq!(bits.len() % VAR_BASE_MUL_BITS, 0);
+
+    // the gates also recompose the bits they process
+    let mut num = compiler.backend.add_constant(
+        Some("encoding constant 0 for the scalar of a scalar multiplication"),
+        VestaField::zero(),
+        span,
+    );
+    let mut acc = acc;
+
+    for chunk in bits.chunks(VAR_BASE_MUL_BITS) {
+        // n' = 32 * n + 16 * b0 + 8 * b1 + 4 * b2 + 2 * b3 + b4
+        let mut terms = vec![(VestaField::from(1u64 << VAR_BASE_MUL_BITS), num)];
+        for (ii, bit) in chunk.iter().enumerate() {
+            let power = VestaField::from(1u64 << (VAR_BASE_MUL_BITS - 1 - ii));
+            terms.push((power, *bit));
+        }
+        let next_num = compiler
+            .backend
+            .new_internal_var(Value::LinearCombination(terms, VestaField::zero()), span);
+
+        // the accumulators, and the slopes of the first addition of each step
+        let mut accs = vec![acc];
+        let mut slopes = vec![];
+        for bit in chunk {
+            let inputs = [base[0], base[1], acc[0], acc[1], *bit].map(ConstOrCell::Cell);
+            let [slope, x, y] = [0, 1, 2].map(|idx| {
+                ec::hint(
+                    compiler,
+                    &inputs,
+                    move |values| {
+                        let base = [values[0], values[1]];
+                        let acc = [values[2], values[3]];
+                        double_and_add(acc, base, values[4])
+                            .map(|res| res[idx])
+                            .ok_or_else(|| {
+                                Error::new(
+                                    "runtime",
+                                    ErrorKind::UnexpectedError(
+                                        "a scalar multiplication added equal or opposite points",
+                                    ),
+                                    span,
+                                )
+                            })
+                    },
+                    span,
+                )
+            });
+
+            acc = [x, y];
+            accs.push(acc);
+            slopes.push(slope);
+        }
+
+        let mut first_row = vec![
+            Some(base[0]),
+            Some(base[1]),
+            Some(accs[0][0]),
+            Some(accs[0][1]),
+            Some(num),
+            Some(next_num),
+            None,
+        ];
+        for point in &accs[1..VAR_BASE_MUL_BITS] {
+            first_row.push(Some(point[0]));
+            first_row.push(Some(point[1]));
+        }
+
+        let mut second_row = vec![
+            Some(accs[VAR_BASE_MUL_BITS][0]),
+            Some(accs[VAR_BASE_MUL_BITS][1]),
+        ];
+        second_row.extend(chunk.iter().copied().map(Some));
+        second_row.extend(slopes.into_iter().map(Some));
+
+        compiler.backend.add_gate(
+            "uses a variable-base scalar multiplication gate to process 5 bits of a scalar",
+            GateKind::VarBaseMul,
+            first_row,
+            vec![],
+            span,
+        );
+        compiler.backend.add_gate(
+            "uses a zero gate to store the bits and slopes of a scalar multiplication",
+            GateKind::Zero,
+            second_row,
+            vec![],
+            span,
+        );
+
+        num = next_num;
+    }
+
+    acc
+}
+
+/// Computes `2 * acc + (2 * bit - 1) * base` outside of the circuit, as `(acc + q) + acc`,
+/// and returns the slope of the first addition and the result.
+fn double_and_add(
+    acc: [VestaField; 2],
+    base: [VestaField; 2],
+    bit: VestaField,
+) -> Option<[VestaField; 3]> {
+    let [xa, ya] = acc;
+    let [xt, yt] = base;
+
+    let yq = yt * (bit.double() - VestaField::one());
+    let s1 = (ya - yq) * (xa - xt).inverse()?;
+    let s1_squared = s1.square();
+    let s2 = ya.double() * (xa.double() + xt - s1_squared).inverse()? - s1;
+
+    let x = xt + s2.square() - s1_squared;
+    let y = (xa - x) * s2 - ya;
+
+    Some([s1, x, y])
+}
+
+/// Constrains a point to be on the curve `y^2 = x^3 + 5`,
+/// as the elliptic curve gates assume that their inputs are on the curve.
+/// A constant point is checked at compile time.
+fn assert_on_curve(
+    compiler: &mut CircuitWriter<KimchiVesta>,
+    point: &[ConstOrCell<VestaField, KimchiCellVar>; 2],
+    span: Span,
+) -> Result<()> {
+    if let Some(point) = ec::constant::<KimchiVesta>(point) {
+        if !ec::is_on_curve(point) {
+            return Err(compiler.error(ErrorKind::PointNotOnCurve, span));
+        }
+        return Ok(());
+    }
+
+    let [x, y] = point;
+    let y_squared = field::mul(compiler, y, y, span);
+    let x_squared = field::mul(compiler, x, x, span);
+    let x_cubed = field::mul(compiler, &x_squared[0], x, span);
+    let five = ConstOrCell::Const(VestaField::from(5u64));
+    let rhs = field::add(compiler, &x_cubed[0], &five, span);
+
+    let lhs = to_cell(compiler, &y_squared[0], span);
+    let rhs = to_cell(compiler, &rhs[0], span);
+    compiler.backend.assert_eq_var(&lhs, &rhs, span);
+
+    Ok(())
+}
+
+/// Returns the cells of a point, constraining its coordinates if they are constants.
+fn point_cells(
+    compiler: &mut CircuitWriter<KimchiVesta>,
+    point: &[ConstOrCell<VestaField, KimchiCellVar>; 2],
+    span: Span,
+) -> [KimchiCellVar; 2] {
+    [0, 1].map(|idx| to_cell(compiler, &point[idx], span))
+}
+
+fn to_cell(
+    compiler: &mut CircuitWriter<KimchiVesta>,
+    const_or_cell: &ConstOrCell<VestaField, KimchiCellVar>,
+    span: Span,
+) -> KimchiCellVar {
+    match const_or_cell {
+        ConstOrCell::Const(cst) => compiler.backend.add_constant(
+            Some("encoding constant input to an elliptic curve gate"),
+            *cst,
+            span,
+        ),
+        ConstOrCell::Cell(cell) => *cell,
+    }
+}
+
+fn point_var(point: [KimchiCellVar; 2], span: Span) -> Var<VestaField, KimchiCellVar> {
+    Var::new(point.map(ConstOrCell::Cell).to_vec(), span)
+}
+
+fn constant_point_var(point: [VestaField; 2], span: Span) -> Var<VestaField, KimchiCellVar> {
+    Var::new(point.map(ConstOrCell::Const).to_vec(), span)
 }
@@ -0,0 +1,167 @@
+//! Keccak-256 with the gates of kimchi.
+//!
+//! Unlike the default gadget (see [crate::constraints::keccak]), a 64-bit lane is a single field element.
+//! XORs are done with [GateKind::Xor16](crate::circuit_writer::GateKind::Xor16) gates,
+//! rotations with [GateKind::Rot64](crate::circuit_writer::GateKind::Rot64) gates,
+//! and ANDs are derived from XORs (`a and b = (a + b - (a xor b)) / 2`).
+
+use ark_ff::{Field, Zero};
+
+use super::{bitwise, KimchiCellVar, KimchiVesta, VestaField};
+use crate::{
+    backends::Backend,
+    circuit_writer::{CircuitWriter, VarInfo},
+    constants::Span,
+    constraints::{
+        field,
+        keccak::{padding, RATE, ROTATIONS, ROUND_CONSTANTS},
+    },
+    error::Result,
+    var::{ConstOrCell, Value, Var},
+};
+
+/// A 64-bit lane.
+type Lane = ConstOrCell<VestaField, KimchiCellVar>;
+
+/// Hashes an array of bytes with Keccak-256.
+pub fn keccak256(
+    compiler: &mut CircuitWriter<KimchiVesta>,
+    vars: &[VarInfo<VestaField, KimchiCellVar>],
+    span: Span,
+) -> Result<Option<Var<VestaField, KimchiCellVar>>> {
+    // we get an array of bytes
+    assert_eq!(vars.len(), 1);
+    let input = &vars[0].var;
+
+    // the bytes are range checked, as lanes are recomposed from them
+    let mut message = input.cvars.clone();
+    for byte in &message {
+        if let ConstOrCell::Cell(byte) = byte {
+            compiler.backend.range_check(byte, 8, span);
+        }
+    }
+    for byte in padding(input.len()) {
+        message.push(constant(byte as u64));
+    }
+
+    let mut state: Vec<Lane> = vec![constant(0); 25];
+
+    for block in message.chunks(RATE) {
+        // the bytes of a lane are little-endian
+        for (lane, bytes) in state.iter_mut().zip(block.chunks(8)) {
+            let input = bytes.iter().rev().fold(constant(0), |acc, byte| {
+                let shifted = field::mul(compiler, &acc, &constant(1 << 8), span);
+                field::add(compiler, &shifted[0], byte, span)[0]
+            });
+            *lane = xor(compiler, lane, &input, span);
+        }
+
+        permute(compiler, &mut state, span);
+    }
+
+    // the digest is made of the first 32 bytes of the state
+    let mut digest = vec![];
+    for lane in &state[..4] {
+        digest.extend(to_bytes(compiler, lane, span));
+    }
+
+    Ok(Some(Var::new(digest, span)))
+}
+
+/// The Keccak-f[1600] permutation.
+fn permute(compiler: &mut CircuitWriter<KimchiVesta>, state: &mut [Lane], span: Span) {
+    for round_constant in ROUND_CONSTANTS {
+        // θ
+        let mut columns = vec![];
+        for x in 0..5 {
+            let mut column = state[x];
+            for y in 1..5 {
+                column = xor(compiler, &column, &state[x + 5 * y], span);
+            }
+            columns.push(column);
+        }
+
+        for x in 0..5 {
+            let rotated = bitwise::rotl64(compiler, &columns[(x + 1) % 5], 1, span);
+            let diff = xor(compiler, &columns[(x + 4) % 5], &rotated, span);
+            for y in 0..5 {
+                state[x + 5 * y] = xor(compiler, &state[x + 5 * y], &diff, span);
+            }
+        }
+
+        // ρ and π
+        let mut permuted = vec![constant(0); 25];
+        for x in 0..5 {
+            for y in 0..5 {
+                permuted[y + 5 * ((2 * x + 3 * y) % 5)] =
+                    bitwise::rotl64(compiler, &state[x + 5 * y], ROTATIONS[x][y], span);
+            }
+        }
+
+        // χ
+        for x in 0..5 {
+            for y in 0..5 {
+                let next = &permuted[(x + 1) % 5 + 5 * y];
+                let next_next = &permuted[(x + 2) % 5 + 5 * y];
+
+                let not_next = field::sub(compiler, &constant(u64::MAX), next, span);
+                let res = and(compiler, &not_next[0], next_next, span);
+                state[x + 5 * y] = xor(compiler, &permuted[x + 5 * y], &res, span);
+            }
+        }
+
+        // ι
+        state[0] = xor(compiler, &state[0], &constant(round_constant), span);
+    }
+}
+
+fn constant(value: u64) -> Lane {
+    ConstOrCell::Const(VestaField::from(value))
+}
+
+fn xor(compiler: &mut CircuitWriter<KimchiVesta>, lhs: &Lane, rhs: &Lane, span: Span) -> Lane {
+    bitwise::xor(compiler, lhs, rhs, 64, span)
+}
+
+/// Returns `lhs and rhs`, as `(lhs + rhs - (lhs xor rhs)) / 2`.
+fn and(compiler: &mut CircuitWriter<KimchiVesta>, lhs: &Lane, rhs: &Lane, span: Span) -> Lane {
+    let lhs_xor_rhs = xor(compiler, lhs, rhs, span);
+    let res = field::add(compiler, lhs, rhs, span);
+    let res = field::sub(compiler, &res[0], &lhs_xor_rhs, span);
+    let half = VestaField::from(2u64).inverse().unwrap();
+    field::mul(compiler, &res[0], &ConstOrCell::Const(half), span)[0]
+}
+
+/// Splits a lane in its 8 bytes, from the least significant one.
+/// The bytes are range checked, and must recompose the lane.
+fn to_bytes(compiler: &mut CircuitWriter<KimchiVesta>, lane: &Lane, span: Span) -> Vec<Lane> {
+    let var = match lane {
+        ConstOrCell::Const(cst) => {
+            let value = bitwise::to_u64(*cst);
+            return value
+                .to_le_bytes()
+                .map(|byte| constant(byte as u64))
+                .to_vec();
+        }
+        ConstOrCell::Cell(var) => *var,
+    };
+
+    let mut bytes = vec![];
+    let mut recomposed = ConstOrCell::Const(VestaField::zero());
+    for ii in 0..8 {
+        let byte = compiler
+            .backend
+            .new_internal_var(Value::Bits(var, 8 * ii, 8), span);
+        compiler.backend.range_check(&byte, 8, span);
+
+        let byte = ConstOrCell::Cell(byte);
+        let shifted = field::mul(compiler, &byte, &constant(1 << (8 * ii)), span);
+        recomposed = field::add(compiler, &recomposed, &shifted[0], span)[0];
+        bytes.push(byte);
+    }
+
+    let recomposed = bitwise::to_cell(compiler, &recomposed, span);
+    compiler.backend.assert_eq_var(&recomposed, &var, span);
+
+    bytes
+}
@@ -1,6 +1,9 @@
 pub mod asm;
+pub mod bitwise;
 pub mod builtin;
+pub mod keccak;
 pub mod prover;
+pub mod sha256;
 
 use educe::Educe;
 use std::{
@@ -11,6 +14,7 @@ use std::{
 
 use itertools::{izip, Itertools};
 use kimchi::circuits::polynomials::generic::{GENERIC_COEFFS, GENERIC_REGISTERS};
+use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -23,11 +27,12 @@ use crate::{
     constants::Span,
     error::{Error, ErrorKind, Result},
     helpers::PrettyField,
+    inputs::ExtField,
     var::{Value, Var},
     witness::WitnessEnv,
 };
 
-use ark_ff::{One, Zero};
+use ark_ff::{Field, One, Zero};
 
 use self::asm::{extract_vars_from_coeffs, OrderedHashSet};
 
@@ -131,6 +136,34 @@ impl Witness {
     }
 }
 
+impl GeneratedWitness {
+    /// Encodes the generated witness in JSON, with field elements written in decimal:
+    ///
+    /// ```json
+    /// {
+    ///   "witness": [["1", "0", ...], ...],
+    ///   "full_public_inputs": ["8", "1"],
+    ///   "public_outputs": ["8"]
+    /// }
+    /// ```
+    ///
+    /// - `witness` contains one array of [NUM_REGISTERS] values per row of the circuit.
+    /// - `full_public_inputs` contains the public outputs followed by the public inputs,
+    ///   as expected by the verifier.
+    /// - `public_outputs` contains the public outputs only.
+    pub fn to_json(&self) -> serde_json::Value {
+        let to_dec = |fields: &[VestaField]| -> Vec<String> {
+            fields.iter().map(|field| field.to_dec_string()).collect()
+        };
+
+        serde_json::json!({
+            "witness": self.all_witness.0.iter().map(|row| to_dec(row)).collect::<Vec<_>>(),
+            "full_public_inputs": to_dec(&self.full_public_inputs),
+            "public_outputs": to_dec(&self.public_outputs),
+        })
+    }
+}
+
 impl KimchiVesta {
     pub fn new(double_generic_gate_optimization: bool) -> Self {
         Self {
@@ -242,6 +275,45 @@ impl KimchiVesta {
             });
         }
     }
+
+    /// Constrains a var to fit in 64 bits, using a [GateKind::RangeCheck0] gate.
+    fn range_check_64(&mut self, var: &KimchiCellVar, span: Span) {
+        // the gate checks 88 bits, so the two most significant limbs must be zero.
+        // These limbs are not looked up by the gate, so we wire them to the constant zero.
+        let zero = self.add_constant(
+            Some("hardcode zero for the limbs of a range check"),
+            VestaField::zero(),
+            span,
+        );
+
+        let mut vars = vec![Some(*var), Some(zero), Some(zero)];
+        for col in 3..NUM_REGISTERS {
+            let (start, len) = range_check_slice(col);
+            let limb = self.new_internal_var(Value::Bits(*var, start, len), span);
+            vars.push(Some(limb));
+        }
+
+        self.add_gate(
+            "range check a value to 64 bits",
+            GateKind::RangeCheck0,
+            vars,
+            // the gate is not in compact mode
+            vec![VestaField::zero()],
+            span,
+        );
+    }
+}
+
+/// In a [GateKind::RangeCheck0] gate, column 0 contains the value being checked,
+/// columns 1 to 6 contain its 12-bit limbs and columns 7 to 14 contain its 2-bit crumbs,
+/// from the most significant bits to the least significant ones.
+/// This returns the position of the bits stored in the given column, as `(start, len)`.
+fn range_check_slice(col: usize) -> (usize, usize) {
+    match col {
+        1..=6 => (88 - 12 * col, 12),
+        7..=14 => (2 * (14 - col), 2),
+        _ => unreachable!("column {col} does not contain a limb"),
+    }
 }
 
 #[derive(Default, Clone, Copy, Debug, Eq, Hash, Serialize, Deserialize, PartialEq, Ord, Educe)]
@@ -269,6 +341,30 @@ impl Backend for KimchiVesta {
         builtin::poseidon
     }
 
+    fn poseidon_permutation() -> crate::imports::PermutationHandle<Self> {
+        builtin::poseidon_permutation
+    }
+
+    fn ec_add() -> Option<crate::imports::FnHandle<Self>> {
+        Some(builtin::ec_add)
+    }
+
+    fn ec_double() -> Option<crate::imports::FnHandle<Self>> {
+        Some(builtin::ec_double)
+    }
+
+    fn ec_scale() -> Option<crate::imports::FnHandle<Self>> {
+        Some(builtin::ec_scale)
+    }
+
+    fn sha256() -> crate::imports::FnHandle<Self> {
+        sha256::sha256
+    }
+
+    fn keccak256() -> crate::imports::FnHandle<Self> {
+        keccak::keccak256
+    }
+
     fn new_internal_var(&mut self, val: Value<KimchiVesta>, span: Span) -> KimchiCellVar {
         // create new var
         let var = KimchiCellVar::new(self.next_variable, span);
@@ -441,7 +537,6 @@ impl Backend for KimchiVesta {
         {
             let is_not_public_input = row >= self.public_input_size;
             if is_not_public_input {
-                #[allow(clippy::single_match)]
                 match gate.typ {
                     // only check the generic gate
                     crate::circuit_writer::GateKind::DoubleGeneric => {
@@ -464,6 +559,81 @@ impl Backend for KimchiVesta {
                             ));
                         }
                     }
+                    // check that the limbs recompose the value
+                    // (the lookups on the limbs are checked by the prover)
+                    crate::circuit_writer::GateKind::RangeCheck0 => {
+                        let recomposed =
+                            (1..NUM_REGISTERS).fold(Self::Field::zero(), |acc, col| {
+                                let (start, _) = range_check_slice(col);
+                                acc + witness_row[col] * Self::Field::from(1u128 << start)
+                            });
+                        if recomposed != witness_row[0] {
+                            return Err(Error::new(
+                                "runtime",
+                                ErrorKind::InvalidWitness(row),
+                                debug_info.span,
+                            ));
+                        }
+                    }
+                    // check that the nibbles and the next row recompose the inputs and the output,
+                    // and that the nibbles of the output are the XOR of the nibbles of the inputs
+                    // (which the prover checks with lookups)
+                    crate::circuit_writer::GateKind::Xor16 => {
+                        let next_row = &witness[row + 1];
+                        let recomposes = (0..3).all(|col| {
+                            let recomposed = (0..4).fold(
+                                next_row[col] * Self::Field::from(1u64 << 16),
+                                |acc, ii| {
+                                    let nibble = witness_row[3 + 4 * col + ii];
+                                    acc + nibble * Self::Field::from(1u64 << (4 * ii))
+                                },
+                            );
+                            recomposed == witness_row[col]
+                        });
+
+                        let nibbles: Vec<BigUint> = witness_row[3..]
+                            .iter()
+                            .map(|nibble| (*nibble).into())
+                            .collect();
+                        let xors = (0..4).all(|ii| {
+                            let (in1, in2, out) =
+                                (&nibbles[ii], &nibbles[4 + ii], &nibbles[8 + ii]);
+                            in1.bits() <= 4 && in2.bits() <= 4 && *out == in1 ^ in2
+                        });
+
+                        if !recomposes || !xors {
+                            return Err(Error::new(
+                                "runtime",
+                                ErrorKind::InvalidWitness(row),
+                                debug_info.span,
+                            ));
+                        }
+                    }
+                    // check the rotation with the shifted value of the next row,
+                    // and that the limbs of the bound recompose `excess + 2^64 - 2^amount`
+                    // (the lookups on the limbs are checked by the prover)
+                    crate::circuit_writer::GateKind::Rot64 => {
+                        let shift = gate.coeffs[0];
+                        let two_to_64 = Self::Field::from(1u128 << 64);
+                        let (word, rotated, excess) =
+                            (witness_row[0], witness_row[1], witness_row[2]);
+                        let shifted = witness[row + 1][0];
+                        let bound = (3..NUM_REGISTERS).fold(Self::Field::zero(), |acc, col| {
+                            let (start, _) = range_check_slice(col);
+                            acc + witness_row[col] * Self::Field::from(1u128 << start)
+                        });
+
+                        if word * shift != excess * two_to_64 + shifted
+                            || rotated != shifted + excess
+                            || bound != excess + two_to_64 - shift
+                        {
+                            return Err(Error::new(
+                                "runtime",
+                                ErrorKind::InvalidWitness(row),
+                                debug_info.span,
+                            ));
+                        }
+                    }
                     // for all other gates, we trust the gadgets
                     _ => (),
                 }
@@ -714,6 +884,71 @@ impl Backend for KimchiVesta {
         );
     }
 
+    fn range_check(&mut self, var: &KimchiCellVar, num_bits: usize, span: Span) {
+        assert!(num_bits <= 64);
+
+        // a range check gate checks that the var fits in 64 bits
+        self.range_check_64(var, span);
+
+        // for smaller ranges, we also check that `var + 2^64 - 2^num_bits` fits in 64 bits
+        // (which can only be true if `var < 2^num_bits`)
+        if num_bits < 64 {
+            let offset = (1u128 << 64) - (1u128 << num_bits);
+            let shifted = self.add_const(var, &Self::Field::from(offset), span);
+            self.range_check_64(&shifted, span);
+        }
+    }
+
+    fn to_bits(&mut self, var: &KimchiCellVar, num_bits: usize, span: Span) -> Vec<KimchiCellVar> {
+        let zero = Self::Field::zero();
+        let one = Self::Field::one();
+
+        let mut bits = Vec::with_capacity(num_bits);
+        let mut sum: Option<KimchiCellVar> = None;
+        let mut power = one;
+
+        for i in 0..num_bits {
+            let bit = self.new_internal_var(Value::Bits(*var, i, 1), span);
+
+            // b * b - b = 0
+            self.add_generic_gate(
+                "constrain a bit to be boolean",
+                vec![Some(bit), Some(bit)],
+                vec![one.neg(), zero, zero, one],
+                span,
+            );
+
+            // sum_{i+1} = sum_i + 2^i * b_i
+            sum = Some(match sum {
+                None => bit,
+                Some(acc) => {
+                    let res = self.new_internal_var(
+                        Value::LinearCombination(vec![(one, acc), (power, bit)], zero),
+                        span,
+                    );
+                    self.add_generic_gate(
+                        "accumulate the bits of a decomposition",
+                        vec![Some(acc), Some(bit), Some(res)],
+                        vec![one, power, one.neg()],
+                        span,
+                    );
+                    res
+                }
+            });
+
+            power.double_in_place();
+            bits.push(bit);
+        }
+
+        // the bits must recompose the var
+        match sum {
+            Some(sum) => self.assert_eq_var(&sum, var, span),
+            None => self.assert_eq_const(var, zero, span),
+        }
+
+        bits
+    }
+
     fn add_public_input(&mut self, val: Value<Self>, span: Span) -> KimchiCellVar {
         // create the var
         let cvar = self.new_internal_var(val, span);
@@ -3,14 +3,20 @@
 use std::iter::once;
 
 use crate::{
-    backends::kimchi::{KimchiVesta, VestaField},
+    backends::{
+        artifacts::{read_artifact, write_artifact},
+        kimchi::{KimchiVesta, VestaField},
+    },
     circuit_writer::Wiring,
     compiler::{generate_witness, Sources},
-    inputs::JsonInputs,
+    inputs::{JsonInputs, PublicIO},
     witness::CompiledCircuit,
 };
 
+use camino::Utf8Path;
 use itertools::chain;
+use kimchi::circuits::{constraints::FeatureFlags, gate::CircuitGate};
+use kimchi::linearization::expr_linearization;
 use kimchi::mina_curves::pasta::{Vesta, VestaParameters};
 use kimchi::mina_poseidon::constants::PlonkSpongeConstantsKimchi;
 use kimchi::mina_poseidon::sponge::{DefaultFqSponge, DefaultFrSponge};
@@ -25,6 +31,7 @@ use kimchi::{
 use miette::{Context, IntoDiagnostic};
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 //
 // aliases
@@ -36,6 +43,13 @@ type SpongeParams = PlonkSpongeConstantsKimchi;
 type BaseSponge = DefaultFqSponge<VestaParameters, SpongeParams>;
 type ScalarSponge = DefaultFrSponge<kimchi::mina_curves::pasta::Fp, SpongeParams>;
 
+//
+// Constants
+//
+
+/// The name of the backend (as used by the CLI).
+const BACKEND_NAME: &str = "kimchi-vesta";
+
 //
 // Lazy static
 //
@@ -58,17 +72,42 @@ pub struct VerifierIndex {
     index: kimchi::verifier_index::VerifierIndex<Curve, OpeningProof<Curve>>,
 }
 
+/// A circuit in kimchi's format: the (wired) gates and the number of public inputs.
+/// The prover and verifier indexes are deterministically derived from it.
+#[derive(Serialize, Deserialize)]
+pub struct KimchiCircuit {
+    gates: Vec<CircuitGate<VestaField>>,
+    public_input_size: usize,
+}
+
+//
+// Artifacts
+//
+
+/// The content of a `prover.nope` artifact: kimchi's prover index.
+/// Kimchi does not serialize the SRS and the linearization of the index, they are recreated on load.
+#[derive(Serialize, Deserialize)]
+struct ProverArtifact {
+    index: kimchi::prover_index::ProverIndex<Curve, OpeningProof<Curve>>,
+}
+
+/// The content of a `verifier.nope` artifact.
+/// It contains everything needed to verify a proof, so that verifiers don't need the source code.
+#[derive(Serialize, Deserialize)]
+struct VerifierArtifact {
+    index: kimchi::verifier_index::VerifierIndex<Curve, OpeningProof<Curve>>,
+    /// The gates used by the circuit, needed to recreate the linearization of the index.
+    feature_flags: FeatureFlags,
+    public_io: PublicIO,
+}
+
 //
 // Setup
 //
 
 impl KimchiVesta {
-    pub fn compile_to_indexes(
-        &self,
-    ) -> miette::Result<(
-        kimchi::prover_index::ProverIndex<Curve, OpeningProof<Curve>>,
-        kimchi::verifier_index::VerifierIndex<Curve, OpeningProof<Curve>>,
-    )> {
+    /// Converts the circuit to kimchi's format, wiring included.
+    pub fn to_kimchi_circuit(&self) -> KimchiCircuit {
         // convert gates to kimchi gates
         let mut gates: Vec<_> = self
             .gates
@@ -99,25 +138,50 @@ impl KimchiVesta {
             }
         }
 
+        KimchiCircuit {
+            gates,
+            public_input_size: self.public_input_size,
+        }
+    }
+
+    pub fn compile_to_indexes(
+        &self,
+    ) -> miette::Result<(
+        kimchi::prover_index::ProverIndex<Curve, OpeningProof<Curve>>,
+        kimchi::verifier_index::VerifierIndex<Curve, OpeningProof<Curve>>,
+    )> {
+        self.to_kimchi_circuit().compile_to_indexes()
+    }
+}
+
+impl KimchiCircuit {
+    /// Returns the SHA-256 hash of the serialized circuit.
+    pub fn hash(&self) -> [u8; 32] {
+        let bytes = rmp_serde::to_vec(self).expect("couldn't serialize the circuit");
+        Sha256::digest(bytes).into()
+    }
+
+    pub fn compile_to_indexes(
+        self,
+    ) -> miette::Result<(
+        kimchi::prover_index::ProverIndex<Curve, OpeningProof<Curve>>,
+        kimchi::verifier_index::VerifierIndex<Curve, OpeningProof<Curve>>,
+    )> {
         // create constraint system
-        let cs = ConstraintSystem::create(gates)
+        let cs = ConstraintSystem::create(self.gates)
             .public(self.public_input_size)
             .build()
             .into_diagnostic()
             .wrap_err("kimchi: could not create a constraint system with the given circuit and public input size")?;
 
-        // create SRS (for vesta, as the circuit is in Fp)
-        let mut srs = SRS::<Curve>::create(cs.domain.d1.size as usize);
+        let mut srs = create_srs(cs.domain.d1.size as usize);
         srs.add_lagrange_basis(cs.domain.d1);
-        let srs = std::sync::Arc::new(srs);
-
-        println!("using an SRS of size {}", srs.g.len());
 
         // create indexes
-        let (endo_q, _endo_r) = kimchi::poly_commitment::srs::endos::<OtherCurve>();
-
         let prover_index = kimchi::prover_index::ProverIndex::<Curve, OpeningProof<Curve>>::create(
-            cs, endo_q, srs,
+            cs,
+            endo_q(),
+            std::sync::Arc::new(srs),
         );
         let verifier_index = prover_index.verifier_index();
 
@@ -125,6 +189,21 @@ impl KimchiVesta {
     }
 }
 
+/// Creates an SRS of the given size (for vesta, as the circuit is in Fp).
+fn create_srs(size: usize) -> SRS<Curve> {
+    let srs = SRS::<Curve>::create(size);
+
+    println!("using an SRS of size {}", srs.g.len());
+
+    srs
+}
+
+/// The endomorphism coefficient used by the indexes.
+fn endo_q() -> VestaField {
+    let (endo_q, _endo_r) = kimchi::poly_commitment::srs::endos::<OtherCurve>();
+    endo_q
+}
+
 impl CompiledCircuit<KimchiVesta> {
     pub fn compile_to_indexes(self) -> miette::Result<(ProverIndex, VerifierIndex)> {
         let (prover_index, verifier_index) = self.circuit.backend.compile_to_indexes()?;
@@ -142,6 +221,60 @@ impl CompiledCircuit<KimchiVesta> {
         // return asm + indexes
         Ok((prover_index, verifier_index))
     }
+
+    /// Creates the prover and verifier indexes of the circuit,
+    /// and writes them as artifacts to the given paths.
+    pub fn write_artifacts(
+        &self,
+        prover_path: &Utf8Path,
+        verifier_path: &Utf8Path,
+    ) -> miette::Result<()> {
+        let circuit = self.circuit.backend.to_kimchi_circuit();
+        let circuit_hash = circuit.hash();
+        let (prover_index, verifier_index) = circuit.compile_to_indexes()?;
+
+        let verifier_artifact = VerifierArtifact {
+            index: verifier_index,
+            feature_flags: prover_index.cs.feature_flags,
+            public_io: self.public_io(),
+        };
+        write_artifact(
+            verifier_path,
+            BACKEND_NAME,
+            circuit_hash,
+            &verifier_artifact,
+        )?;
+
+        let prover_artifact = ProverArtifact {
+            index: prover_index,
+        };
+        write_artifact(prover_path, BACKEND_NAME, circuit_hash, &prover_artifact)
+    }
+
+    /// Loads the prover index from the prover artifact at the given path.
+    /// The artifact must have been built from the same circuit.
+    pub fn prover_index_from_artifact(self, path: &Utf8Path) -> miette::Result<ProverIndex> {
+        let (header, artifact): (_, ProverArtifact) = read_artifact(path, BACKEND_NAME)?;
+
+        if header.circuit_hash != self.circuit.backend.to_kimchi_circuit().hash() {
+            miette::bail!("the prover artifact at `{path}` does not match the circuit (it is probably stale). Please run `noname build` again.");
+        }
+
+        // recreate what kimchi does not serialize
+        let mut index = artifact.index;
+        let (linearization, powers_of_alpha) =
+            expr_linearization(Some(&index.cs.feature_flags), true);
+        index.linearization = linearization;
+        index.powers_of_alpha = powers_of_alpha;
+        let mut srs = create_srs(index.cs.domain.d1.size as usize);
+        srs.add_lagrange_basis(index.cs.domain.d1);
+        index.srs = std::sync::Arc::new(srs);
+
+        Ok(ProverIndex {
+            index,
+            compiled_circuit: self,
+        })
+    }
 }
 
 //
@@ -153,6 +286,16 @@ impl ProverIndex {
         self.compiled_circuit.asm(sources, debug)
     }
 
+    pub fn compiled_circuit(&self) -> &CompiledCircuit<KimchiVesta> {
+        &self.compiled_circuit
+    }
+
+    pub fn verifier_index(&self) -> VerifierIndex {
+        VerifierIndex {
+            index: self.index.verifier_index(),
+        }
+    }
+
     pub fn len(&self) -> usize {
         self.compiled_circuit.circuit.backend.gates.len()
     }
@@ -217,6 +360,27 @@ impl ProverIndex {
 //
 
 impl VerifierIndex {
+    /// Loads a verifier index from the verifier artifact at the given path,
+    /// along with the layout of the public inputs and output.
+    /// This does not require the source code of the circuit.
+    pub fn from_artifact(path: &Utf8Path) -> miette::Result<(Self, PublicIO)> {
+        let (_, artifact): (_, VerifierArtifact) = read_artifact(path, BACKEND_NAME)?;
+
+        // recreate what kimchi does not serialize
+        let mut index = artifact.index;
+        let (linearization, powers_of_alpha) =
+            expr_linearization(Some(&artifact.feature_flags), true);
+        index.linearization = linearization;
+        index.powers_of_alpha = powers_of_alpha;
+        index.endo = endo_q();
+
+        let mut srs = create_srs(index.domain.size as usize);
+        srs.add_lagrange_basis(index.domain);
+        index.srs = std::sync::Arc::new(srs);
+
+        Ok((Self { index }, artifact.public_io))
+    }
+
     pub fn verify(
         &self,
         full_public_inputs: Vec<VestaField>,
@@ -239,7 +403,7 @@ mod tests {
     use kimchi::circuits::constraints::GateError;
 
     use crate::{
-        backends::kimchi::{KimchiVesta, VestaField},
+        backends::kimchi::{prover::VerifierIndex, KimchiVesta, VestaField, NUM_REGISTERS},
         compiler::{compile, generate_witness, typecheck_next_file, Sources},
         inputs::parse_inputs,
         type_checker::TypeChecker,
@@ -361,4 +525,157 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_witness_json() -> miette::Result<()> {
+        let code = r#"fn main(pub public_input: Field, private_input: Field) -> Field {
+            return private_input + public_input;
+        }"#;
+
+        let mut sources = Sources::new();
+        let mut tast = TypeChecker::new();
+        typecheck_next_file(
+            &mut tast,
+            None,
+            &mut sources,
+            "inline_test_witness.no".to_string(),
+            code.to_owned(),
+            0,
+        )?;
+        let compiled_circuit = compile(&sources, tast, KimchiVesta::new(false))?;
+
+        let public_inputs = parse_inputs(r#"{"public_input": "1"}"#).unwrap();
+        let private_inputs = parse_inputs(r#"{"private_input": "2"}"#).unwrap();
+        let generated_witness =
+            generate_witness(&compiled_circuit, &sources, public_inputs, private_inputs)?;
+
+        let json = generated_witness.to_json();
+        assert_eq!(json["full_public_inputs"], serde_json::json!(["3", "1"]));
+        assert_eq!(json["public_outputs"], serde_json::json!(["3"]));
+
+        let rows = json["witness"].as_array().unwrap();
+        assert_eq!(rows.len(), generated_witness.all_witness.len());
+        assert!(rows
+            .iter()
+            .all(|row| row.as_array().unwrap().len() == NUM_REGISTERS));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_with_public_output() -> miette::Result<()> {
+        let code = r#"fn main(pub public_input: Field, private_input: Field) -> Field {
+            let xx = private_input + public_input;
+            assert_eq(xx, 2);
+            let yy = xx + 6;
+            return yy;
+        }"#;
+
+        let mut sources = Sources::new();
+        let mut tast = TypeChecker::new();
+        let _node_id = typecheck_next_file(
+            &mut tast,
+            None,
+            &mut sources,
+            "inline_test_verify.no".to_string(),
+            code.to_owned(),
+            0,
+        )
+        .unwrap();
+
+        let kimchi_vesta = KimchiVesta::new(false);
+        let compiled_circuit = compile(&sources, tast, kimchi_vesta)?;
+        let (prover_index, verifier_index) = compiled_circuit.compile_to_indexes().unwrap();
+
+        // create a proof
+        let public_inputs = parse_inputs(r#"{"public_input": "1"}"#).unwrap();
+        let private_inputs = parse_inputs(r#"{"private_input": "1"}"#).unwrap();
+        let (proof, full_public_inputs, _) =
+            prover_index.prove(&sources, public_inputs.clone(), private_inputs, false)?;
+
+        // the verifier rebuilds the full public input from JSON
+        let compiled_circuit = prover_index.compiled_circuit();
+        let rebuilt = compiled_circuit
+            .full_public_inputs(public_inputs.clone(), Some(serde_json::json!("8")))
+            .unwrap();
+        assert_eq!(rebuilt, full_public_inputs);
+        verifier_index.verify(rebuilt, proof.clone())?;
+
+        // a wrong public output should not verify
+        let wrong = compiled_circuit
+            .full_public_inputs(public_inputs.clone(), Some(serde_json::json!("9")))
+            .unwrap();
+        assert!(verifier_index.verify(wrong, proof).is_err());
+
+        // a missing public output is an error
+        let res = compiled_circuit.full_public_inputs(public_inputs, None);
+        assert!(matches!(
+            res.unwrap_err().kind,
+            crate::error::ErrorKind::MissingPublicOutput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_artifacts_roundtrip() -> miette::Result<()> {
+        let compile_code = |code: &str| -> miette::Result<_> {
+            let mut sources = Sources::new();
+            let mut tast = TypeChecker::new();
+            typecheck_next_file(
+                &mut tast,
+                None,
+                &mut sources,
+                "inline_test_artifacts.no".to_string(),
+                code.to_owned(),
+                0,
+            )?;
+            let compiled_circuit = compile(&sources, tast, KimchiVesta::new(false))?;
+            Ok((sources, compiled_circuit))
+        };
+
+        let code = r#"fn main(pub public_input: Field, private_input: Field) -> Field {
+            let xx = private_input + public_input;
+            return xx * 2;
+        }"#;
+
+        let dir = std::env::temp_dir().join(format!("noname_artifacts_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let dir = camino::Utf8PathBuf::from_path_buf(dir).unwrap();
+        let prover_path = dir.join("prover.nope");
+        let verifier_path = dir.join("verifier.nope");
+
+        // build
+        let (_, compiled_circuit) = compile_code(code)?;
+        compiled_circuit.write_artifacts(&prover_path, &verifier_path)?;
+
+        // prove, recompiling the circuit but not the indexes
+        let (sources, compiled_circuit) = compile_code(code)?;
+        let prover_index = compiled_circuit.prover_index_from_artifact(&prover_path)?;
+        let public_inputs = parse_inputs(r#"{"public_input": "2"}"#).unwrap();
+        let private_inputs = parse_inputs(r#"{"private_input": "3"}"#).unwrap();
+        let (proof, _, _) =
+            prover_index.prove(&sources, public_inputs.clone(), private_inputs, false)?;
+
+        // verify, from the verifier artifact only
+        let (verifier_index, public_io) = VerifierIndex::from_artifact(&verifier_path)?;
+        let full_public_inputs = public_io
+            .full_public_inputs(public_inputs, Some(serde_json::json!("10")))
+            .unwrap();
+        verifier_index.verify(full_public_inputs, proof)?;
+
+        // a stale prover artifact is rejected
+        let (_, other_circuit) = compile_code(
+            r#"fn main(pub public_input: Field, private_input: Field) -> Field {
+            return private_input + public_input;
+        }"#,
+        )?;
+        assert!(other_circuit
+            .prover_index_from_artifact(&prover_path)
+            .is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        Ok(())
+    }
 }
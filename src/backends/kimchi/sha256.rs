@@ -0,0 +1,305 @@
+//! SHA-256 with the gates of kimchi.
+//!
+//! Unlike the default gadget (see [crate::constraints::sha256]), a 32-bit word is a single field element.
+//! XORs are done with [GateKind::Xor16](crate::circuit_writer::GateKind::Xor16) gates,
+//! and ANDs are derived from them (`a and b = (a + b - (a xor b)) / 2`).
+//! Rotations, shifts and additions modulo `2^32` split words in parts
+//! that are range checked with [GateKind::RangeCheck0](crate::circuit_writer::GateKind::RangeCheck0) gates.
+
+use ark_ff::Field;
+
+use super::{bitwise, KimchiCellVar, KimchiVesta, VestaField};
+use crate::{
+    backends::Backend,
+    circuit_writer::{CircuitWriter, VarInfo},
+    constants::Span,
+    constraints::{field, sha256::padding, sha256::H0, sha256::K},
+    error::Result,
+    var::{ConstOrCell, Value, Var},
+};
+
+/// A 32-bit word.
+type Word = ConstOrCell<VestaField, KimchiCellVar>;
+
+/// Hashes an array of bytes with SHA-256.
+pub fn sha256(
+    compiler: &mut CircuitWriter<KimchiVesta>,
+    vars: &[VarInfo<VestaField, KimchiCellVar>],
+    span: Span,
+) -> Result<Option<Var<VestaField, KimchiCellVar>>> {
+    // we get an array of bytes
+    assert_eq!(vars.len(), 1);
+    let input = &vars[0].var;
+
+    // the bytes are range checked, as words are recomposed from them
+    let mut message = input.cvars.clone();
+    for byte in &message {
+        if let ConstOrCell::Cell(byte) = byte {
+            compiler.backend.range_check(byte, 8, span);
+        }
+    }
+    for byte in padding(input.len()) {
+        message.push(constant(byte as u32));
+    }
+
+    let mut state: Vec<Word> = H0.iter().map(|hh| constant(*hh)).collect();
+
+    for block in message.chunks(64) {
+        state = compress(compiler, &state, block, span);
+    }
+
+    // the words of the state are big-endian
+    let mut digest = vec![];
+    for word in &state {
+        let (high, low) = split(compiler, word, 16, 16, span);
+        for half in [high, low] {
+            let (high, low) = split(compiler, &half, 8, 8, span);
+            digest.extend([high, low]);
+        }
+    }
+
+    Ok(Some(Var::new(digest, span)))
+}
+
+/// Processes a block of 64 bytes.
+fn compress(
+    compiler: &mut CircuitWriter<KimchiVesta>,
+    state: &[Word],
+    block: &[Word],
+    span: Span,
+) -> Vec<Word> {
+    // message schedule (the bytes of a word are big-endian)
+    let mut schedule: Vec<Word> = block
+        .chunks(4)
+        .map(|bytes| {
+            bytes.iter().fold(constant(0), |acc, byte| {
+                let shifted = field::mul(compiler, &acc, &constant(1 << 8), span);
+                field::add(compiler, &shifted[0], byte, span)[0]
+            })
+        })
+        .collect();
+
+    for tt in 16..64 {
+        let s0 = {
+            let rot7 = rotr(compiler, &schedule[tt - 15], 7, span);
+            let rot18 = rotr(compiler, &schedule[tt - 15], 18, span);
+            let shr3 = shr(compiler, &schedule[tt - 15], 3, span);
+            xor3(compiler, &rot7, &rot18, &shr3, span)
+        };
+        let s1 = {
+            let rot17 = rotr(compiler, &schedule[tt - 2], 17, span);
+            let rot19 = rotr(compiler, &schedule[tt - 2], 19, span);
+            let shr10 = shr(compiler, &schedule[tt - 2], 10, span);
+            xor3(compiler, &rot17, &rot19, &shr10, span)
+        };
+
+        let word = add_words(
+            compiler,
+            &[&s1, &schedule[tt - 7], &s0, &schedule[tt - 16]],
+            span,
+        );
+        schedule.push(word);
+    }
+
+    // rounds
+    let [mut aa, mut bb, mut cc, mut dd, mut ee, mut ff, mut gg, mut hh] =
+        <[Word; 8]>::try_from(state.to_vec())
+            .unwrap_or_else(|_| unreachable!("the state has 8 words"));
+
+    for (round_constant, word) in K.iter().zip(&schedule) {
+        let sigma1 = {
+            let rot6 = rotr(compiler, &ee, 6, span);
+            let rot11 = rotr(compiler, &ee, 11, span);
+            let rot25 = rotr(compiler, &ee, 25, span);
+            xor3(compiler, &rot6, &rot11, &rot25, span)
+        };
+        let choice = ch(compiler, &ee, &ff, &gg, span);
+        let round_constant = constant(*round_constant);
+        let temp1 = sum(
+            compiler,
+            &[&hh, &sigma1, &choice, &round_constant, word],
+            span,
+        );
+
+        let sigma0 = {
+            let rot2 = rotr(compiler, &aa, 2, span);
+            let rot13 = rotr(compiler, &aa, 13, span);
+            let rot22 = rotr(compiler, &aa, 22, span);
+            xor3(compiler, &rot2, &rot13, &rot22, span)
+        };
+        let majority = maj(compiler, &aa, &bb, &cc, span);
+        let temp2 = sum(compiler, &[&sigma0, &majority], span);
+
+        hh = gg;
+        gg = ff;
+        ff = ee;
+
+        // d + temp1 is the sum of 6 words
+        let new_ee = field::add(compiler, &dd, &temp1, span);
+        ee = truncate(compiler, &new_ee[0], 6, span);
+
+        dd = cc;
+        cc = bb;
+        bb = aa;
+
+        // temp1 + temp2 is the sum of 7 words
+        let new_aa = field::add(compiler, &temp1, &temp2, span);
+        aa = truncate(compiler, &new_aa[0], 7, span);
+    }
+
+    state
+        .iter()
+        .zip([aa, bb, cc, dd, ee, ff, gg, hh])
+        .map(|(word, var)| add_words(compiler, &[word, &var], span))
+        .collect()
+}
+
+fn constant(value: u32) -> Word {
+    ConstOrCell::Const(VestaField::from(value))
+}
+
+/// Splits a value in the `high_bits` bits above its `num_bits` lower bits, and these lower bits.
+/// Both parts are range checked, so the value must fit in `num_bits + high_bits` bits.
+fn split(
+    compiler: &mut CircuitWriter<KimchiVesta>,
+    value: &Word,
+    num_bits: usize,
+    high_bits: usize,
+    span: Span,
+) -> (Word, Word) {
+    let var = match value {
+        ConstOrCell::Const(cst) => {
+            let value = bitwise::to_u64(*cst);
+            return (
+                ConstOrCell::Const(VestaField::from(value >> num_bits)),
+                ConstOrCell::Const(VestaField::from(value & ((1 << num_bits) - 1))),
+            );
+        }
+        ConstOrCell::Cell(var) => *var,
+    };
+
+    let high = compiler
+        .backend
+        .new_internal_var(Value::Bits(var, num_bits, high_bits), span);
+    let low = compiler
+        .backend
+        .new_internal_var(Value::Bits(var, 0, num_bits), span);
+    compiler.backend.range_check(&high, high_bits, span);
+    compiler.backend.range_check(&low, num_bits, span);
+
+    // value = high * 2^num_bits + low
+    let shift = VestaField::from(1u64 << num_bits);
+    let shifted = compiler.backend.mul_const(&high, &shift, span);
+    let recomposed = compiler.backend.add(&shifted, &low, span);
+    compiler.backend.assert_eq_var(&recomposed, &var, span);
+
+    (ConstOrCell::Cell(high), ConstOrCell::Cell(low))
+}
+
+/// Rotates a word to the right.
+fn rotr(compiler: &mut CircuitWriter<KimchiVesta>, word: &Word, amount: usize, span: Span) -> Word {
+    let (high, low) = split(compiler, word, amount, 32 - amount, span);
+    let shift = ConstOrCell::Const(VestaField::from(1u64 << (32 - amount)));
+    let shifted = field::mul(compiler, &low, &shift, span);
+    field::add(compiler, &shifted[0], &high, span)[0]
+}
+
+/// Shifts a word to the right.
+fn shr(compiler: &mut CircuitWriter<KimchiVesta>, word: &Word, amount: usize, span: Span) -> Word {
+    split(compiler, word, amount, 32 - amount, span).0
+}
+
+fn xor(compiler: &mut CircuitWriter<KimchiVesta>, lhs: &Word, rhs: &Word, span: Span) -> Word {
+    bitwise::xor(compiler, lhs, rhs, 32, span)
+}
+
+fn xor3(
+    compiler: &mut CircuitWriter<KimchiVesta>,
+    aa: &Word,
+    bb: &Word,
+    cc: &Word,
+    span: Span,
+) -> Word {
+    let res = xor(compiler, aa, bb, span);
+    xor(compiler, &res, cc, span)
+}
+
+/// Returns `lhs and rhs` from `lhs xor rhs`, as `(lhs + rhs - (lhs xor rhs)) / 2`.
+fn and_from_xor(
+    compiler: &mut CircuitWriter<KimchiVesta>,
+    lhs: &Word,
+    rhs: &Word,
+    lhs_xor_rhs: &Word,
+    span: Span,
+) -> Word {
+    let res = field::add(compiler, lhs, rhs, span);
+    let res = field::sub(compiler, &res[0], lhs_xor_rhs, span);
+    let half = VestaField::from(2u64).inverse().unwrap();
+    field::mul(compiler, &res[0], &ConstOrCell::Const(half), span)[0]
+}
+
+fn and(compiler: &mut CircuitWriter<KimchiVesta>, lhs: &Word, rhs: &Word, span: Span) -> Word {
+    let lhs_xor_rhs = xor(compiler, lhs, rhs, span);
+    and_from_xor(compiler, lhs, rhs, &lhs_xor_rhs, span)
+}
+
+/// `ch(e, f, g) = (e and f) xor (not e and g)`,
+/// computed as `(e and f) + (not e and g)` as the two terms don't have bits in common.
+fn ch(
+    compiler: &mut CircuitWriter<KimchiVesta>,
+    ee: &Word,
+    ff: &Word,
+    gg: &Word,
+    span: Span,
+) -> Word {
+    let ee_and_ff = and(compiler, ee, ff, span);
+    let not_ee = field::sub(compiler, &constant(u32::MAX), ee, span);
+    let not_ee_and_gg = and(compiler, &not_ee[0], gg, span);
+    field::add(compiler, &ee_and_ff, &not_ee_and_gg, span)[0]
+}
+
+/// `maj(a, b, c) = (a and b) xor (a and c) xor (b and c)`,
+/// computed as `(a and b) + (c and (a xor b))` as the two terms don't have bits in common.
+fn maj(
+    compiler: &mut CircuitWriter<KimchiVesta>,
+    aa: &Word,
+    bb: &Word,
+    cc: &Word,
+    span: Span,
+) -> Word {
+    let aa_xor_bb = xor(compiler, aa, bb, span);
+    let aa_and_bb = and_from_xor(compiler, aa, bb, &aa_xor_bb, span);
+    let cc_and_xor = and(compiler, cc, &aa_xor_bb, span);
+    field::add(compiler, &aa_and_bb, &cc_and_xor, span)[0]
+}
+
+/// Returns the sum of words, as a field element.
+fn sum(compiler: &mut CircuitWriter<KimchiVesta>, words: &[&Word], span: Span) -> Word {
+    words.iter().fold(constant(0), |acc, word| {
+        field::add(compiler, &acc, word, span)[0]
+    })
+}
+
+/// Adds words modulo `2^32`.
+fn add_words(compiler: &mut CircuitWriter<KimchiVesta>, words: &[&Word], span: Span) -> Word {
+    let res = sum(compiler, words, span);
+    truncate(compiler, &res, words.len(), span)
+}
+
+/// Returns the 32 least significant bits of a sum of `num_words` words.
+fn truncate(
+    compiler: &mut CircuitWriter<KimchiVesta>,
+    sum: &Word,
+    num_words: usize,
+    span: Span,
+) -> Word {
+    // the sum fits in `32 + log2(num_words)` bits
+    let carry_bits = (usize::BITS - (num_words - 1).leading_zeros()) as usize;
+
+    if let ConstOrCell::Const(cst) = sum {
+        let value = bitwise::to_u64(*cst) as u32;
+        return constant(value);
+    }
+
+    split(compiler, sum, 32, carry_bits, span).1
+}
@@ -1,6 +1,6 @@
 use std::{fmt::Debug, hash::Hash, str::FromStr};
 
-use ark_ff::{Field, Zero};
+use ark_ff::{Field, One, Zero};
 use num_bigint::BigUint;
 
 use crate::{
@@ -8,7 +8,7 @@ use crate::{
     constants::Span,
     error::{Error, ErrorKind, Result},
     helpers::PrettyField,
-    imports::FnHandle,
+    imports::{FnHandle, PermutationHandle},
     parser::FunctionDef,
     var::{Value, Var},
     witness::WitnessEnv,
@@ -19,6 +19,7 @@ use self::{
     r1cs::{R1csBls12381Field, R1csBn254Field, R1CS},
 };
 
+pub mod artifacts;
 pub mod kimchi;
 pub mod r1cs;
 
@@ -30,7 +31,7 @@ pub trait BackendField:
 
 /// This trait allows different backends to have different cell var types.
 /// It is intended to make it opaque to the frondend.
-pub trait BackendVar: Clone + Debug + PartialEq + Eq {}
+pub trait BackendVar: Clone + Debug + PartialEq + Eq + 'static {}
 
 pub enum BackendKind {
     KimchiVesta(KimchiVesta),
@@ -53,7 +54,7 @@ impl BackendKind {
 }
 
 // TODO: should it be cloneable? It is now so because FnInfo needs to be cloneable.
-pub trait Backend: Clone {
+pub trait Backend: Clone + 'static {
     /// The circuit field / scalar field that the circuit is written on.
     type Field: BackendField;
 
@@ -68,6 +69,33 @@ pub trait Backend: Clone {
     /// poseidon crypto builtin function for different backends
     fn poseidon() -> FnHandle<Self>;
 
+    /// poseidon permutation for different backends, used by the sponge of the standard library
+    fn poseidon_permutation() -> PermutationHandle<Self>;
+
+    /// elliptic curve builtins of `std::ec`, or `None` if the backend doesn't support them:
+    /// the curve of `std::ec` is Pallas, which is only defined over the circuit field of kimchi
+    fn ec_add() -> Option<FnHandle<Self>> {
+        None
+    }
+
+    fn ec_double() -> Option<FnHandle<Self>> {
+        None
+    }
+
+    fn ec_scale() -> Option<FnHandle<Self>> {
+        None
+    }
+
+    /// SHA-256 builtin of `std::crypto`, which is constrained bit by bit by default
+    fn sha256() -> FnHandle<Self> {
+        crate::stdlib::crypto::sha256::<Self>
+    }
+
+    /// Keccak-256 builtin of `std::crypto`, which is constrained bit by bit by default
+    fn keccak256() -> FnHandle<Self> {
+        crate::stdlib::crypto::keccak256::<Self>
+    }
+
     /// Init circuit
     fn init_circuit(&mut self) {
         // do nothing by default
@@ -104,6 +132,13 @@ pub trait Backend: Clone {
     /// add a constraint to assert a var equals another var
     fn assert_eq_var(&mut self, lhs: &Self::Var, rhs: &Self::Var, span: Span);
 
+    /// add constraints to assert a var fits in `num_bits` bits (at most 64)
+    fn range_check(&mut self, var: &Self::Var, num_bits: usize, span: Span);
+
+    /// decompose a var into `num_bits` boolean vars (from the least significant bit),
+    /// and add constraints to assert that they recompose the var
+    fn to_bits(&mut self, var: &Self::Var, num_bits: usize, span: Span) -> Vec<Self::Var>;
+
     /// Process a public input
     fn add_public_input(&mut self, val: Value<Self>, span: Span) -> Self::Var;
 
@@ -141,8 +176,7 @@ pub trait Backend: Clone {
 
         match val {
             Value::Hint(func) => {
-                let res = func(self, env)
-                    .expect("that function doesn't return a var (type checker error)");
+                let res = func(self, env)?;
                 env.cached_values.insert(cache_key, res);
                 Ok(res)
             }
@@ -168,6 +202,14 @@ pub trait Backend: Clone {
                 env.cached_values.insert(cache_key, res); // cache
                 Ok(res)
             }
+            Value::Bits(var, start, len) => {
+                let val: BigUint = self.compute_var(env, var)?.into();
+                let mask = (BigUint::one() << *len) - BigUint::one();
+                let bits = u64::try_from((val >> *start) & mask).expect("too many bits");
+                let res = Self::Field::from(bits);
+                env.cached_values.insert(cache_key, res); // cache
+                Ok(res)
+            }
             Value::External(name, idx) => Ok(env.get_external(name)[*idx]),
             Value::PublicOutput(var) => {
                 // var can be none. what could be the better way to pass in the span in that case?
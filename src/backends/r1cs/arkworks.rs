@@ -13,26 +13,34 @@ use crate::circuit_writer::CircuitWriter;
 use crate::compiler::{typecheck_next_file, Sources};
 use crate::type_checker::TypeChecker;
 
-pub struct NoNameCircuit<BF: BackendField> {
-    compiled_circuit: CompiledCircuit<R1CS<BF>>,
-    witness: GeneratedWitness<BF>,
+/// An arkworks circuit built from a compiled noname circuit.
+/// The witness can be omitted when the circuit is only used for a setup.
+pub struct NoNameCircuit<'a, BF: BackendField> {
+    pub compiled_circuit: &'a CompiledCircuit<R1CS<BF>>,
+    pub witness: Option<GeneratedWitness<BF>>,
 }
 
-impl<BF: BackendField> ConstraintSynthesizer<BF> for NoNameCircuit<BF> {
+impl<BF: BackendField> ConstraintSynthesizer<BF> for NoNameCircuit<'_, BF> {
     fn generate_constraints(self, cs: ConstraintSystemRef<BF>) -> Result<(), SynthesisError> {
-        let public_io_length = self.compiled_circuit.circuit.backend.public_inputs.len()
-            + self.compiled_circuit.circuit.backend.public_outputs.len();
+        let r1cs = &self.compiled_circuit.circuit.backend;
+        let public_io_length = r1cs.public_inputs.len() + r1cs.public_outputs.len();
 
         // arkworks assigns by default the 1 constant
         // assumes witness is: [1, public_outputs, public_inputs, private_inputs, aux]
-        let witness_size = self.witness.witness.len();
+        let witness_size = r1cs.witness_vector.len();
         for idx in 1..witness_size {
-            let value: BigUint = Into::into(self.witness.witness[idx]);
-            let field_element = BF::from(value);
+            // (the value is not needed during a setup)
+            let value = || -> Result<BF, SynthesisError> {
+                let witness = self
+                    .witness
+                    .as_ref()
+                    .ok_or(SynthesisError::AssignmentMissing)?;
+                Ok(witness.witness[idx])
+            };
             if idx <= public_io_length {
-                cs.new_input_variable(|| Ok(field_element))?;
+                cs.new_input_variable(value)?;
             } else {
-                cs.new_witness_variable(|| Ok(field_element))?;
+                cs.new_witness_variable(value)?;
             }
         }
 
@@ -47,11 +55,11 @@ impl<BF: BackendField> ConstraintSynthesizer<BF> for NoNameCircuit<BF> {
             }
         };
 
-        let make_lc = |lc_data: NoNameLinearCombination<BF>| {
+        let make_lc = |lc_data: &NoNameLinearCombination<BF>| {
             let mut lc = LinearCombination::<BF>::zero();
-            for (cellvar, coeff) in lc_data.terms.into_iter() {
+            for (cellvar, coeff) in &lc_data.terms {
                 let idx = make_index(cellvar.index);
-                let coeff = BF::from(Into::<BigUint>::into(coeff));
+                let coeff = BF::from(Into::<BigUint>::into(*coeff));
                 lc += (coeff, idx)
             }
 
@@ -61,11 +69,11 @@ impl<BF: BackendField> ConstraintSynthesizer<BF> for NoNameCircuit<BF> {
             lc
         };
 
-        for constraint in self.compiled_circuit.circuit.backend.constraints {
+        for constraint in &r1cs.constraints {
             cs.enforce_constraint(
-                make_lc(constraint.a),
-                make_lc(constraint.b),
-                make_lc(constraint.c),
+                make_lc(&constraint.a),
+                make_lc(&constraint.b),
+                make_lc(&constraint.c),
             )?;
         }
 
@@ -88,6 +96,13 @@ pub const WITH_PUBLIC_OUTPUT_ARRAY: &str =
     return [xx, yy];
 }";
 
+pub const WITH_POSEIDON: &str = "use std::crypto;
+
+fn main(pub public_input: Field, private_input: [Field; 2]) {
+    let digest = crypto::poseidon(private_input);
+    assert_eq(digest[0], public_input);
+}";
+
 pub fn compile_source_code<BF: BackendField>(
     code: &str,
 ) -> Result<CompiledCircuit<R1CS<BF>>, crate::error::Error> {
@@ -131,8 +146,8 @@ mod tests {
             .unwrap();
 
         let noname_circuit = NoNameCircuit {
-            compiled_circuit,
-            witness: generated_witness,
+            compiled_circuit: &compiled_circuit,
+            witness: Some(generated_witness),
         };
 
         let cs = ConstraintSystem::<Fr>::new_ref();
@@ -153,8 +168,30 @@ mod tests {
             .generate_witness(json_public, json_private)
             .unwrap();
         let noname_circuit = NoNameCircuit {
-            compiled_circuit,
-            witness: generated_witness,
+            compiled_circuit: &compiled_circuit,
+            witness: Some(generated_witness),
+        };
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        noname_circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_arkworks_cs_is_satisfied_poseidon() {
+        let compiled_circuit = compile_source_code::<R1csBn254Field>(WITH_POSEIDON).unwrap();
+        // circomlib's `poseidon([1, 2])`
+        let inputs_public = r#"{"public_input": "7853200120776062878684798364095072458815029376092732009249414926327459813530"}"#;
+        let inputs_private = r#"{"private_input": ["1", "2"]}"#;
+
+        let json_public = parse_inputs(inputs_public).unwrap();
+        let json_private = parse_inputs(inputs_private).unwrap();
+        let generated_witness = compiled_circuit
+            .generate_witness(json_public, json_private)
+            .unwrap();
+        let noname_circuit = NoNameCircuit {
+            compiled_circuit: &compiled_circuit,
+            witness: Some(generated_witness),
         };
 
         let cs = ConstraintSystem::<Fr>::new_ref();
@@ -1,14 +1,17 @@
 use crate::{
-    backends::BackendField,
+    backends::{Backend, BackendField},
     circuit_writer::{CircuitWriter, VarInfo},
     constants::Span,
-    error::Result,
-    var::Var,
+    error::{ErrorKind, Result},
+    parser::types::TyKind,
+    var::{ConstOrCell, Var},
 };
 
-use super::{LinearCombination, R1CS};
+use super::{
+    poseidon::{is_full_round, PoseidonParams, FULL_ROUNDS, PARTIAL_ROUNDS, WIDTH},
+    LinearCombination, R1CS,
+};
 
-// todo: impl this
 pub fn poseidon<F>(
     compiler: &mut CircuitWriter<R1CS<F>>,
     vars: &[VarInfo<F, LinearCombination<F>>],
@@ -17,6 +20,130 @@ pub fn poseidon<F>(
 where
     F: BackendField,
 {
-    // dummy for now
-    unimplemented!()
+    //
+    // sanity checks
+    //
+
+    // only one [Var] is passed
+    assert_eq!(vars.len(), 1);
+    let var_info = &vars[0];
+
+    // an array of length 2
+    match &var_info.typ {
+        Some(TyKind::Array(el_typ, 2)) => {
+            assert!(matches!(&**el_typ, TyKind::Field | TyKind::BigInt));
+        }
+        _ => panic!("wrong type for input to poseidon"),
+    };
+
+    // extract the values
+    let input = &var_info.var;
+    assert_eq!(input.len(), 2);
+
+    // hashing a full-constant input is not a good idea
+    if input[0].is_const() && input[1].is_const() {
+        return Err(compiler.error(
+            ErrorKind::UnexpectedError("cannot hash a full-constant input"),
+            span,
+        ));
+    }
+
+    // the state starts with the capacity (set to zero) followed by the input.
+    // constants can be encoded directly in the linear combinations
+    let mut state: Vec<LinearCombination<F>> = vec![LinearCombination::from_const(F::zero(), span)];
+    for const_or_cell in &input.cvars {
+        match const_or_cell {
+            ConstOrCell::Const(cst) => state.push(LinearCombination::from_const(*cst, span)),
+            ConstOrCell::Cell(lc) => state.push(lc.clone()),
+        }
+    }
+
+    let state = permute(compiler, state, span)?;
+
+    let vars = state.into_iter().map(ConstOrCell::Cell).collect();
+
+    Ok(Some(Var::new(vars, span)))
+}
+
+/// The poseidon permutation, on a state laid out as the rate followed by the capacity
+/// (the layout used by the sponge of the standard library).
+pub fn poseidon_permutation<F>(
+    compiler: &mut CircuitWriter<R1CS<F>>,
+    state: &[ConstOrCell<F, LinearCombination<F>>],
+    span: Span,
+) -> Result<Vec<LinearCombination<F>>>
+where
+    F: BackendField,
+{
+    assert_eq!(state.len(), WIDTH);
+
+    let to_lc = |const_or_cell: &ConstOrCell<F, LinearCombination<F>>| match const_or_cell {
+        ConstOrCell::Const(cst) => LinearCombination::from_const(*cst, span),
+        ConstOrCell::Cell(lc) => lc.clone(),
+    };
+
+    // our parameters expect the capacity first
+    let state = vec![to_lc(&state[2]), to_lc(&state[0]), to_lc(&state[1])];
+    let mut state = permute(compiler, state, span)?;
+    state.rotate_left(1);
+
+    Ok(state)
+}
+
+/// Constrains the poseidon permutation of a state starting with the capacity.
+fn permute<F>(
+    compiler: &mut CircuitWriter<R1CS<F>>,
+    mut state: Vec<LinearCombination<F>>,
+    span: Span,
+) -> Result<Vec<LinearCombination<F>>>
+where
+    F: BackendField,
+{
+    // get constants needed for poseidon
+    let params = PoseidonParams::<F>::new().ok_or_else(|| {
+        compiler.error(
+            ErrorKind::UnexpectedError("poseidon is not supported on this field"),
+            span,
+        )
+    })?;
+
+    for round in 0..FULL_ROUNDS + PARTIAL_ROUNDS {
+        // add round constants (no constraint needed)
+        for (i, x) in state.iter_mut().enumerate() {
+            *x = compiler
+                .backend
+                .add_const(x, &params.round_constants[round * WIDTH + i], span);
+        }
+
+        // S-box, each costs 3 constraints
+        let sbox_len = if is_full_round(round) { WIDTH } else { 1 };
+        for x in state.iter_mut().take(sbox_len) {
+            *x = sbox(&mut compiler.backend, x, span);
+        }
+
+        // mix layer (no constraint needed)
+        let mut new_state = Vec::with_capacity(WIDTH);
+        for row in &params.mds {
+            let mut acc = LinearCombination::from_const(F::zero(), span);
+            for (coeff, x) in row.iter().zip(&state) {
+                let scaled = compiler.backend.mul_const(x, coeff, span);
+                acc = compiler.backend.add(&acc, &scaled, span);
+            }
+            new_state.push(acc);
+        }
+        state = new_state;
+    }
+
+    Ok(state)
+}
+
+/// Constrains `x^5`.
+fn sbox<F: BackendField>(
+    backend: &mut R1CS<F>,
+    x: &LinearCombination<F>,
+    span: Span,
+) -> LinearCombination<F> {
+    let x2 = backend.mul(x, x, span);
+    let x4 = backend.mul(&x2, &x2, span);
+    backend.mul(&x4, x, span)
 }
@@ -0,0 +1,360 @@
+//! Groth16 proofs for the R1CS backends, using [arkworks](http://arkworks.rs/).
+//!
+//! Note that the Groth16 setup is circuit-specific and randomized.
+//! It is run by `noname build`, and whoever runs it could forge proofs,
+//! so this is meant for local testing.
+
+use ark_ec::PairingEngine;
+use camino::Utf8Path;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    backends::{
+        artifacts::{read_artifact, write_artifact},
+        BackendField,
+    },
+    compiler::{generate_witness, Sources},
+    inputs::{JsonInputs, PublicIO},
+    serialization::SerdeAs,
+    witness::CompiledCircuit,
+};
+
+use super::{arkworks::NoNameCircuit, R1csBls12381Field, R1csBn254Field, R1CS};
+
+/// A field that is the scalar field of a pairing-friendly curve,
+/// which allows us to use Groth16 on circuits written on that field.
+pub trait PairingField: BackendField {
+    type Engine: PairingEngine<Fr = Self>;
+
+    /// The name of the backend (as used by the CLI).
+    const BACKEND_NAME: &'static str;
+}
+
+impl PairingField for R1csBn254Field {
+    type Engine = ark_bn254::Bn254;
+    const BACKEND_NAME: &'static str = "r1cs-bn254";
+}
+
+impl PairingField for R1csBls12381Field {
+    type Engine = ark_bls12_381::Bls12_381;
+    const BACKEND_NAME: &'static str = "r1cs-bls12-381";
+}
+
+//
+// Data Structures
+//
+
+#[serde_as]
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Groth16Proof<F: PairingField> {
+    #[serde_as(as = "SerdeAs")]
+    proof: ark_groth16::Proof<F::Engine>,
+
+    /// The hash of the circuit the proof was created for.
+    circuit_hash: [u8; 32],
+}
+
+pub struct Groth16ProverIndex<F: PairingField> {
+    proving_key: ark_groth16::ProvingKey<F::Engine>,
+    circuit_hash: [u8; 32],
+    compiled_circuit: CompiledCircuit<R1CS<F>>,
+}
+
+pub struct Groth16VerifierIndex<F: PairingField> {
+    verifying_key: ark_groth16::VerifyingKey<F::Engine>,
+    circuit_hash: [u8; 32],
+}
+
+/// The content of a Groth16 prover artifact.
+#[serde_as]
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+struct ProverArtifact<F: PairingField> {
+    #[serde_as(as = "SerdeAs")]
+    proving_key: ark_groth16::ProvingKey<F::Engine>,
+}
+
+/// The content of a Groth16 verifier artifact.
+/// It contains everything needed to verify a proof, so that verifiers don't need the source code.
+#[serde_as]
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+struct VerifierArtifact<F: PairingField> {
+    #[serde_as(as = "SerdeAs")]
+    verifying_key: ark_groth16::VerifyingKey<F::Engine>,
+    public_io: PublicIO,
+}
+
+//
+// Setup
+//
+
+impl<F: BackendField> R1CS<F> {
+    /// Returns the SHA-256 hash of the constraints and of the layout of the witness.
+    pub fn circuit_hash(&self) -> [u8; 32] {
+        let mut bytes = vec![];
+        for len in [
+            self.public_outputs.len(),
+            self.public_inputs.len(),
+            self.witness_vector.len(),
+            self.constraints.len(),
+        ] {
+            bytes.extend((len as u64).to_le_bytes());
+        }
+
+        for constraint in &self.constraints {
+            for lc in constraint.as_array() {
+                let mut terms: Vec<_> = lc.terms.iter().collect();
+                terms.sort_by_key(|(var, _)| var.index);
+
+                bytes.extend((terms.len() as u64).to_le_bytes());
+                for (var, coeff) in terms {
+                    bytes.extend((var.index as u64).to_le_bytes());
+                    coeff
+                        .serialize(&mut bytes)
+                        .expect("couldn't serialize field element");
+                }
+                lc.constant
+                    .serialize(&mut bytes)
+                    .expect("couldn't serialize field element");
+            }
+        }
+
+        Sha256::digest(bytes).into()
+    }
+
+    /// Returns the number of public inputs (including the public outputs),
+    /// as seen by a Groth16 verifier.
+    fn public_io_length(&self) -> usize {
+        self.public_outputs.len() + self.public_inputs.len()
+    }
+}
+
+impl<F: PairingField> CompiledCircuit<R1CS<F>> {
+    /// Runs a Groth16 setup for the circuit,
+    /// and writes the resulting prover and verifier artifacts to the given paths.
+    pub fn write_groth16_artifacts(
+        &self,
+        prover_path: &Utf8Path,
+        verifier_path: &Utf8Path,
+    ) -> miette::Result<()> {
+        let circuit = NoNameCircuit {
+            compiled_circuit: self,
+            witness: None,
+        };
+
+        let proving_key = ark_groth16::generate_random_parameters::<F::Engine, _, _>(
+            circuit,
+            &mut rand::thread_rng(),
+        )
+        .map_err(|err| miette::miette!("groth16: could not run the setup: {err}"))?;
+
+        let circuit_hash = self.circuit.backend.circuit_hash();
+
+        let verifier_artifact = VerifierArtifact::<F> {
+            verifying_key: proving_key.vk.clone(),
+            public_io: self.public_io(),
+        };
+        write_artifact(
+            verifier_path,
+            F::BACKEND_NAME,
+            circuit_hash,
+            &verifier_artifact,
+        )?;
+
+        let prover_artifact = ProverArtifact::<F> { proving_key };
+        write_artifact(prover_path, F::BACKEND_NAME, circuit_hash, &prover_artifact)
+    }
+
+    /// Creates a prover index from the prover artifact at the given path.
+    /// The artifact must have been built from the same circuit.
+    pub fn groth16_prover_index_from_artifact(
+        self,
+        path: &Utf8Path,
+    ) -> miette::Result<Groth16ProverIndex<F>> {
+        let (header, artifact): (_, ProverArtifact<F>) = read_artifact(path, F::BACKEND_NAME)?;
+
+        if header.circuit_hash != self.circuit.backend.circuit_hash() {
+            miette::bail!("the prover artifact at `{path}` does not match the circuit (it is probably stale). Please run `noname build` again.");
+        }
+
+        Ok(Groth16ProverIndex {
+            proving_key: artifact.proving_key,
+            circuit_hash: header.circuit_hash,
+            compiled_circuit: self,
+        })
+    }
+}
+
+//
+// Proving
+//
+
+impl<F: PairingField> Groth16ProverIndex<F> {
+    pub fn compiled_circuit(&self) -> &CompiledCircuit<R1CS<F>> {
+        &self.compiled_circuit
+    }
+
+    pub fn verifier_index(&self) -> Groth16VerifierIndex<F> {
+        Groth16VerifierIndex {
+            verifying_key: self.proving_key.vk.clone(),
+            circuit_hash: self.circuit_hash,
+        }
+    }
+
+    /// returns a proof, the full public input, and the public output
+    #[allow(clippy::type_complexity)]
+    pub fn prove(
+        &self,
+        sources: &Sources,
+        public_inputs: JsonInputs,
+        private_inputs: JsonInputs,
+    ) -> miette::Result<(Groth16Proof<F>, Vec<F>, Vec<F>)> {
+        // generate the witness
+        let generated_witness = generate_witness(
+            &self.compiled_circuit,
+            sources,
+            public_inputs,
+            private_inputs,
+        )?;
+
+        // the public outputs are followed by the public inputs, right after the constant 1
+        let public_io_length = self.compiled_circuit.circuit.backend.public_io_length();
+        let full_public_inputs = generated_witness.witness[1..=public_io_length].to_vec();
+        let public_output = generated_witness.outputs.clone();
+
+        // create proof
+        let circuit = NoNameCircuit {
+            compiled_circuit: &self.compiled_circuit,
+            witness: Some(generated_witness),
+        };
+        let proof =
+            ark_groth16::create_random_proof(circuit, &self.proving_key, &mut rand::thread_rng())
+                .map_err(|err| miette::miette!("groth16: could not create a proof: {err}"))?;
+
+        let proof = Groth16Proof {
+            proof,
+            circuit_hash: self.circuit_hash,
+        };
+
+        Ok((proof, full_public_inputs, public_output))
+    }
+}
+
+//
+// Verifying
+//
+
+impl<F: PairingField> Groth16VerifierIndex<F> {
+    /// Loads a verifier index from the verifier artifact at the given path,
+    /// along with the layout of the public inputs and output.
+    /// This does not require the source code of the circuit.
+    pub fn from_artifact(path: &Utf8Path) -> miette::Result<(Self, PublicIO)> {
+        let (header, artifact): (_, VerifierArtifact<F>) = read_artifact(path, F::BACKEND_NAME)?;
+
+        Ok((
+            Self {
+                verifying_key: artifact.verifying_key,
+                circuit_hash: header.circuit_hash,
+            },
+            artifact.public_io,
+        ))
+    }
+
+    pub fn verify(&self, full_public_inputs: Vec<F>, proof: Groth16Proof<F>) -> miette::Result<()> {
+        if proof.circuit_hash != self.circuit_hash {
+            miette::bail!("groth16: the proof was created for a different circuit than the one of the verifier parameters");
+        }
+
+        let prepared_verifying_key = ark_groth16::prepare_verifying_key(&self.verifying_key);
+
+        let valid =
+            ark_groth16::verify_proof(&prepared_verifying_key, &proof.proof, &full_public_inputs)
+                .map_err(|err| miette::miette!("groth16: could not verify the proof: {err}"))?;
+
+        if !valid {
+            miette::bail!("groth16: failed to verify the proof");
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        backends::r1cs::{arkworks::WITH_PUBLIC_OUTPUT_ARRAY, R1csBn254Field, R1CS},
+        compiler::{compile, typecheck_next_file, Sources},
+        inputs::parse_inputs,
+        type_checker::TypeChecker,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_groth16_artifacts_roundtrip() -> miette::Result<()> {
+        let mut sources = Sources::new();
+        let mut tast = TypeChecker::new();
+        typecheck_next_file(
+            &mut tast,
+            None,
+            &mut sources,
+            "inline_test_groth16.no".to_string(),
+            WITH_PUBLIC_OUTPUT_ARRAY.to_string(),
+            0,
+        )?;
+        let compiled_circuit = compile(&sources, tast, R1CS::<R1csBn254Field>::new())?;
+
+        let dir = std::env::temp_dir().join(format!("noname_groth16_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let dir = camino::Utf8PathBuf::from_path_buf(dir).unwrap();
+        let prover_path = dir.join("prover.nope");
+        let verifier_path = dir.join("verifier.nope");
+
+        // setup
+        compiled_circuit.write_groth16_artifacts(&prover_path, &verifier_path)?;
+
+        // prove
+        let prover_index = compiled_circuit.groth16_prover_index_from_artifact(&prover_path)?;
+        let public_inputs = parse_inputs(r#"{"public_input": ["2", "5"]}"#).unwrap();
+        let private_inputs = parse_inputs(r#"{"private_input": ["8", "2"]}"#).unwrap();
+        let (proof, full_public_inputs, _) =
+            prover_index.prove(&sources, public_inputs.clone(), private_inputs)?;
+
+        // verify, from the verifier artifact only
+        let (verifier_index, public_io) =
+            Groth16VerifierIndex::<R1csBn254Field>::from_artifact(&verifier_path)?;
+        let rebuilt = public_io
+            .full_public_inputs(public_inputs.clone(), Some(serde_json::json!(["10", "10"])))
+            .unwrap();
+        assert_eq!(rebuilt, full_public_inputs);
+        verifier_index.verify(rebuilt, proof)?;
+
+        // a wrong public output does not verify
+        let (proof, _, _) = prover_index.prove(
+            &sources,
+            public_inputs.clone(),
+            parse_inputs(r#"{"private_input": ["8", "2"]}"#).unwrap(),
+        )?;
+        let wrong = public_io
+            .full_public_inputs(public_inputs.clone(), Some(serde_json::json!(["10", "11"])))
+            .unwrap();
+        assert!(verifier_index.verify(wrong, proof).is_err());
+
+        // a proof created for another circuit is rejected
+        let (mut proof, full_public_inputs, _) = prover_index.prove(
+            &sources,
+            public_inputs,
+            parse_inputs(r#"{"private_input": ["8", "2"]}"#).unwrap(),
+        )?;
+        proof.circuit_hash = [0; 32];
+        assert!(verifier_index.verify(full_public_inputs, proof).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        Ok(())
+    }
+}
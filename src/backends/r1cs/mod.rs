@@ -1,5 +1,7 @@
 pub mod arkworks;
 pub mod builtin;
+pub mod groth16;
+pub mod poseidon;
 pub mod snarkjs;
 
 use std::collections::{HashMap, HashSet};
@@ -293,6 +295,10 @@ where
         builtin::poseidon::<F>
     }
 
+    fn poseidon_permutation() -> crate::imports::PermutationHandle<Self> {
+        builtin::poseidon_permutation::<F>
+    }
+
     fn init_circuit(&mut self) {
         // create the first var that is always 1
         self.new_internal_var(Value::Constant(F::one()), Span::default());
@@ -573,6 +579,40 @@ where
         lhs.assert_eq(self, rhs, span)
     }
 
+    /// Decomposes the var into `num_bits` bits, see [Backend::to_bits].
+    fn range_check(&mut self, x: &LinearCombination<F>, num_bits: usize, span: Span) {
+        assert!(num_bits <= 64);
+
+        self.to_bits(x, num_bits, span);
+    }
+
+    /// Decomposes the var into `num_bits` bits, and constrains:
+    /// - each bit to be boolean: `b * b = b`
+    /// - the bits to recompose the var: `sum(b_i * 2^i) * 1 = x`
+    fn to_bits(
+        &mut self,
+        x: &LinearCombination<F>,
+        num_bits: usize,
+        span: Span,
+    ) -> Vec<LinearCombination<F>> {
+        let mut bits = Vec::with_capacity(num_bits);
+        let mut sum = LinearCombination::from_const(F::zero(), span);
+        let mut power = F::one();
+
+        for i in 0..num_bits {
+            let bit = self.new_internal_var(Value::Bits(x.clone(), i, 1), span);
+            self.enforce_constraint(&bit, &bit, &bit, span);
+
+            sum = sum.add(&bit.scale(power, span), span);
+            power.double_in_place();
+            bits.push(bit);
+        }
+
+        sum.assert_eq(self, x, span);
+
+        bits
+    }
+
     /// Adds the public input cell vars.
     fn add_public_input(&mut self, val: Value<Self>, span: Span) -> LinearCombination<F> {
         let var = self.new_internal_var(val, span);
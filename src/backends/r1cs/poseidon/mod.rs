@@ -0,0 +1,195 @@
+//! Poseidon parameters and native implementation for the R1CS backends.
+//!
+//! We use the original (unoptimized) Poseidon permutation with a width of 3,
+//! 8 full rounds, 57 partial rounds, and `x^5` as the S-box.
+//! On BN254 this matches circomlib's `Poseidon(2)`:
+//! the state is initialized as `[0, input[0], input[1]]`
+//! and the first element of the final state is the digest.
+//! On BLS12-381 the parameters are the ones of the reference implementation,
+//! whose test vector `poseidonperm_x5_255_3` is checked in the tests.
+
+pub mod bls12_381;
+pub mod bn254;
+
+use ark_ff::{FpParameters, PrimeField};
+use num_bigint::BigUint;
+
+use crate::backends::BackendField;
+
+use super::{R1csBls12381Field, R1csBn254Field};
+
+/// Width of the Poseidon state.
+pub const WIDTH: usize = 3;
+
+/// Number of full rounds (half of them at the start, half at the end).
+pub const FULL_ROUNDS: usize = 8;
+
+/// Number of partial rounds (S-box applied to the first element only).
+pub const PARTIAL_ROUNDS: usize = 57;
+
+pub struct PoseidonParams<F>
+where
+    F: BackendField,
+{
+    /// One constant per state element per round.
+    pub round_constants: Vec<F>,
+    pub mds: [[F; WIDTH]; WIDTH],
+}
+
+impl<F> PoseidonParams<F>
+where
+    F: BackendField,
+{
+    /// Returns the parameters associated to the field `F`,
+    /// or `None` if the field is not supported.
+    pub fn new() -> Option<Self> {
+        let modulus: BigUint = F::Params::MODULUS.into();
+        let bn254_modulus: BigUint = <R1csBn254Field as PrimeField>::Params::MODULUS.into();
+        let bls12_381_modulus: BigUint = <R1csBls12381Field as PrimeField>::Params::MODULUS.into();
+
+        let (round_constants, mds) = if modulus == bn254_modulus {
+            (&bn254::ROUND_CONSTANTS, &bn254::MDS)
+        } else if modulus == bls12_381_modulus {
+            (&bls12_381::ROUND_CONSTANTS, &bls12_381::MDS)
+        } else {
+            return None;
+        };
+
+        let parse = |s: &str| -> F {
+            F::from_str(s).unwrap_or_else(|_| panic!("invalid poseidon constant `{s}`"))
+        };
+
+        Some(Self {
+            round_constants: round_constants.iter().map(|c| parse(c)).collect(),
+            mds: mds.map(|row| row.map(parse)),
+        })
+    }
+}
+
+/// Returns true if the given round applies the S-box to the whole state.
+pub fn is_full_round(round: usize) -> bool {
+    !(FULL_ROUNDS / 2..FULL_ROUNDS / 2 + PARTIAL_ROUNDS).contains(&round)
+}
+
+/// Applies the Poseidon permutation to the given state.
+pub fn permutation<F: BackendField>(params: &PoseidonParams<F>, state: &mut [F; WIDTH]) {
+    for round in 0..FULL_ROUNDS + PARTIAL_ROUNDS {
+        // add round constants
+        for (i, x) in state.iter_mut().enumerate() {
+            *x += params.round_constants[round * WIDTH + i];
+        }
+
+        // S-box
+        if is_full_round(round) {
+            for x in state.iter_mut() {
+                *x = x.pow([5]);
+            }
+        } else {
+            state[0] = state[0].pow([5]);
+        }
+
+        // mix layer
+        let prev = *state;
+        for (i, x) in state.iter_mut().enumerate() {
+            *x = params.mds[i]
+                .iter()
+                .zip(&prev)
+                .fold(F::zero(), |acc, (m, y)| acc + *m * y);
+        }
+    }
+}
+
+/// Hashes two field elements natively, returning the entire final state
+/// (the digest is the first element).
+pub fn poseidon<F: BackendField>(input: [F; 2]) -> [F; WIDTH] {
+    let params = PoseidonParams::new().expect("poseidon is not supported on this field");
+    let mut state = [F::zero(), input[0], input[1]];
+    permutation(&params, &mut state);
+    state
+}
+
+/// Applies the permutation to a state laid out as the rate followed by the capacity,
+/// like the sponge of the standard library does (see [super::builtin::poseidon_permutation]).
+fn sponge_permutation<F: BackendField>(params: &PoseidonParams<F>, state: &mut [F; WIDTH]) {
+    let mut permuted = [state[2], state[0], state[1]];
+    permutation(params, &mut permuted);
+    permuted.rotate_left(1);
+    *state = permuted;
+}
+
+/// Hashes field elements natively like `std::crypto::hash`,
+/// which absorbs the number of elements before the elements in a sponge of rate 2.
+pub fn hash<F: BackendField>(input: &[F]) -> F {
+    let params = PoseidonParams::new().expect("poseidon is not supported on this field");
+    let rate = WIDTH - 1;
+
+    let mut state = [F::zero(); WIDTH];
+    let mut absorbed = 0;
+    for elem in std::iter::once(F::from(input.len() as u64)).chain(input.iter().copied()) {
+        if absorbed == rate {
+            sponge_permutation(&params, &mut state);
+            absorbed = 0;
+        }
+        state[absorbed] += elem;
+        absorbed += 1;
+    }
+
+    sponge_permutation(&params, &mut state);
+    state[0]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    /// Parses the hexadecimal field elements of a test vector.
+    fn from_hex<F: BackendField>(elems: [&str; WIDTH]) -> [F; WIDTH] {
+        elems.map(|hex| {
+            let value = BigUint::parse_bytes(hex.as_bytes(), 16).unwrap();
+            F::try_from(value).unwrap_or_else(|_| panic!("`{hex}` is not in the field"))
+        })
+    }
+
+    // The test vectors of the Poseidon reference implementation
+    // (https://extgit.iaik.tugraz.at/krypto/hadeshash, `code/test_vectors.txt`),
+    // which permute the state `[0, 1, 2]` (the state used to hash `[1, 2]`).
+
+    #[test]
+    fn test_bn254_vector() {
+        // `poseidonperm_x5_254_3`
+        let state = poseidon([R1csBn254Field::from(1u64), R1csBn254Field::from(2u64)]);
+        let expected = from_hex([
+            "115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a",
+            "0fca49b798923ab0239de1c9e7a4a9a2210312b6a2f616d18b5a87f9b628ae29",
+            "0e7ae82e40091e63cbd4f16a6d16310b3729d4b6e138fcf54110e2867045a30c",
+        ]);
+        assert_eq!(state, expected);
+    }
+
+    #[test]
+    fn test_bls12_381_vector() {
+        // `poseidonperm_x5_255_3`
+        let state = poseidon([R1csBls12381Field::from(1u64), R1csBls12381Field::from(2u64)]);
+        let expected = from_hex([
+            "28ce19420fc246a05553ad1e8c98f5c9d67166be2c18e9e4cb4b4e317dd2a78a",
+            "51f3e312c95343a896cfd8945ea82ba956c1118ce9b9859b6ea56637b4b1ddc4",
+            "3b2b69139b235626a0bfb56c9527ae66a7bf486ad8c11c14d1da0c69bbe0f79a",
+        ]);
+        assert_eq!(state, expected);
+    }
+
+    #[test]
+    fn test_circomlib_vector() {
+        // circomlib's `poseidon([1, 2])`
+        let digest = poseidon([R1csBn254Field::from(1u64), R1csBn254Field::from(2u64)]);
+        assert_eq!(
+            digest[0],
+            R1csBn254Field::from_str(
+                "7853200120776062878684798364095072458815029376092732009249414926327459813530"
+            )
+            .unwrap()
+        );
+    }
+}
@@ -39,7 +39,7 @@ enum Commands {
     /// Run the main function and produce a proof
     Prove(CmdProve),
 
-    /// Verify a proof. This command does not currently work
+    /// Verify a proof
     Verify(CmdVerify),
 
     /// Tests a single file (as opposed to a package with a `Noname.toml` manifest file).
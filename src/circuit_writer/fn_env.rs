@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 
+use num_bigint::BigUint;
+
 use crate::{
     backends::{BackendField, BackendVar},
-    parser::types::TyKind,
+    parser::types::{ConstExpr, FunctionDef, TyKind},
     var::Var,
 };
 
@@ -75,6 +77,10 @@ where
     /// This needs to be garbage collected when we exit a scope.
     /// Note: The `usize` is the scope in which the variable was created.
     vars: HashMap<String, (usize, VarInfo<F, C>)>,
+
+    /// The values that the generic array sizes (and `const` arguments) of the function
+    /// are bound to in this instantiation of the function.
+    generics: HashMap<String, ConstExpr>,
 }
 
 impl<F: BackendField, C: BackendVar> FnEnv<F, C> {
@@ -83,9 +89,61 @@ impl<F: BackendField, C: BackendVar> FnEnv<F, C> {
         Self {
             current_scope: 0,
             vars: HashMap::new(),
+            generics: HashMap::new(),
+        }
+    }
+
+    /// Creates a new FnEnv for an instantiation of a function with generic array sizes.
+    pub fn with_generics(generics: HashMap<String, ConstExpr>) -> Self {
+        Self {
+            generics,
+            ..Self::new()
         }
     }
 
+    /// Creates a new FnEnv for a call to the given function,
+    /// binding the generic array sizes of its signature to the ones observed at the call site,
+    /// as well as the values of its `const` arguments.
+    /// (The arguments themselves still need to be added to the environment.)
+    pub fn for_call(function: &FunctionDef, args: &[VarInfo<F, C>]) -> Self {
+        let mut generics = HashMap::new();
+        for (arg, var_info) in function.sig.arguments.iter().zip(args) {
+            if arg.is_constant() {
+                if let Some(value) = var_info.var.constant() {
+                    let value: BigUint = value.into();
+                    if let Ok(value) = u32::try_from(value) {
+                        generics.insert(arg.name.value.clone(), ConstExpr::Literal(value));
+                    }
+                }
+            }
+
+            if let Some(typ) = &var_info.typ {
+                arg.typ.kind.bind_generics(typ, &mut generics);
+            }
+        }
+
+        Self::with_generics(generics)
+    }
+
+    /// Returns the value of a range bound in this instantiation of the function,
+    /// or `None` if it can't be evaluated (e.g. if it underflows).
+    pub fn range_bound(&self, bound: &ConstExpr) -> Option<u32> {
+        bound
+            .substitute(&|name| self.generics.get(name).cloned())
+            .value()
+    }
+
+    /// Replaces the generic array sizes in a type with the values they are bound to.
+    pub fn resolve_generics(&self, typ: &TyKind) -> TyKind {
+        // (all the generic sizes are known when a function is instantiated)
+        let resolved = typ.resolve_generics(&self.generics);
+        assert!(
+            !resolved.is_generic(),
+            "type checking bug: generic array size not bound"
+        );
+        resolved
+    }
+
     /// Enters a scoped block.
     pub fn nest(&mut self) {
         self.current_scope += 1;
@@ -162,6 +220,32 @@ impl<F: BackendField, C: BackendVar> FnEnv<F, C> {
         self.vars.insert(var_name.to_string(), (*scope, var_info));
     }
 
+    /// Merges the variables at the end of the two blocks of an `if` statement,
+    /// where `self` is the environment at the end of the `else` block.
+    /// The variables that differ between the two blocks are merged with `merge(then_var, else_var)`.
+    pub fn merge_blocks(
+        &mut self,
+        then_env: &Self,
+        mut merge: impl FnMut(&Var<F, C>, &Var<F, C>) -> Var<F, C>,
+    ) {
+        // (sorted, so that the constraints are always created in the same order)
+        let mut names: Vec<_> = self.vars.keys().cloned().collect();
+        names.sort();
+
+        for name in names {
+            let (_, then_info) = then_env
+                .vars
+                .get(&name)
+                .expect("local variable not found after the `then` block");
+            let (_, else_info) = self.vars.get_mut(&name).unwrap();
+
+            if then_info.var.cvars != else_info.var.cvars {
+                let var = merge(&then_info.var, &else_info.var);
+                *else_info = else_info.reassign(var);
+            }
+        }
+    }
+
     /// Same as [Self::reassign_var], but only reassigns a specific range of the variable.
     pub fn reassign_var_range(&mut self, var_name: &str, var: Var<F, C>, start: usize, len: usize) {
         // get the scope first, we don't want to modify that
@@ -0,0 +1,815 @@
+//! Hint functions (`hint fn`) are written in noname, but they are not compiled in the circuit.
+//! A call to a hint creates fresh cells, whose values are computed when generating the witness
+//! by interpreting the function on the values of its arguments.
+//! Only the types of these cells are constrained (like the inputs of the main function),
+//! checking that they are related to the arguments is up to the caller.
+
+use std::sync::Arc;
+
+use ark_ff::{Field, One, Zero};
+use num_bigint::BigUint;
+
+use crate::{
+    backends::{Backend, BackendField, BackendVar},
+    circuit_writer::{CircuitWriter, FnEnv, VarInfo},
+    constants::Span,
+    error::{Error, ErrorKind, Result},
+    imports::FnKind,
+    parser::{
+        types::{ForLoopArgument, FunctionDef, Stmt, StmtKind, TyKind},
+        Expr, ExprKind, MatchPattern, Op2,
+    },
+    syntax::is_type,
+    type_checker::{FullyQualified, TypeChecker},
+    var::{ConstOrCell, Value, Var},
+    witness::WitnessEnv,
+};
+
+impl<B: Backend> CircuitWriter<B> {
+    /// Compiles a call to a hint function,
+    /// by creating a fresh cell for each field element of its output.
+    pub(crate) fn compile_hint_call(
+        &mut self,
+        function: &FunctionDef,
+        args: Vec<VarInfo<B::Field, B::Var>>,
+        span: Span,
+    ) -> Result<Option<Var<B::Field, B::Var>>> {
+        // the size of the output depends on the generic array sizes observed at this call site
+        let return_typ = function
+            .sig
+            .return_type
+            .as_ref()
+            .expect("the parser checked that hints return a value");
+        let return_typ = FnEnv::for_call(function, &args).resolve_generics(&return_typ.kind);
+        let len = self.size_of(&return_typ);
+
+        // all the outputs of a call are computed at once, the first time one of them is needed
+        let call_id = self.hint_calls;
+        self.hint_calls += 1;
+
+        let function = Arc::new(function.clone());
+        let args = Arc::new(args);
+
+        let mut cvars = Vec::with_capacity(len);
+        for idx in 0..len {
+            let typed = self.typed.clone();
+            let function = function.clone();
+            let args = args.clone();
+
+            let cvar = self.backend.new_internal_var(
+                Value::Hint(Arc::new(move |backend, env| {
+                    if let Some(outputs) = env.hint_outputs.get(&call_id) {
+                        return Ok(outputs[idx]);
+                    }
+
+                    let outputs = interpret_hint(&typed, backend, env, &function, &args)?;
+                    let res = outputs[idx];
+                    env.hint_outputs.insert(call_id, outputs);
+                    Ok(res)
+                })),
+                span,
+            );
+            cvars.push(ConstOrCell::Cell(cvar));
+        }
+
+        // a prover could compute anything instead of the hint,
+        // so at least the values must be of the type returned by the hint
+        self.constrain_inputs_to_main(&cvars, &return_typ, span)?;
+
+        Ok(Some(Var::new(cvars, span)))
+    }
+}
+
+/// Computes the output of a call to a hint function, from the cells passed as arguments.
+fn interpret_hint<B: Backend>(
+    typed: &TypeChecker<B>,
+    backend: &B,
+    env: &mut WitnessEnv<B::Field>,
+    function: &FunctionDef,
+    args: &[VarInfo<B::Field, B::Var>],
+) -> Result<Vec<B::Field>> {
+    // the arguments are passed by value
+    let mut values = Vec::with_capacity(args.len());
+    for var_info in args {
+        let mut cvars = Vec::with_capacity(var_info.var.len());
+        for cvar in var_info.var.iter() {
+            let value = match cvar {
+                ConstOrCell::Const(cst) => *cst,
+                ConstOrCell::Cell(cvar) => backend.compute_var(env, cvar)?,
+            };
+            cvars.push(ConstOrCell::Const(value));
+        }
+        values.push(var_info.reassign(Var::new(cvars, var_info.var.span)));
+    }
+
+    let interpreter = Interpreter { typed };
+    interpreter
+        .call(function, values)?
+        .ok_or_else(|| interpreter.error(ErrorKind::MissingReturn, function.span))
+}
+
+/// Returns the values of a variable, which only holds constants in a hint.
+fn values<F: BackendField, C: BackendVar>(var: &Var<F, C>) -> Vec<F> {
+    var.cvars
+        .iter()
+        .map(|cvar| cvar.cst().expect("hints only handle values"))
+        .collect()
+}
+
+/// Creates a variable holding the given values.
+fn constants<F: BackendField, C: BackendVar>(values: Vec<F>, span: Span) -> Var<F, C> {
+    Var::new(values.into_iter().map(ConstOrCell::Const).collect(), span)
+}
+
+fn from_bool<F: BackendField>(value: bool) -> F {
+    if value {
+        F::one()
+    } else {
+        F::zero()
+    }
+}
+
+/// Converts an integer to a field element, modulo the size of the field.
+fn from_biguint<F: BackendField>(value: BigUint) -> F {
+    let max: BigUint = (-F::one()).into();
+    F::try_from(value % (max + 1u32))
+        .ok()
+        .expect("the value was reduced modulo the size of the field")
+}
+
+/// Executes the body of hint functions (and of the functions they call) on actual values.
+/// It follows the semantics of the [CircuitWriter], without creating any constraints:
+/// variables only hold constants, and only the branches that are taken are executed.
+struct Interpreter<'a, B>
+where
+    B: Backend,
+{
+    typed: &'a TypeChecker<B>,
+}
+
+impl<'a, B: Backend> Interpreter<'a, B> {
+    fn error(&self, kind: ErrorKind, span: Span) -> Error {
+        Error::new("hint", kind, span)
+    }
+
+    /// Returns the type of an expression, in this instantiation of the function.
+    fn expr_type(&self, fn_env: &FnEnv<B::Field, B::Var>, expr: &Expr) -> Option<TyKind> {
+        self.typed
+            .expr_type(expr)
+            .map(|typ| fn_env.resolve_generics(typ))
+    }
+
+    fn call(
+        &self,
+        function: &FunctionDef,
+        args: Vec<VarInfo<B::Field, B::Var>>,
+    ) -> Result<Option<Vec<B::Field>>> {
+        let fn_env = &mut FnEnv::for_call(function, &args);
+
+        for (arg, var_info) in function.sig.arguments.iter().zip(args) {
+            fn_env.add_local_var(arg.name.value.clone(), var_info);
+        }
+
+        self.run_block(fn_env, &function.body)
+    }
+
+    fn run_block(
+        &self,
+        fn_env: &mut FnEnv<B::Field, B::Var>,
+        stmts: &[Stmt],
+    ) -> Result<Option<Vec<B::Field>>> {
+        fn_env.nest();
+        for stmt in stmts {
+            if let Some(res) = self.run_stmt(fn_env, stmt)? {
+                // (a return in a nested block returns from the function)
+                return Ok(Some(res));
+            }
+        }
+        fn_env.pop();
+        Ok(None)
+    }
+
+    fn run_stmt(
+        &self,
+        fn_env: &mut FnEnv<B::Field, B::Var>,
+        stmt: &Stmt,
+    ) -> Result<Option<Vec<B::Field>>> {
+        match &stmt.kind {
+            StmtKind::Assign { mutable, lhs, rhs } => {
+                let rhs_values = self.eval_value(fn_env, rhs)?;
+                let typ = self.expr_type(fn_env, rhs);
+                let var_info = VarInfo::new(constants(rhs_values, rhs.span), *mutable, typ);
+                fn_env.add_local_var(lhs.value.clone(), var_info);
+            }
+
+            StmtKind::TupleAssign { lhs, rhs } => {
+                let rhs_values = self.eval_value(fn_env, rhs)?;
+                let types = match self.expr_type(fn_env, rhs) {
+                    Some(TyKind::Tuple(types)) => types,
+                    _ => panic!("expected tuple"),
+                };
+
+                // each name gets its part of the tuple
+                let mut start = 0;
+                for ((mutable, name), typ) in lhs.iter().zip(types) {
+                    let len = self.typed.size_of(&typ);
+                    let var = constants(rhs_values[start..start + len].to_vec(), name.span);
+                    start += len;
+
+                    let var_info = VarInfo::new(var, *mutable, Some(typ));
+                    fn_env.add_local_var(name.value.clone(), var_info);
+                }
+            }
+
+            StmtKind::ForLoop {
+                var,
+                argument,
+                body,
+            } => match argument {
+                ForLoopArgument::Range(range) => {
+                    let start = fn_env
+                        .range_bound(&range.start)
+                        .ok_or_else(|| self.error(ErrorKind::InvalidRangeSize, range.span))?;
+                    let end = fn_env
+                        .range_bound(&range.end)
+                        .ok_or_else(|| self.error(ErrorKind::InvalidRangeSize, range.span))?;
+
+                    for ii in start..end {
+                        fn_env.nest();
+
+                        let cst_var = Var::new_constant(ii.into(), var.span);
+                        let var_info = VarInfo::new(cst_var, false, Some(TyKind::Field));
+                        fn_env.add_local_var(var.value.clone(), var_info);
+
+                        if let Some(res) = self.run_block(fn_env, body)? {
+                            return Ok(Some(res));
+                        }
+
+                        fn_env.pop();
+                    }
+                }
+                ForLoopArgument::Iterator(iterator) => {
+                    let array = self.eval_value(fn_env, iterator)?;
+                    let (elem_type, array_len) = match self.expr_type(fn_env, iterator) {
+                        Some(TyKind::Array(ty, array_len)) => (*ty, array_len as usize),
+                        _ => panic!("expected array"),
+                    };
+                    let len = self.typed.size_of(&elem_type);
+
+                    for ii in 0..array_len {
+                        fn_env.nest();
+
+                        let elem = constants(array[ii * len..(ii + 1) * len].to_vec(), var.span);
+                        let var_info = VarInfo::new(elem, false, Some(elem_type.clone()));
+                        fn_env.add_local_var(var.value.clone(), var_info);
+
+                        if let Some(res) = self.run_block(fn_env, body)? {
+                            return Ok(Some(res));
+                        }
+
+                        fn_env.pop();
+                    }
+                }
+            },
+
+            // only the block that is taken is executed
+            StmtKind::If { cond, then_, else_ } => {
+                let cond = self.eval_value(fn_env, cond)?;
+                let block = if cond[0].is_one() { then_ } else { else_ };
+                if let Some(res) = self.run_block(fn_env, block)? {
+                    return Ok(Some(res));
+                }
+            }
+
+            StmtKind::Expr(expr) => {
+                self.eval(fn_env, expr)?;
+            }
+
+            StmtKind::Return(expr) => {
+                let res = self.eval_value(fn_env, expr)?;
+                return Ok(Some(res));
+            }
+
+            StmtKind::Comment(_) => (),
+        }
+
+        Ok(None)
+    }
+
+    /// Evaluates an expression that must return a value.
+    fn eval_value(
+        &self,
+        fn_env: &mut FnEnv<B::Field, B::Var>,
+        expr: &Expr,
+    ) -> Result<Vec<B::Field>> {
+        self.eval(fn_env, expr)?
+            .ok_or_else(|| self.error(ErrorKind::CannotComputeExpression, expr.span))
+    }
+
+    /// Evaluates the arguments of a call, which are passed by value.
+    fn eval_args(
+        &self,
+        fn_env: &mut FnEnv<B::Field, B::Var>,
+        args: &[Expr],
+    ) -> Result<Vec<VarInfo<B::Field, B::Var>>> {
+        let mut vars = Vec::with_capacity(args.len());
+        for arg in args {
+            let arg_values = self.eval_value(fn_env, arg)?;
+            let typ = self.expr_type(fn_env, arg);
+            vars.push(VarInfo::new(constants(arg_values, arg.span), false, typ));
+        }
+        Ok(vars)
+    }
+
+    /// Returns the range of a field in a struct.
+    fn field_range(&self, struct_typ: Option<TyKind>, field_name: &str) -> (usize, usize) {
+        let (module, struct_name) = match struct_typ {
+            Some(TyKind::Custom { module, name }) => (module, name),
+            _ => panic!("field access on a non-struct"),
+        };
+
+        let qualified = FullyQualified::new(&module, &struct_name);
+        let struct_info = self
+            .typed
+            .struct_info(&qualified)
+            .expect("struct info not found for custom struct");
+
+        let mut start = 0;
+        for (field, field_typ) in &struct_info.fields {
+            let len = self.typed.size_of(field_typ);
+            if field == field_name {
+                return (start, len);
+            }
+            start += len;
+        }
+
+        panic!("field not found in struct (type checker bug)");
+    }
+
+    /// Returns the range of an element in a tuple.
+    fn tuple_range(&self, tuple_typ: Option<TyKind>, idx: usize) -> (usize, usize) {
+        let types = match tuple_typ {
+            Some(TyKind::Tuple(types)) => types,
+            _ => panic!("expected tuple"),
+        };
+
+        let start = types[..idx].iter().map(|typ| self.typed.size_of(typ)).sum();
+        (start, self.typed.size_of(&types[idx]))
+    }
+
+    /// Returns the range of the element of an array at the given index,
+    /// which can be any value in a hint.
+    fn elem_range(
+        &self,
+        array_typ: Option<TyKind>,
+        idx: B::Field,
+        span: Span,
+    ) -> Result<(usize, usize)> {
+        let (elem_type, array_len) = match array_typ {
+            Some(TyKind::Array(ty, array_len)) => (ty, array_len as usize),
+            _ => panic!("expected array"),
+        };
+
+        let idx: BigUint = idx.into();
+        let idx = usize::try_from(idx).unwrap_or(usize::MAX);
+        if idx >= array_len {
+            return Err(self.error(
+                ErrorKind::ArrayIndexOutOfBounds(idx, array_len.saturating_sub(1)),
+                span,
+            ));
+        }
+
+        let len = self.typed.size_of(&elem_type);
+        Ok((idx * len, len))
+    }
+
+    /// Returns the variable that an assignment modifies,
+    /// along with the range of the values that are modified.
+    fn place(
+        &self,
+        fn_env: &mut FnEnv<B::Field, B::Var>,
+        expr: &Expr,
+    ) -> Result<(String, usize, usize)> {
+        match &expr.kind {
+            ExprKind::Variable { module: _, name } => {
+                let var_info = fn_env.get_local_var(&name.value);
+                Ok((name.value.clone(), 0, var_info.var.len()))
+            }
+            ExprKind::FieldAccess { lhs, rhs } => {
+                let (var_name, start, _) = self.place(fn_env, lhs)?;
+                let (offset, len) = self.field_range(self.expr_type(fn_env, lhs), &rhs.value);
+                Ok((var_name, start + offset, len))
+            }
+            ExprKind::TupleAccess { tuple, idx } => {
+                let (var_name, start, _) = self.place(fn_env, tuple)?;
+                let (offset, len) = self.tuple_range(self.expr_type(fn_env, tuple), *idx);
+                Ok((var_name, start + offset, len))
+            }
+            ExprKind::ArrayAccess { array, idx } => {
+                let (var_name, start, _) = self.place(fn_env, array)?;
+                let idx_values = self.eval_value(fn_env, idx)?;
+                let array_typ = self.expr_type(fn_env, array);
+                let (offset, len) = self.elem_range(array_typ, idx_values[0], expr.span)?;
+                Ok((var_name, start + offset, len))
+            }
+            _ => Err(self.error(ErrorKind::InvalidAssignmentExpression, expr.span)),
+        }
+    }
+
+    fn eval(
+        &self,
+        fn_env: &mut FnEnv<B::Field, B::Var>,
+        expr: &Expr,
+    ) -> Result<Option<Vec<B::Field>>> {
+        match &expr.kind {
+            ExprKind::FnCall {
+                module,
+                fn_name,
+                args,
+            } => {
+                let qualified = FullyQualified::new(module, &fn_name.value);
+                let fn_info = self.typed.fn_info(&qualified).ok_or_else(|| {
+                    self.error(
+                        ErrorKind::UndefinedFunction(fn_name.value.clone()),
+                        fn_name.span,
+                    )
+                })?;
+
+                let vars = self.eval_args(fn_env, args)?;
+
+                match &fn_info.kind {
+                    FnKind::BuiltIn(sig, _) => self.eval_builtin(&sig.name.value, vars, expr.span),
+                    // functions called from a hint (hints or not) are interpreted as well
+                    FnKind::Native(func) => self.call(func, vars),
+                }
+            }
+
+            ExprKind::MethodCall {
+                lhs,
+                method_name,
+                args,
+            } => {
+                let lhs_typ = self.expr_type(fn_env, lhs).expect("method call on what?");
+                let (module, struct_name) = match &lhs_typ {
+                    TyKind::Custom { module, name } => (module, name),
+                    _ => panic!("method call only work on custom types"),
+                };
+
+                let qualified = FullyQualified::new(module, struct_name);
+                let struct_info = self
+                    .typed
+                    .struct_info(&qualified)
+                    .expect("struct info not found for custom struct");
+                let func = struct_info
+                    .methods
+                    .get(&method_name.value)
+                    .expect("could not find method");
+
+                // if the method has a `self` argument, it is the first argument
+                let mut vars = vec![];
+                let is_method = func
+                    .sig
+                    .arguments
+                    .first()
+                    .map(|arg| arg.name.value == "self")
+                    .unwrap_or(false);
+                if is_method {
+                    let self_values = self
+                        .eval(fn_env, lhs)?
+                        .ok_or_else(|| self.error(ErrorKind::NotAStaticMethod, method_name.span))?;
+                    let self_var = constants(self_values, lhs.span);
+                    vars.push(VarInfo::new(self_var, false, Some(lhs_typ.clone())));
+                }
+
+                vars.extend(self.eval_args(fn_env, args)?);
+
+                self.call(func, vars)
+            }
+
+            ExprKind::Assignment { lhs, rhs } => {
+                let (var_name, start, len) = self.place(fn_env, lhs)?;
+                let rhs_values = self.eval_value(fn_env, rhs)?;
+                fn_env.reassign_var_range(&var_name, constants(rhs_values, rhs.span), start, len);
+
+                Ok(None)
+            }
+
+            ExprKind::CompoundAssignment { op, lhs, rhs } => {
+                let uint_typ = [lhs, rhs]
+                    .into_iter()
+                    .filter_map(|operand| self.typed.expr_type(operand))
+                    .find(|typ| typ.uint_bits().is_some())
+                    .cloned();
+
+                // the place of lhs is only evaluated once
+                let (var_name, start, len) = self.place(fn_env, lhs)?;
+                let current =
+                    values(&fn_env.get_local_var(&var_name).var)[start..start + len].to_vec();
+                let rhs_values = self.eval_value(fn_env, rhs)?;
+                let res = self.eval_binary_op(op, &current, &rhs_values, uint_typ, expr.span)?;
+                fn_env.reassign_var_range(&var_name, constants(vec![res], rhs.span), start, len);
+
+                Ok(None)
+            }
+
+            ExprKind::FieldAccess { lhs, rhs } => {
+                let lhs_values = self.eval_value(fn_env, lhs)?;
+                let (start, len) = self.field_range(self.expr_type(fn_env, lhs), &rhs.value);
+                Ok(Some(lhs_values[start..start + len].to_vec()))
+            }
+
+            ExprKind::TupleAccess { tuple, idx } => {
+                let tuple_values = self.eval_value(fn_env, tuple)?;
+                let (start, len) = self.tuple_range(self.expr_type(fn_env, tuple), *idx);
+                Ok(Some(tuple_values[start..start + len].to_vec()))
+            }
+
+            ExprKind::ArrayAccess { array, idx } => {
+                let array_values = self.eval_value(fn_env, array)?;
+                let idx_values = self.eval_value(fn_env, idx)?;
+                let array_typ = self.expr_type(fn_env, array);
+                let (start, len) = self.elem_range(array_typ, idx_values[0], expr.span)?;
+                Ok(Some(array_values[start..start + len].to_vec()))
+            }
+
+            ExprKind::BinaryOp { op, lhs, rhs, .. } => {
+                let uint_typ = [lhs, rhs]
+                    .into_iter()
+                    .filter_map(|operand| self.typed.expr_type(operand))
+                    .find(|typ| typ.uint_bits().is_some())
+                    .cloned();
+
+                let lhs = self.eval_value(fn_env, lhs)?;
+                let rhs = self.eval_value(fn_env, rhs)?;
+                self.eval_binary_op(op, &lhs, &rhs, uint_typ, expr.span)
+                    .map(|res| Some(vec![res]))
+            }
+
+            ExprKind::Negated(inner) => {
+                let inner = self.eval_value(fn_env, inner)?;
+                Ok(Some(vec![-inner[0]]))
+            }
+
+            ExprKind::Not(inner) => {
+                let inner = self.eval_value(fn_env, inner)?;
+                Ok(Some(vec![B::Field::one() - inner[0]]))
+            }
+
+            ExprKind::BigUInt(b) => {
+                let ff = B::Field::try_from(b.to_owned()).map_err(|_| {
+                    self.error(ErrorKind::CannotConvertToField(b.to_string()), expr.span)
+                })?;
+                Ok(Some(vec![ff]))
+            }
+
+            ExprKind::Bool(b) => Ok(Some(vec![from_bool(*b)])),
+
+            ExprKind::Variable { module, name } => {
+                // a type is followed by a static method call
+                if is_type(&name.value) {
+                    return Ok(None);
+                }
+
+                let qualified = FullyQualified::new(module, &name.value);
+                if let Some(cst_info) = self.typed.const_info(&qualified) {
+                    return Ok(Some(cst_info.value.clone()));
+                }
+
+                let var_info = fn_env.get_local_var(&name.value);
+                Ok(Some(values(&var_info.var)))
+            }
+
+            ExprKind::ArrayDeclaration(items) | ExprKind::TupleDeclaration(items) => {
+                let mut res = vec![];
+                for item in items {
+                    res.extend(self.eval_value(fn_env, item)?);
+                }
+                Ok(Some(res))
+            }
+
+            ExprKind::CustomTypeDeclaration { custom: _, fields } => {
+                let mut res = vec![];
+                for (_field, rhs) in fields {
+                    res.extend(self.eval_value(fn_env, rhs)?);
+                }
+                Ok(Some(res))
+            }
+
+            ExprKind::EnumVariant {
+                enum_name,
+                variant,
+                args,
+            } => {
+                let qualified = FullyQualified::new(&enum_name.module, &enum_name.name);
+                let enum_info = self
+                    .typed
+                    .enum_info(&qualified)
+                    .expect("enum info not found for enum");
+                let (tag, _) = enum_info
+                    .variant(&variant.value)
+                    .expect("variant not found in enum");
+
+                // the tag, followed by the values held by the variant, padded with zeros
+                let mut res = vec![B::Field::from(tag as u64)];
+                for arg in args {
+                    res.extend(self.eval_value(fn_env, arg)?);
+                }
+
+                let typ = TyKind::Custom {
+                    module: enum_name.module.clone(),
+                    name: enum_name.name.clone(),
+                };
+                res.resize(self.typed.size_of(&typ), B::Field::zero());
+
+                Ok(Some(res))
+            }
+
+            ExprKind::Match { matched, arms } => {
+                let matched_values = self.eval_value(fn_env, matched)?;
+
+                let (module, name) = match self.typed.expr_type(matched) {
+                    Some(TyKind::Custom { module, name }) => (module, name),
+                    _ => panic!("expected enum"),
+                };
+                let enum_info = self
+                    .typed
+                    .enum_info(&FullyQualified::new(module, name))
+                    .expect("enum info not found for enum");
+
+                // only the arm that is taken is evaluated
+                let tag: BigUint = matched_values[0].into();
+                for arm in arms {
+                    fn_env.nest();
+
+                    let taken = match &arm.pattern {
+                        MatchPattern::Variant {
+                            variant, bindings, ..
+                        } => {
+                            let (arm_tag, types) = enum_info
+                                .variant(&variant.value)
+                                .expect("variant not found in enum");
+
+                            let taken = tag == BigUint::from(arm_tag);
+                            if taken {
+                                let mut start = 1;
+                                for (binding, typ) in bindings.iter().zip(types) {
+                                    let len = self.typed.size_of(typ);
+                                    if let Some(binding) = binding {
+                                        let var = constants(
+                                            matched_values[start..start + len].to_vec(),
+                                            binding.span,
+                                        );
+                                        let var_info = VarInfo::new(var, false, Some(typ.clone()));
+                                        fn_env.add_local_var(binding.value.clone(), var_info);
+                                    }
+                                    start += len;
+                                }
+                            }
+                            taken
+                        }
+                        MatchPattern::Wildcard(_) => true,
+                    };
+
+                    if taken {
+                        let res = self.eval_value(fn_env, &arm.expr)?;
+                        fn_env.pop();
+                        return Ok(Some(res));
+                    }
+
+                    fn_env.pop();
+                }
+
+                // (the tag of an enum is always one of its variants)
+                Err(self.error(ErrorKind::CannotComputeExpression, expr.span))
+            }
+
+            ExprKind::IfElse { cond, then_, else_ } => {
+                let cond = self.eval_value(fn_env, cond)?;
+                if cond[0].is_one() {
+                    self.eval(fn_env, then_)
+                } else {
+                    self.eval(fn_env, else_)
+                }
+            }
+        }
+    }
+
+    fn eval_builtin(
+        &self,
+        name: &str,
+        vars: Vec<VarInfo<B::Field, B::Var>>,
+        span: Span,
+    ) -> Result<Option<Vec<B::Field>>> {
+        let args: Vec<_> = vars.iter().map(|var_info| values(&var_info.var)).collect();
+
+        match name {
+            "assert" => {
+                if !args[0][0].is_one() {
+                    return Err(self.error(ErrorKind::AssertionFailed, span));
+                }
+                Ok(None)
+            }
+            "assert_eq" => {
+                if args[0] != args[1] {
+                    return Err(self.error(ErrorKind::AssertionFailed, span));
+                }
+                Ok(None)
+            }
+            "to_bits" => {
+                let value: BigUint = args[0][0].into();
+
+                let len: BigUint = args[1][0].into();
+                let len: usize = len
+                    .try_into()
+                    .expect("the number of bits was checked by the type checker");
+
+                if value.bits() as usize > len {
+                    return Err(self.error(
+                        ErrorKind::BitDecompositionOverflow(value.to_string(), len),
+                        span,
+                    ));
+                }
+
+                let bits = (0..len).map(|ii| from_bool(value.bit(ii as u64)));
+                Ok(Some(bits.collect()))
+            }
+            "from_bits" => {
+                let two = B::Field::from(2u64);
+                let res = args[0]
+                    .iter()
+                    .rev()
+                    .fold(B::Field::zero(), |acc, bit| acc * two + bit);
+                Ok(Some(vec![res]))
+            }
+            _ => Err(self.error(ErrorKind::InvalidHintCall(name.to_string()), span)),
+        }
+    }
+
+    fn eval_binary_op(
+        &self,
+        op: &Op2,
+        lhs: &[B::Field],
+        rhs: &[B::Field],
+        uint_typ: Option<TyKind>,
+        span: Span,
+    ) -> Result<B::Field> {
+        let (xx, yy) = (lhs[0], rhs[0]);
+        let (big_xx, big_yy): (BigUint, BigUint) = (xx.into(), yy.into());
+
+        let res = match op {
+            Op2::Addition => xx + yy,
+            Op2::Subtraction => xx - yy,
+            Op2::Multiplication => xx * yy,
+            Op2::Division => {
+                let inv = yy
+                    .inverse()
+                    .ok_or_else(|| self.error(ErrorKind::DivisionByZero, span))?;
+                xx * inv
+            }
+            Op2::Equality => from_bool(lhs == rhs),
+            Op2::Inequality => from_bool(lhs != rhs),
+            Op2::LessThan => from_bool(big_xx < big_yy),
+            Op2::LessThanOrEqual => from_bool(big_xx <= big_yy),
+            Op2::GreaterThan => from_bool(big_xx > big_yy),
+            Op2::GreaterThanOrEqual => from_bool(big_xx >= big_yy),
+            Op2::BoolAnd => from_bool(xx.is_one() && yy.is_one()),
+            Op2::BoolOr => from_bool(xx.is_one() || yy.is_one()),
+            // (on booleans, these are logical operations)
+            Op2::BitwiseAnd => from_biguint(big_xx & big_yy),
+            Op2::BitwiseOr => from_biguint(big_xx | big_yy),
+            Op2::BitwiseXor => from_biguint(big_xx ^ big_yy),
+            Op2::ShiftLeft | Op2::ShiftRight => {
+                let shift = u32::try_from(&big_yy).map_err(|_| {
+                    let typ = uint_typ.clone().unwrap_or(TyKind::Field);
+                    self.error(ErrorKind::InvalidShift(big_yy.to_string(), typ), span)
+                })?;
+
+                if matches!(op, Op2::ShiftRight) {
+                    from_biguint(big_xx >> shift)
+                } else if let Some(bits) = uint_typ.as_ref().and_then(|typ| typ.uint_bits()) {
+                    // like in Rust, the bits shifted out are discarded
+                    if shift as usize >= bits {
+                        B::Field::zero()
+                    } else {
+                        let mask = (BigUint::one() << bits) - BigUint::one();
+                        from_biguint((big_xx << shift) & mask)
+                    }
+                } else {
+                    xx * B::Field::from(2u64).pow([shift as u64])
+                }
+            }
+        };
+
+        // arithmetic on unsigned integers is overflow-checked, like in the circuit
+        let is_arithmetic = matches!(op, Op2::Addition | Op2::Subtraction | Op2::Multiplication);
+        if let Some(typ) = uint_typ.filter(|_| is_arithmetic) {
+            let bits = typ.uint_bits().expect("expected an unsigned integer");
+            let value: BigUint = res.into();
+            if value.bits() as usize > bits {
+                return Err(self.error(ErrorKind::UintOverflow(value.to_string(), typ), span));
+            }
+        }
+
+        Ok(res)
+    }
+}
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use crate::{
     backends::Backend,
     constants::Span,
@@ -6,8 +8,8 @@ use crate::{
         types::{AttributeKind, FnArg, TyKind},
         Expr,
     },
-    type_checker::{ConstInfo, FnInfo, FullyQualified, StructInfo, TypeChecker},
-    var::Var,
+    type_checker::{ConstInfo, EnumInfo, FnInfo, FullyQualified, StructInfo, TypeChecker},
+    var::{ConstOrCell, Var},
     witness::{CompiledCircuit, WitnessEnv},
 };
 
@@ -17,6 +19,7 @@ use serde::{Deserialize, Serialize};
 pub use writer::{Gate, GateKind, Wiring};
 
 pub mod fn_env;
+pub mod hint;
 pub mod writer;
 
 //#[derive(Debug, Serialize, Deserialize)]
@@ -29,7 +32,8 @@ where
     // Important: this field must not be used directly.
     // This is because, depending on the value of [current_module],
     // the type checker state might be this one, or one of the ones in [dependencies].
-    typed: TypeChecker<B>,
+    // (it is shared with the hints, which are interpreted when generating the witness)
+    typed: Arc<TypeChecker<B>>,
 
     /// The constraint backend for the circuit.
     /// For now, this needs to be exposed for the kimchi prover for kimchi specific low level data.
@@ -45,6 +49,14 @@ where
     /// 3. During witness generation, the public output computation
     ///    is delayed until the very end.
     pub(crate) public_output: Option<Var<B::Field, B::Var>>,
+
+    /// The condition under which the assertions are currently made,
+    /// set when compiling the blocks of an `if` statement.
+    pub(crate) condition: Option<ConstOrCell<B::Field, B::Var>>,
+
+    /// The number of calls to hint functions in the circuit,
+    /// used to identify their outputs when generating the witness.
+    pub(crate) hint_calls: usize,
 }
 
 /// Debug information related to a single row in a circuit.
@@ -71,6 +83,10 @@ impl<B: Backend> CircuitWriter<B> {
         self.typed.struct_info(qualified)
     }
 
+    pub fn enum_info(&self, qualified: &FullyQualified) -> Option<&EnumInfo> {
+        self.typed.enum_info(qualified)
+    }
+
     pub fn fn_info(&self, qualified: &FullyQualified) -> Option<&FnInfo<B>> {
         self.typed.fn_info(qualified)
     }
@@ -136,9 +152,11 @@ impl<B: Backend> CircuitWriter<B> {
     /// Creates a global environment from the one created by the type checker.
     fn new(typed: TypeChecker<B>, backend: B) -> Self {
         Self {
-            typed,
+            typed: Arc::new(typed),
             backend,
             public_output: None,
+            condition: None,
+            hint_calls: 0,
         }
     }
 
@@ -238,12 +256,6 @@ impl<B: Backend> CircuitWriter<B> {
         // get length
         let len = match &typ.kind {
             TyKind::Field => 1,
-            TyKind::Array(typ, len) => {
-                if !matches!(**typ, TyKind::Field) {
-                    unimplemented!();
-                }
-                *len as usize
-            }
             TyKind::Bool => 1,
             typ => self.size_of(typ),
         };
@@ -10,12 +10,12 @@ use crate::{
     backends::{kimchi::VestaField, Backend},
     circuit_writer::{CircuitWriter, DebugInfo, FnEnv, VarInfo},
     constants::Span,
-    constraints::{boolean, field},
+    constraints::{boolean, field, uint},
     error::{ErrorKind, Result},
     imports::FnKind,
     parser::{
-        types::{FunctionDef, Stmt, StmtKind, TyKind},
-        Expr, ExprKind, Op2,
+        types::{ForLoopArgument, FunctionDef, Stmt, StmtKind, TyKind},
+        Expr, ExprKind, MatchPattern, Op2,
     },
     syntax::is_type,
     type_checker::FullyQualified,
@@ -26,11 +26,21 @@ use crate::{
 // Data structures
 //
 
+/// The kimchi gates used by the circuit writer and the gadgets of the kimchi backend.
+///
+/// The gates of kimchi's endomorphism-based scalar multiplication (`EndoMul` and `EndoMulScalar`) are left out:
+/// they multiply by scalars given in the decomposition used by the endomorphism,
+/// while `Point.scale` of `std::ec` takes the bits of its scalar, which [GateKind::VarBaseMul] consumes directly.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum GateKind {
     Zero,
     DoubleGeneric,
     Poseidon,
+    RangeCheck0,
+    CompleteAdd,
+    VarBaseMul,
+    Xor16,
+    Rot64,
 }
 
 impl From<GateKind> for kimchi::circuits::gate::GateType {
@@ -40,6 +50,11 @@ impl From<GateKind> for kimchi::circuits::gate::GateType {
             GateKind::Zero => Zero,
             GateKind::DoubleGeneric => Generic,
             GateKind::Poseidon => Poseidon,
+            GateKind::RangeCheck0 => RangeCheck0,
+            GateKind::CompleteAdd => CompleteAdd,
+            GateKind::VarBaseMul => VarBaseMul,
+            GateKind::Xor16 => Xor16,
+            GateKind::Rot64 => Rot64,
         }
     }
 }
@@ -129,7 +144,7 @@ impl<B: Backend> CircuitWriter<B> {
                 // obtain the actual values
                 let rhs_var = rhs_var.value(self, fn_env);
 
-                let typ = self.expr_type(rhs).cloned();
+                let typ = self.expr_type(rhs).map(|typ| fn_env.resolve_generics(typ));
                 let var_info = VarInfo::new(rhs_var, *mutable, typ);
 
                 // store the new variable
@@ -137,19 +152,124 @@ impl<B: Backend> CircuitWriter<B> {
                 self.add_local_var(fn_env, lhs.value.clone(), var_info);
             }
 
-            StmtKind::ForLoop { var, range, body } => {
-                for ii in range.range() {
-                    fn_env.nest();
+            StmtKind::TupleAssign { lhs, rhs } => {
+                // compute the rhs
+                let rhs_var = self
+                    .compute_expr(fn_env, rhs)?
+                    .ok_or_else(|| self.error(ErrorKind::CannotComputeExpression, stmt.span))?
+                    .value(self, fn_env);
 
-                    let cst_var = Var::new_constant(ii.into(), var.span);
-                    let var_info = VarInfo::new(cst_var, false, Some(TyKind::Field));
-                    self.add_local_var(fn_env, var.value.clone(), var_info);
+                let typ = self.expr_type(rhs).expect("cannot find type of tuple");
+                let types = match fn_env.resolve_generics(typ) {
+                    TyKind::Tuple(types) => types,
+                    _ => panic!("expected tuple"),
+                };
 
-                    self.compile_block(fn_env, body)?;
+                // each name gets its part of the tuple
+                let mut start = 0;
+                for ((mutable, name), typ) in lhs.iter().zip(types) {
+                    let len = self.size_of(&typ);
+                    let var = Var::new(rhs_var.range(start, len).to_vec(), rhs_var.span);
+                    start += len;
 
-                    fn_env.pop();
+                    let var_info = VarInfo::new(var, *mutable, Some(typ));
+                    self.add_local_var(fn_env, name.value.clone(), var_info);
                 }
             }
+
+            StmtKind::ForLoop {
+                var,
+                argument,
+                body,
+            } => match argument {
+                ForLoopArgument::Range(range) => {
+                    let start = fn_env
+                        .range_bound(&range.start)
+                        .ok_or_else(|| self.error(ErrorKind::InvalidRangeSize, range.span))?;
+                    let end = fn_env
+                        .range_bound(&range.end)
+                        .ok_or_else(|| self.error(ErrorKind::InvalidRangeSize, range.span))?;
+
+                    for ii in start..end {
+                        fn_env.nest();
+
+                        let cst_var = Var::new_constant(ii.into(), var.span);
+                        let var_info = VarInfo::new(cst_var, false, Some(TyKind::Field));
+                        self.add_local_var(fn_env, var.value.clone(), var_info);
+
+                        self.compile_block(fn_env, body)?;
+
+                        fn_env.pop();
+                    }
+                }
+                ForLoopArgument::Iterator(iterator) => {
+                    // the array is evaluated once, before the loop
+                    let array = self
+                        .compute_expr(fn_env, iterator)?
+                        .ok_or_else(|| {
+                            self.error(ErrorKind::CannotComputeExpression, iterator.span)
+                        })?
+                        .value(self, fn_env);
+
+                    // retrieve the type of the elements in the array
+                    let array_typ = self.expr_type(iterator).expect("cannot find type of array");
+                    let (elem_type, array_len) = match fn_env.resolve_generics(array_typ) {
+                        TyKind::Array(ty, array_len) => (*ty, array_len as usize),
+                        _ => panic!("expected array"),
+                    };
+                    let len = self.size_of(&elem_type);
+
+                    for ii in 0..array_len {
+                        fn_env.nest();
+
+                        let elem = Var::new(array.range(ii * len, len).to_vec(), var.span);
+                        let var_info = VarInfo::new(elem, false, Some(elem_type.clone()));
+                        self.add_local_var(fn_env, var.value.clone(), var_info);
+
+                        self.compile_block(fn_env, body)?;
+
+                        fn_env.pop();
+                    }
+                }
+            },
+            StmtKind::If { cond, then_, else_ } => {
+                let cond = self
+                    .compute_expr(fn_env, cond)?
+                    .ok_or_else(|| self.error(ErrorKind::CannotComputeExpression, cond.span))?
+                    .value(self, fn_env)[0]
+                    .clone();
+
+                // the assertions of a block are only enforced if the block is taken,
+                // which also depends on the blocks of the outer `if` statements
+                let outer = self.condition.clone();
+                let then_cond = match &outer {
+                    None => cond.clone(),
+                    Some(outer) => boolean::and(self, outer, &cond, stmt.span)[0].clone(),
+                };
+
+                let before = fn_env.clone();
+                self.condition = Some(then_cond);
+                self.compile_block(fn_env, then_)?;
+                let then_env = std::mem::replace(fn_env, before);
+
+                if !else_.is_empty() {
+                    let not_cond = boolean::not(self, &cond, stmt.span)[0].clone();
+                    let else_cond = match &outer {
+                        None => not_cond,
+                        Some(outer) => boolean::and(self, outer, &not_cond, stmt.span)[0].clone(),
+                    };
+
+                    self.condition = Some(else_cond);
+                    self.compile_block(fn_env, else_)?;
+                }
+                self.condition = outer;
+
+                // the variables mutated in the blocks take the values of the block that was taken
+                let cond = Var::new_cvar(cond, stmt.span);
+                fn_env.merge_blocks(&then_env, |then_var, else_var| {
+                    field::if_else(self, &cond, then_var, else_var, stmt.span)
+                });
+            }
             StmtKind::Expr(expr) => {
                 // compute the expression
                 let var = self.compute_expr(fn_env, expr)?;
@@ -199,12 +319,12 @@ impl<B: Backend> CircuitWriter<B> {
     ) -> Result<Option<Var<B::Field, B::Var>>> {
         assert!(!function.is_main());
 
-        // create new fn_env
-        let fn_env = &mut FnEnv::new();
-
         // set arguments
         assert_eq!(function.sig.arguments.len(), args.len());
 
+        // create new fn_env, for the generic array sizes observed at this call site
+        let fn_env = &mut FnEnv::for_call(function, &args);
+
         for (name, var_info) in function.sig.arguments.iter().zip(args) {
             self.add_local_var(fn_env, name.name.value.clone(), var_info);
         }
@@ -225,6 +345,10 @@ impl<B: Backend> CircuitWriter<B> {
                 assert_eq!(input.len(), 1);
                 boolean::check(self, &input[0], span);
             }
+            TyKind::U8 | TyKind::U16 | TyKind::U32 | TyKind::U64 => {
+                assert_eq!(input.len(), 1);
+                uint::check(self, &input[0], input_typ, span)?;
+            }
             TyKind::Array(tykind, _) => {
                 let el_size = self.size_of(tykind);
                 for el in input.chunks(el_size) {
@@ -236,6 +360,35 @@ impl<B: Backend> CircuitWriter<B> {
                 name: struct_name,
             } => {
                 let qualified = FullyQualified::new(module, &struct_name);
+
+                if let Some(enum_info) = self.enum_info(&qualified).cloned() {
+                    // the values held by the variants share the same cells,
+                    // so they can't be constrained depending on their type
+                    let mut typ = enum_info.variants.iter().flat_map(|(_, types)| types);
+                    if let Some(typ) = typ.find(|typ| **typ != TyKind::Field) {
+                        return Err(self.error(ErrorKind::InvalidEnumInput(typ.clone()), span));
+                    }
+
+                    // the tag must be the one of a variant:
+                    // tag * (tag - 1) * ... * (tag - (num_variants - 1)) = 0
+                    let tag = &input[0];
+                    let mut acc = tag.clone();
+                    for ii in 1..enum_info.variants.len() {
+                        let ii = ConstOrCell::Const(B::Field::from(ii as u64));
+                        let diff = field::sub(self, tag, &ii, span);
+                        acc = field::mul(self, &acc, &diff[0], span)[0].clone();
+                    }
+
+                    match acc {
+                        ConstOrCell::Cell(cvar) => {
+                            self.backend.assert_eq_const(&cvar, B::Field::zero(), span)
+                        }
+                        ConstOrCell::Const(_) => unreachable!("inputs are not constants"),
+                    }
+
+                    return Ok(());
+                }
+
                 let struct_info = self
                     .struct_info(&qualified)
                     .ok_or(self.error(ErrorKind::UnexpectedError("struct not found"), span))?
@@ -249,7 +402,15 @@ impl<B: Backend> CircuitWriter<B> {
                     offset += len;
                 }
             }
-            TyKind::BigInt => unreachable!(),
+            TyKind::Tuple(types) => {
+                let mut offset = 0;
+                for typ in types {
+                    let len = self.size_of(typ);
+                    self.constrain_inputs_to_main(&input[offset..(offset + len)], typ, span)?;
+                    offset += len;
+                }
+            }
+            TyKind::BigInt | TyKind::GenericSizedArray(..) => unreachable!(),
         };
         Ok(())
     }
@@ -291,6 +452,66 @@ impl<B: Backend> CircuitWriter<B> {
         }
     }
 
+    /// Returns the type of the unsigned integers a binary operation is on, if any,
+    /// and whether it is on booleans.
+    fn binary_op_operand_types(&self, lhs: &Expr, rhs: &Expr) -> (Option<TyKind>, bool) {
+        // arithmetic on unsigned integers is overflow-checked,
+        // and they are the only values that can be compared or used in bitwise operations
+        let uint_typ = [lhs, rhs]
+            .into_iter()
+            .filter_map(|operand| self.expr_type(operand))
+            .find(|typ| typ.uint_bits().is_some())
+            .cloned();
+        let is_bool = matches!(self.expr_type(lhs), Some(TyKind::Bool));
+
+        (uint_typ, is_bool)
+    }
+
+    /// Applies a binary operation to the values of its operands.
+    fn compile_binary_op(
+        &mut self,
+        op: &Op2,
+        lhs: &Var<B::Field, B::Var>,
+        rhs: &Var<B::Field, B::Var>,
+        uint_typ: Option<TyKind>,
+        is_bool: bool,
+        span: Span,
+    ) -> Result<Var<B::Field, B::Var>> {
+        let res = match (op, uint_typ) {
+            (Op2::Addition, Some(typ)) => uint::add(self, &lhs[0], &rhs[0], &typ, span)?,
+            (Op2::Subtraction, Some(typ)) => uint::sub(self, &lhs[0], &rhs[0], &typ, span)?,
+            (Op2::Multiplication, Some(typ)) => uint::mul(self, &lhs[0], &rhs[0], &typ, span)?,
+            (Op2::Addition, None) => field::add(self, &lhs[0], &rhs[0], span),
+            (Op2::Subtraction, None) => field::sub(self, &lhs[0], &rhs[0], span),
+            (Op2::Multiplication, None) => field::mul(self, &lhs[0], &rhs[0], span),
+            (Op2::Equality, _) => field::equal(self, &lhs, &rhs, span),
+            (Op2::Inequality, _) => field::not_equal(self, &lhs, &rhs, span),
+            (Op2::LessThan, typ) => uint::less_than(self, &lhs[0], &rhs[0], typ.as_ref(), span)?,
+            (Op2::LessThanOrEqual, typ) => {
+                uint::less_than_or_equal(self, &lhs[0], &rhs[0], typ.as_ref(), span)?
+            }
+            (Op2::GreaterThan, typ) => uint::less_than(self, &rhs[0], &lhs[0], typ.as_ref(), span)?,
+            (Op2::GreaterThanOrEqual, typ) => {
+                uint::less_than_or_equal(self, &rhs[0], &lhs[0], typ.as_ref(), span)?
+            }
+            (Op2::BitwiseAnd, None) if is_bool => boolean::and(self, &lhs[0], &rhs[0], span),
+            (Op2::BitwiseOr, None) if is_bool => boolean::or(self, &lhs[0], &rhs[0], span),
+            (Op2::BitwiseXor, None) if is_bool => boolean::xor(self, &lhs[0], &rhs[0], span),
+            (Op2::BitwiseAnd, typ) => uint::and(self, &lhs[0], &rhs[0], typ.as_ref(), span)?,
+            (Op2::BitwiseOr, typ) => uint::or(self, &lhs[0], &rhs[0], typ.as_ref(), span)?,
+            (Op2::BitwiseXor, typ) => uint::xor(self, &lhs[0], &rhs[0], typ.as_ref(), span)?,
+            (Op2::ShiftLeft, typ) => uint::shift_left(self, &lhs[0], &rhs[0], typ.as_ref(), span)?,
+            (Op2::ShiftRight, typ) => {
+                uint::shift_right(self, &lhs[0], &rhs[0], typ.as_ref(), span)?
+            }
+            (Op2::BoolAnd, _) => boolean::and(self, &lhs[0], &rhs[0], span),
+            (Op2::BoolOr, _) => boolean::or(self, &lhs[0], &rhs[0], span),
+            (Op2::Division, _) => field::div(self, &lhs[0], &rhs[0], span)?,
+        };
+
+        Ok(res)
+    }
+
     fn compute_expr(
         &mut self,
         fn_env: &mut FnEnv<B::Field, B::Var>,
@@ -333,7 +554,7 @@ impl<B: Backend> CircuitWriter<B> {
                     // we pass variables by values always
                     let var = var.value(self, fn_env);
 
-                    let typ = self.expr_type(arg).cloned();
+                    let typ = self.expr_type(arg).map(|typ| fn_env.resolve_generics(typ));
                     let mutable = false; // TODO: mut keyword in arguments?
                     let var_info = VarInfo::new(var, mutable, typ);
 
@@ -347,6 +568,11 @@ impl<B: Backend> CircuitWriter<B> {
                         res.map(|r| r.map(VarOrRef::Var))
                     }
 
+                    // the output of a hint is computed outside of the circuit
+                    FnKind::Native(func) if func.is_hint => self
+                        .compile_hint_call(&func, vars, expr.span)
+                        .map(|r| r.map(VarOrRef::Var)),
+
                     // fn_name(args)
                     // ^^^^^^^
                     FnKind::Native(func) => {
@@ -401,6 +627,30 @@ impl<B: Backend> CircuitWriter<B> {
                 Ok(Some(var))
             }
 
+            ExprKind::TupleAccess { tuple, idx } => {
+                // get var behind the tuple
+                let tuple_var = self
+                    .compute_expr(fn_env, tuple)?
+                    .ok_or_else(|| self.error(ErrorKind::CannotComputeExpression, tuple.span))?;
+
+                // get the types of the elements of the tuple
+                let tuple_typ = self
+                    .expr_type(tuple)
+                    .ok_or_else(|| self.error(ErrorKind::CannotComputeExpression, tuple.span))?;
+                let types = match fn_env.resolve_generics(tuple_typ) {
+                    TyKind::Tuple(types) => types,
+                    _ => panic!("expected tuple"),
+                };
+
+                // find range of element
+                let start: usize = types[..*idx].iter().map(|typ| self.size_of(typ)).sum();
+                let len = self.size_of(&types[*idx]);
+
+                // narrow the variable to the given range
+                let var = tuple_var.narrow(start, len);
+                Ok(Some(var))
+            }
+
             // `Thing.method(args)` or `thing.method(args)`
             ExprKind::MethodCall {
                 lhs,
@@ -467,7 +717,7 @@ impl<B: Backend> CircuitWriter<B> {
                     let mutable = false;
                     let var = var.value(self, fn_env);
 
-                    let typ = self.expr_type(arg).cloned();
+                    let typ = self.expr_type(arg).map(|typ| fn_env.resolve_generics(typ));
                     let var_info = VarInfo::new(var, mutable, typ);
 
                     vars.push(var_info);
@@ -531,23 +781,44 @@ impl<B: Backend> CircuitWriter<B> {
                 Ok(None)
             }
 
+            ExprKind::CompoundAssignment { op, lhs, rhs } => {
+                let (uint_typ, is_bool) = self.binary_op_operand_types(lhs, rhs);
+
+                // the place of lhs is only evaluated once
+                let place = self.compute_expr(fn_env, lhs)?.unwrap();
+                let VarOrRef::Ref {
+                    var_name,
+                    start,
+                    len,
+                } = place
+                else {
+                    panic!("can't reassign this non-mutable variable");
+                };
+                let current = {
+                    let var_info = self.get_local_var(fn_env, &var_name);
+                    Var::new(var_info.var.range(start, len).to_vec(), var_info.var.span)
+                };
+
+                let rhs = self.compute_expr(fn_env, rhs)?.unwrap();
+                let rhs = rhs.value(self, fn_env);
+
+                let res =
+                    self.compile_binary_op(op, &current, &rhs, uint_typ, is_bool, expr.span)?;
+                fn_env.reassign_var_range(&var_name, res, start, len);
+
+                Ok(None)
+            }
+
             ExprKind::BinaryOp { op, lhs, rhs, .. } => {
+                let (uint_typ, is_bool) = self.binary_op_operand_types(lhs, rhs);
+
                 let lhs = self.compute_expr(fn_env, lhs)?.unwrap();
                 let rhs = self.compute_expr(fn_env, rhs)?.unwrap();
 
                 let lhs = lhs.value(self, fn_env);
                 let rhs = rhs.value(self, fn_env);
 
-                let res = match op {
-                    Op2::Addition => field::add(self, &lhs[0], &rhs[0], expr.span),
-                    Op2::Subtraction => field::sub(self, &lhs[0], &rhs[0], expr.span),
-                    Op2::Multiplication => field::mul(self, &lhs[0], &rhs[0], expr.span),
-                    Op2::Equality => field::equal(self, &lhs, &rhs, expr.span),
-                    Op2::Inequality => field::not_equal(self, &lhs, &rhs, expr.span),
-                    Op2::BoolAnd => boolean::and(self, &lhs[0], &rhs[0], expr.span),
-                    Op2::BoolOr => boolean::or(self, &lhs[0], &rhs[0], expr.span),
-                    Op2::Division => todo!(),
-                };
+                let res = self.compile_binary_op(op, &lhs, &rhs, uint_typ, is_bool, expr.span)?;
 
                 Ok(Some(VarOrRef::Var(res)))
             }
@@ -557,7 +828,8 @@ impl<B: Backend> CircuitWriter<B> {
 
                 let var = var.value(self, fn_env);
 
-                todo!()
+                let res = field::neg(self, &var[0], expr.span);
+                Ok(Some(VarOrRef::Var(res)))
             }
 
             ExprKind::Not(b) => {
@@ -620,30 +892,38 @@ impl<B: Backend> CircuitWriter<B> {
                 let idx_var = self
                     .compute_expr(fn_env, idx)?
                     .ok_or_else(|| self.error(ErrorKind::CannotComputeExpression, expr.span))?;
-                let idx = idx_var
-                    .constant()
-                    .ok_or_else(|| self.error(ErrorKind::ExpectedConstant, expr.span))?;
-                let idx: BigUint = idx.into();
-                let idx: usize = idx.try_into().unwrap();
 
                 // retrieve the type of the elements in the array
                 let array_typ = self.expr_type(array).expect("cannot find type of array");
+                let array_typ = fn_env.resolve_generics(array_typ);
 
-                let elem_type = match array_typ {
-                    TyKind::Array(ty, array_len) => {
-                        if idx >= (*array_len as usize) {
-                            return Err(self.error(
-                                ErrorKind::ArrayIndexOutOfBounds(idx, *array_len as usize - 1),
-                                expr.span,
-                            ));
-                        }
-                        ty
-                    }
+                let (elem_type, array_len) = match array_typ {
+                    TyKind::Array(ty, array_len) => (ty, array_len as usize),
                     _ => panic!("expected array"),
                 };
 
                 // compute the size of each element in the array
-                let len = self.size_of(elem_type);
+                let len = self.size_of(&elem_type);
+
+                // if the index is not a constant, the element is selected in the circuit
+                let idx = match idx_var.constant() {
+                    Some(idx) => idx,
+                    None => {
+                        let array = var.value(self, fn_env);
+                        let idx = idx_var.value(self, fn_env);
+                        let res = field::multiplex(self, &array, len, &idx[0], expr.span)?;
+                        return Ok(Some(VarOrRef::Var(res)));
+                    }
+                };
+                let idx: BigUint = idx.into();
+                let idx: usize = idx.try_into().unwrap();
+
+                if idx >= array_len {
+                    return Err(self.error(
+                        ErrorKind::ArrayIndexOutOfBounds(idx, array_len - 1),
+                        expr.span,
+                    ));
+                }
 
                 // compute the real index
                 let start = idx * len;
@@ -677,6 +957,130 @@ impl<B: Backend> CircuitWriter<B> {
                 Ok(Some(var))
             }
 
+            ExprKind::TupleDeclaration(items) => {
+                let mut cvars = vec![];
+
+                for item in items {
+                    let var = self
+                        .compute_expr(fn_env, item)?
+                        .ok_or_else(|| self.error(ErrorKind::CannotComputeExpression, item.span))?;
+                    cvars.extend(var.value(self, fn_env).cvars);
+                }
+
+                let var = VarOrRef::Var(Var::new(cvars, expr.span));
+
+                Ok(Some(var))
+            }
+
+            ExprKind::EnumVariant {
+                enum_name,
+                variant,
+                args,
+            } => {
+                let typ = TyKind::Custom {
+                    module: enum_name.module.clone(),
+                    name: enum_name.name.clone(),
+                };
+                let qualified = FullyQualified::new(&enum_name.module, &enum_name.name);
+                let enum_info = self
+                    .enum_info(&qualified)
+                    .expect("enum info not found for enum");
+                let (tag, _) = enum_info
+                    .variant(&variant.value)
+                    .expect("variant not found in enum");
+
+                // the tag comes first
+                let mut cvars = vec![ConstOrCell::Const(B::Field::from(tag as u64))];
+
+                // followed by the values held by the variant
+                for arg in args {
+                    let var = self
+                        .compute_expr(fn_env, arg)?
+                        .ok_or_else(|| self.error(ErrorKind::CannotComputeExpression, arg.span))?;
+                    cvars.extend(var.value(self, fn_env).cvars);
+                }
+
+                // and padded with zeros, to fit the values of any variant
+                cvars.resize(self.size_of(&typ), ConstOrCell::Const(B::Field::zero()));
+
+                let var = VarOrRef::Var(Var::new(cvars, expr.span));
+
+                Ok(Some(var))
+            }
+
+            ExprKind::Match { matched, arms } => {
+                let matched_var = self
+                    .compute_expr(fn_env, matched)?
+                    .ok_or_else(|| self.error(ErrorKind::CannotComputeExpression, matched.span))?
+                    .value(self, fn_env);
+
+                let (module, name) = match self.expr_type(matched) {
+                    Some(TyKind::Custom { module, name }) => (module, name),
+                    _ => panic!("expected enum"),
+                };
+                let enum_info = self
+                    .enum_info(&FullyQualified::new(module, name))
+                    .expect("enum info not found for enum")
+                    .clone();
+
+                // all arms are computed, each with the values of its variant
+                let mut arms_vars = vec![];
+                for arm in arms {
+                    fn_env.nest();
+
+                    let tag = match &arm.pattern {
+                        MatchPattern::Variant {
+                            variant, bindings, ..
+                        } => {
+                            let (tag, types) = enum_info
+                                .variant(&variant.value)
+                                .expect("variant not found in enum");
+
+                            let mut start = 1;
+                            for (binding, typ) in bindings.iter().zip(types) {
+                                let len = self.size_of(typ);
+                                if let Some(binding) = binding {
+                                    let var = Var::new(
+                                        matched_var.range(start, len).to_vec(),
+                                        binding.span,
+                                    );
+                                    let var_info = VarInfo::new(var, false, Some(typ.clone()));
+                                    self.add_local_var(fn_env, binding.value.clone(), var_info);
+                                }
+                                start += len;
+                            }
+
+                            Some(tag)
+                        }
+                        MatchPattern::Wildcard(_) => None,
+                    };
+
+                    let arm_var = self
+                        .compute_expr(fn_env, &arm.expr)?
+                        .ok_or_else(|| self.error(ErrorKind::CannotComputeExpression, arm.span))?
+                        .value(self, fn_env);
+
+                    fn_env.pop();
+
+                    arms_vars.push((tag, arm_var));
+                }
+
+                // the type checker made sure that the arms are exhaustive,
+                // so the last arm is taken if none of the previous ones are
+                let (_, mut res) = arms_vars.pop().expect("empty match");
+
+                // the other arms are selected based on the tag
+                let tag = Var::new_cvar(matched_var[0].clone(), matched.span);
+                for (arm_tag, arm_var) in arms_vars.into_iter().rev() {
+                    let arm_tag = arm_tag.expect("a wildcard can only be the last arm");
+                    let arm_tag = Var::new_constant(B::Field::from(arm_tag as u64), expr.span);
+                    let cond = field::equal(self, &tag, &arm_tag, expr.span);
+                    res = field::if_else(self, &cond, &arm_var, &res, expr.span);
+                }
+
+                Ok(Some(VarOrRef::Var(res)))
+            }
+
             ExprKind::CustomTypeDeclaration { custom: _, fields } => {
                 // create the struct by just concatenating all of its cvars
                 let mut cvars = vec![];
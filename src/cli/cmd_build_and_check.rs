@@ -1,31 +1,43 @@
+use std::collections::HashSet;
+
 use camino::Utf8PathBuf as PathBuf;
 use clap::ValueEnum;
 use miette::{Context, IntoDiagnostic};
 
 use crate::{
     backends::{
-        kimchi::{
-            prover::{ProverIndex, VerifierIndex},
-            KimchiVesta,
-        },
+        kimchi::KimchiVesta,
         r1cs::{snarkjs::SnarkjsExporter, R1CS},
         Backend, BackendField, BackendKind,
     },
     cli::packages::path_to_package,
-    compiler::{compile, generate_witness, typecheck_next_file, Sources},
+    compiler::{
+        compile, generate_witness, get_nast, typecheck_next_file, typecheck_std_modules,
+        IntoMiette, Sources,
+    },
+    error::{Error, ErrorKind},
     inputs::{parse_inputs, JsonInputs},
+    parser::RootKind,
     type_checker::TypeChecker,
+    witness::CompiledCircuit,
 };
 
 use super::packages::{
-    get_deps_of_package, is_lib, validate_package_and_get_manifest, DependencyGraph, UserRepo,
+    get_deps_of_package, is_lib, validate_package_and_get_manifest, DependencyGraph,
+    QualifiedModule, UserRepo, SELF_MODULE,
 };
 
-const COMPILED_DIR: &str = "compiled";
+pub const COMPILED_DIR: &str = "compiled";
+
+/// Default filename of the prover artifact, within [COMPILED_DIR].
+pub const PROVER_ARTIFACT: &str = "prover.nope";
+
+/// Default filename of the verifier artifact, within [COMPILED_DIR].
+pub const VERIFIER_ARTIFACT: &str = "verifier.nope";
 
 /// Possible backends to be used in proving.
 #[derive(Clone, ValueEnum)]
-enum BackendOpt {
+pub enum BackendOpt {
     KimchiVesta,
     R1csBls12_381,
     R1csBn254,
@@ -55,15 +67,21 @@ pub struct CmdBuild {
     #[clap(long)]
     debug: bool,
 
+    /// Backend to build the circuit for.
+    /// The R1CS backends run a (circuit-specific) Groth16 setup.
+    #[clap(short, long, default_value = "kimchi-vesta")]
+    #[arg(value_enum)]
+    backend: BackendOpt,
+
     /// In case the path points to a binary,
     /// outputs the prover parameters to the given file.
-    /// Defaults to `prover.nope`
+    /// Defaults to `compiled/prover.nope`
     #[clap(long, value_parser)]
     prover_params: Option<PathBuf>,
 
     /// In case the path points to a binary,
     /// outputs the verifier parameters to the given file.
-    /// Defaults to `verifier.nope`
+    /// Defaults to `compiled/verifier.nope`
     #[clap(long, value_parser)]
     verifier_params: Option<PathBuf>,
 }
@@ -73,46 +91,43 @@ pub fn cmd_build(args: CmdBuild) -> miette::Result<()> {
         .path
         .unwrap_or_else(|| std::env::current_dir().unwrap().try_into().unwrap());
 
-    let (sources, prover_index, verifier_index) = build(&curr_dir, args.asm, args.debug)?;
-
     // create COMPILED_DIR
     let compiled_path = curr_dir.join(COMPILED_DIR);
     if compiled_path.exists() && !compiled_path.is_dir() {
         miette::bail!("There's a filename called `{}` which collides with noname. Please delete that file first.", compiled_path);
     }
 
-    if args.prover_params.is_none() && args.verifier_params.is_none() && !compiled_path.exists() {
+    if (args.prover_params.is_none() || args.verifier_params.is_none()) && !compiled_path.exists() {
         std::fs::create_dir(&compiled_path)
             .into_diagnostic()
             .wrap_err(format!("could not create dir at `{compiled_path}`"))?;
     }
 
-    // write prover
+    // write prover and verifier artifacts
     let prover_params = args
         .prover_params
-        .unwrap_or(compiled_path.join("prover.nope"));
-    /*
-    std::fs::write(&prover_params, rmp_serde::to_vec(&prover_index).unwrap())
-        .into_diagnostic()
-        .wrap_err(format!(
-            "could not write prover params to `{prover_params}`"
-        ))?;
-        */
-
-    // write verifier
+        .unwrap_or(compiled_path.join(PROVER_ARTIFACT));
     let verifier_params = args
         .verifier_params
-        .unwrap_or(compiled_path.join("verifier.nope"));
-    std::fs::write(
-        &verifier_params,
-        rmp_serde::to_vec(&verifier_index).unwrap(),
-    )
-    .into_diagnostic()
-    .wrap_err(format!(
-        "could not write prover params to `{prover_params}`"
-    ))?;
-
-    println!("successfully built");
+        .unwrap_or(compiled_path.join(VERIFIER_ARTIFACT));
+
+    match BackendKind::from(args.backend) {
+        BackendKind::KimchiVesta(kimchi_vesta) => {
+            let (_sources, compiled_circuit) =
+                build(kimchi_vesta, &curr_dir, args.asm, args.debug)?;
+            compiled_circuit.write_artifacts(&prover_params, &verifier_params)?;
+        }
+        BackendKind::R1csBls12_381(r1cs) => {
+            let (_sources, compiled_circuit) = build(r1cs, &curr_dir, args.asm, args.debug)?;
+            compiled_circuit.write_groth16_artifacts(&prover_params, &verifier_params)?;
+        }
+        BackendKind::R1csBn254(r1cs) => {
+            let (_sources, compiled_circuit) = build(r1cs, &curr_dir, args.asm, args.debug)?;
+            compiled_circuit.write_groth16_artifacts(&prover_params, &verifier_params)?;
+        }
+    }
+
+    println!("successfully built (prover artifact at `{prover_params}`, verifier artifact at `{verifier_params}`)");
 
     //
     Ok(())
@@ -137,7 +152,7 @@ pub fn cmd_check(args: CmdCheck) -> miette::Result<()> {
     Ok(())
 }
 
-fn produce_all_asts<B: Backend>(path: &PathBuf) -> miette::Result<(Sources, TypeChecker<B>)> {
+pub fn produce_all_asts<B: Backend>(path: &PathBuf) -> miette::Result<(Sources, TypeChecker<B>)> {
     // find manifest
     let manifest = validate_package_and_get_manifest(&path, false)?;
 
@@ -162,20 +177,15 @@ fn produce_all_asts<B: Backend>(path: &PathBuf) -> miette::Result<(Sources, Type
     let mut tast = TypeChecker::new();
 
     for dep in dep_graph.from_leaves_to_roots() {
-        let path = path_to_package(&dep);
+        let src_dir = path_to_package(&dep).join("src");
+        let lib_file = src_dir.join("lib.no");
 
-        let lib_file = path.join("src").join("lib.no");
-        let code = std::fs::read_to_string(&lib_file)
-            .into_diagnostic()
-            .wrap_err_with(|| format!("could not read file `{path}`"))?;
-
-        let lib_file_str = lib_file.to_string();
-        node_id = typecheck_next_file(
+        let mut modules = PackageModules::new(Some(&dep), src_dir);
+        node_id = modules.typecheck(
             &mut tast,
-            Some(dep),
             &mut sources,
-            lib_file_str.clone(),
-            code,
+            Some(dep.clone().into()),
+            &lib_file,
             node_id,
         )?;
     }
@@ -193,46 +203,151 @@ fn produce_all_asts<B: Backend>(path: &PathBuf) -> miette::Result<(Sources, Type
         main_file
     };
 
-    let code = std::fs::read_to_string(&file_path)
-        .into_diagnostic()
-        .wrap_err_with(|| format!("could not read file `{file_path}`"))?;
-
-    let _node_id = typecheck_next_file(
-        &mut tast,
-        None,
-        &mut sources,
-        file_path.to_string(),
-        code,
-        node_id,
-    )?;
+    let mut modules = PackageModules::new(None, src_dir);
+    let _node_id = modules.typecheck(&mut tast, &mut sources, None, &file_path, node_id)?;
 
     Ok((sources, tast))
 }
 
-pub fn build(
+/// The modules of a package, which are the files `src/<module>.no` besides `lib.no` or `main.no`.
+/// They are imported with `use self::module;`, and type checked before the files that import them.
+struct PackageModules<'a> {
+    /// The package, or `None` for the package being built.
+    package: Option<&'a UserRepo>,
+
+    src_dir: PathBuf,
+
+    /// The modules that were already type checked.
+    checked: HashSet<String>,
+
+    /// The modules being type checked (to detect cycles).
+    in_progress: Vec<String>,
+}
+
+impl<'a> PackageModules<'a> {
+    fn new(package: Option<&'a UserRepo>, src_dir: PathBuf) -> Self {
+        Self {
+            package,
+            src_dir,
+            checked: HashSet::new(),
+            in_progress: vec![],
+        }
+    }
+
+    /// Type checks the main file of the package (`lib.no` or `main.no`), along with the modules it uses.
+    fn typecheck<B: Backend>(
+        &mut self,
+        tast: &mut TypeChecker<B>,
+        sources: &mut Sources,
+        this_module: Option<QualifiedModule>,
+        file_path: &PathBuf,
+        node_id: usize,
+    ) -> miette::Result<usize> {
+        // the main file of the package can't be imported by its modules
+        if let Some(name) = file_path.file_stem() {
+            self.in_progress.push(name.to_string());
+        }
+
+        self.typecheck_file(tast, sources, this_module, file_path, node_id)
+    }
+
+    /// Type checks a file of the package, after the modules of the package that it imports.
+    fn typecheck_file<B: Backend>(
+        &mut self,
+        tast: &mut TypeChecker<B>,
+        sources: &mut Sources,
+        this_module: Option<QualifiedModule>,
+        file_path: &PathBuf,
+        node_id: usize,
+    ) -> miette::Result<usize> {
+        let code = std::fs::read_to_string(file_path)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("could not read file `{file_path}`"))?;
+
+        let is_lib = this_module.is_some();
+        let (nast, mut node_id) =
+            get_nast::<B>(this_module, sources, file_path.to_string(), code, node_id)
+                .into_miette(sources)?;
+
+        let submodules: Vec<_> = nast
+            .ast
+            .0
+            .iter()
+            .filter_map(|root| match &root.kind {
+                RootKind::Use(use_path) if use_path.module.value == SELF_MODULE => {
+                    Some(use_path.submodule.clone())
+                }
+                _ => None,
+            })
+            .collect();
+
+        for submodule in submodules {
+            let name = &submodule.value;
+            if self.checked.contains(name) {
+                continue;
+            }
+
+            if self.in_progress.contains(name) {
+                let err = Error::new(
+                    "name resolution",
+                    ErrorKind::CyclicSubmodule(name.clone()),
+                    submodule.span,
+                );
+                return Err(err).into_miette(sources);
+            }
+
+            let submodule_path = self.src_dir.join(format!("{name}.no"));
+            if !submodule_path.exists() {
+                let err = Error::new(
+                    "name resolution",
+                    ErrorKind::SubmoduleNotFound(name.clone(), submodule_path.to_string()),
+                    submodule.span,
+                );
+                return Err(err).into_miette(sources);
+            }
+
+            self.in_progress.push(name.clone());
+            let submodule_module = QualifiedModule::Submodule {
+                package: self.package.cloned(),
+                name: name.clone(),
+            };
+            node_id = self.typecheck_file(
+                tast,
+                sources,
+                Some(submodule_module),
+                &submodule_path,
+                node_id,
+            )?;
+            self.in_progress.pop();
+
+            self.checked.insert(name.clone());
+        }
+
+        node_id = typecheck_std_modules(tast, sources, &nast, node_id).into_miette(sources)?;
+
+        tast.analyze(nast, is_lib).into_miette(sources)?;
+
+        Ok(node_id)
+    }
+}
+
+pub fn build<B: Backend>(
+    backend: B,
     curr_dir: &PathBuf,
     asm: bool,
     debug: bool,
-) -> miette::Result<(Sources, ProverIndex, VerifierIndex)> {
+) -> miette::Result<(Sources, CompiledCircuit<B>)> {
     // produce all TASTs
     let (sources, tast) = produce_all_asts(curr_dir)?;
 
-    // produce indexes
-    let double_generic_gate_optimization = false;
-
-    let kimchi_vesta = KimchiVesta::new(double_generic_gate_optimization);
-    let compiled_circuit = compile(&sources, tast, kimchi_vesta)?;
+    // compile the circuit
+    let compiled_circuit = compile(&sources, tast, backend)?;
 
     if asm {
         println!("{}", compiled_circuit.asm(&sources, debug));
     }
 
-    // TODO: cache artifacts
-
-    // produce indexes
-    let (prover_index, verifier_index) = compiled_circuit.compile_to_indexes()?;
-
-    Ok((sources, prover_index, verifier_index))
+    Ok((sources, compiled_circuit))
 }
 
 #[derive(clap::Parser)]
@@ -350,8 +465,8 @@ pub fn cmd_run(args: CmdRun) -> miette::Result<()> {
     };
 
     match BackendKind::from(args.backend) {
-        BackendKind::KimchiVesta(_) => {
-            unimplemented!("kimchi-vesta backend is not yet supported for this command")
+        BackendKind::KimchiVesta(kimchi_vesta) => {
+            run_kimchi_backend(kimchi_vesta, &curr_dir, public_inputs, private_inputs)?
         }
         BackendKind::R1csBls12_381(r1cs) => {
             run_r1cs_backend(r1cs, &curr_dir, public_inputs, private_inputs)?
@@ -364,6 +479,35 @@ pub fn cmd_run(args: CmdRun) -> miette::Result<()> {
     Ok(())
 }
 
+/// Generates the witness of a kimchi circuit, and writes it to `output.witness.json`
+/// (see [GeneratedWitness::to_json](crate::backends::kimchi::GeneratedWitness::to_json) for the format).
+fn run_kimchi_backend(
+    kimchi_vesta: KimchiVesta,
+    curr_dir: &PathBuf,
+    public_inputs: JsonInputs,
+    private_inputs: JsonInputs,
+) -> miette::Result<()> {
+    let (sources, tast) = produce_all_asts(curr_dir)?;
+
+    let compiled_circuit = compile(&sources, tast, kimchi_vesta)?;
+
+    let generated_witness =
+        generate_witness(&compiled_circuit, &sources, public_inputs, private_inputs)?;
+
+    let witness_output_path = curr_dir.join("output.witness.json");
+    let json = serde_json::to_string_pretty(&generated_witness.to_json()).into_diagnostic()?;
+    std::fs::write(&witness_output_path, json)
+        .into_diagnostic()
+        .wrap_err(format!(
+            "could not write the witness to `{witness_output_path}`"
+        ))?;
+
+    // display the info for the generated file
+    println!("Kimchi witness file generated at: {}", witness_output_path);
+
+    Ok(())
+}
+
 fn run_r1cs_backend<F>(
     r1cs: R1CS<F>,
     curr_dir: &PathBuf,
@@ -431,3 +575,100 @@ fn typecheck_file<B: Backend>(path: &PathBuf) -> miette::Result<(TypeChecker<B>,
 
     Ok((tast, sources))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes the given files in the `src` directory of a temporary package,
+    /// and returns that directory.
+    fn write_package(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("noname_{name}_{}", std::process::id()));
+        let src_dir = PathBuf::from_path_buf(dir.join("src")).unwrap();
+        std::fs::create_dir_all(&src_dir).unwrap();
+        for (filename, code) in files {
+            std::fs::write(src_dir.join(filename), code).unwrap();
+        }
+        src_dir
+    }
+
+    fn typecheck_package(src_dir: &PathBuf) -> miette::Result<()> {
+        let mut sources = Sources::new();
+        let mut tast = TypeChecker::<KimchiVesta>::new();
+        let mut modules = PackageModules::new(None, src_dir.clone());
+        modules.typecheck(&mut tast, &mut sources, None, &src_dir.join("main.no"), 0)?;
+        Ok(())
+    }
+
+    fn expect_error(src_dir: &PathBuf, expected: ErrorKind) {
+        let report = typecheck_package(src_dir).unwrap_err();
+        let help = report.help().map(|help| help.to_string());
+        assert_eq!(help, Some(expected.to_string()));
+    }
+
+    #[test]
+    fn test_nested_submodules() {
+        let src_dir = write_package(
+            "nested_submodules",
+            &[
+                (
+                    "main.no",
+                    "use self::utils;\n\nfn main(pub xx: Field) {\n    assert_eq(utils::double_plus_one(xx), 7);\n}\n",
+                ),
+                (
+                    "utils.no",
+                    "use self::constants;\n\nfn double_plus_one(xx: Field) -> Field {\n    return (xx + xx) + constants::one();\n}\n",
+                ),
+                ("constants.no", "fn one() -> Field {\n    return 1;\n}\n"),
+            ],
+        );
+
+        typecheck_package(&src_dir).unwrap();
+
+        std::fs::remove_dir_all(src_dir.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_cyclic_submodules() {
+        let src_dir = write_package(
+            "cyclic_submodules",
+            &[
+                (
+                    "main.no",
+                    "use self::aa;\n\nfn main(pub xx: Field) {\n    assert_eq(aa::id(xx), xx);\n}\n",
+                ),
+                (
+                    "aa.no",
+                    "use self::bb;\n\nfn id(xx: Field) -> Field {\n    return bb::id(xx);\n}\n",
+                ),
+                (
+                    "bb.no",
+                    "use self::aa;\n\nfn id(xx: Field) -> Field {\n    return xx;\n}\n",
+                ),
+            ],
+        );
+
+        expect_error(&src_dir, ErrorKind::CyclicSubmodule("aa".to_string()));
+
+        std::fs::remove_dir_all(src_dir.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_missing_submodule() {
+        let src_dir = write_package(
+            "missing_submodule",
+            &[(
+                "main.no",
+                "use self::missing;\n\nfn main(pub xx: Field) {\n    assert_eq(missing::id(xx), xx);\n}\n",
+            )],
+        );
+
+        let missing_path = src_dir.join("missing.no").to_string();
+        expect_error(
+            &src_dir,
+            ErrorKind::SubmoduleNotFound("missing".to_string(), missing_path),
+        );
+
+        std::fs::remove_dir_all(src_dir.parent().unwrap()).unwrap();
+    }
+}
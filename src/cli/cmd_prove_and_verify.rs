@@ -1,9 +1,24 @@
 use camino::Utf8PathBuf as PathBuf;
 use miette::{Context, IntoDiagnostic};
+use serde::{de::DeserializeOwned, Serialize};
 
-use crate::inputs::parse_inputs;
+use crate::{
+    backends::{
+        kimchi::{prover::VerifierIndex, KimchiVesta},
+        r1cs::{
+            groth16::{Groth16VerifierIndex, PairingField},
+            R1csBls12381Field, R1csBn254Field, R1CS,
+        },
+        Backend, BackendField, BackendKind,
+    },
+    imports::FnKind,
+    inputs::{parse_inputs, PublicIO},
+    witness::CompiledCircuit,
+};
 
-use super::cmd_build_and_check::build;
+use super::cmd_build_and_check::{
+    build, BackendOpt, COMPILED_DIR, PROVER_ARTIFACT, VERIFIER_ARTIFACT,
+};
 
 #[derive(clap::Parser)]
 pub struct CmdProve {
@@ -15,6 +30,15 @@ pub struct CmdProve {
     #[clap(long)]
     debug: bool,
 
+    /// Backend to use for proving. It must match the backend used by `noname build`.
+    #[clap(short, long, default_value = "kimchi-vesta")]
+    #[arg(value_enum)]
+    backend: BackendOpt,
+
+    /// Path to the prover parameters produced by `noname build`. Defaults to `compiled/prover.nope`.
+    #[clap(long, value_parser)]
+    prover_params: Option<PathBuf>,
+
     /// Path to the resulting proof. Defaults to `proof.nope`.
     #[clap(long, value_parser)]
     proof_path: Option<PathBuf>,
@@ -31,9 +55,27 @@ pub struct CmdProve {
 pub fn cmd_prove(args: CmdProve) -> miette::Result<()> {
     let curr_dir = args
         .path
+        .clone()
         .unwrap_or_else(|| std::env::current_dir().unwrap().try_into().unwrap());
 
-    let (sources, prover_index, verifier_index) = build(&curr_dir, false, args.debug)?;
+    match BackendKind::from(args.backend.clone()) {
+        BackendKind::KimchiVesta(kimchi_vesta) => prove_kimchi(kimchi_vesta, &args, &curr_dir),
+        BackendKind::R1csBls12_381(r1cs) => prove_groth16(r1cs, &args, &curr_dir),
+        BackendKind::R1csBn254(r1cs) => prove_groth16(r1cs, &args, &curr_dir),
+    }
+}
+
+fn prove_kimchi(
+    kimchi_vesta: KimchiVesta,
+    args: &CmdProve,
+    curr_dir: &PathBuf,
+) -> miette::Result<()> {
+    // the source is still needed to generate the witness
+    let (sources, compiled_circuit) = build(kimchi_vesta, curr_dir, false, args.debug)?;
+
+    // load the prover parameters
+    let prover_index =
+        compiled_circuit.prover_index_from_artifact(&prover_params(args, curr_dir))?;
 
     // parse inputs
     let public_inputs = parse_inputs(&args.public_inputs).unwrap();
@@ -45,28 +87,91 @@ pub fn cmd_prove(args: CmdProve) -> miette::Result<()> {
 
     // verify proof
     if args.debug {
+        let verifier_index = prover_index.verifier_index();
+        verifier_index.verify(full_public_inputs, proof.clone())?;
+    }
+
+    let proof_path = write_proof(args, curr_dir, &proof)?;
+    notify_proof_created(prover_index.compiled_circuit(), &proof_path, public_output);
+
+    Ok(())
+}
+
+fn prove_groth16<F: PairingField>(
+    r1cs: R1CS<F>,
+    args: &CmdProve,
+    curr_dir: &PathBuf,
+) -> miette::Result<()> {
+    // the source is still needed to generate the witness
+    let (sources, compiled_circuit) = build(r1cs, curr_dir, false, args.debug)?;
+
+    // load the prover parameters
+    let prover_index =
+        compiled_circuit.groth16_prover_index_from_artifact(&prover_params(args, curr_dir))?;
+
+    // parse inputs
+    let public_inputs = parse_inputs(&args.public_inputs).unwrap();
+    let private_inputs = parse_inputs(&args.private_inputs).unwrap();
+
+    // create proof
+    let (proof, full_public_inputs, public_output) =
+        prover_index.prove(&sources, public_inputs, private_inputs)?;
+
+    // verify proof
+    if args.debug {
+        let verifier_index = prover_index.verifier_index();
         verifier_index.verify(full_public_inputs, proof.clone())?;
     }
 
-    // serialize proof
+    let proof_path = write_proof(args, curr_dir, &proof)?;
+    notify_proof_created(prover_index.compiled_circuit(), &proof_path, public_output);
+
+    Ok(())
+}
+
+fn prover_params(args: &CmdProve, curr_dir: &PathBuf) -> PathBuf {
+    args.prover_params
+        .clone()
+        .unwrap_or_else(|| curr_dir.join(COMPILED_DIR).join(PROVER_ARTIFACT))
+}
+
+/// Serializes the proof, and returns the path it was written to.
+fn write_proof(
+    args: &CmdProve,
+    curr_dir: &PathBuf,
+    proof: &impl Serialize,
+) -> miette::Result<PathBuf> {
     let proof_path = args
         .proof_path
+        .clone()
         .unwrap_or_else(|| curr_dir.join("proof.nope"));
-    std::fs::write(&proof_path, rmp_serde::to_vec(&proof).unwrap())
+    std::fs::write(&proof_path, rmp_serde::to_vec(proof).unwrap())
         .into_diagnostic()
         .wrap_err(format!("could not write the proof to `{proof_path}`"))?;
 
-    // notification
-    if public_output.is_empty() {
-        println!(
-            "proof created at path `{proof_path}`. You can use `noname --verify` to verify it. Note that you will need to pass the same JSON-encoded public inputs as you did when creating the proof. (If you didn't use the `--public-inputs` flag, then you don't need to pass any public inputs.)",
-        );
-    } else {
-        println!("proof created at path `{proof_path}`. Since running the proof produced a  public output `{public:?}`, you will need to also pass the expected public output to the verifier (who can run `noname verify --public-output '{public:?}'`).", public=public_output);
-    }
+    Ok(proof_path)
+}
 
-    //
-    Ok(())
+fn notify_proof_created<B: Backend>(
+    compiled_circuit: &CompiledCircuit<B>,
+    proof_path: &PathBuf,
+    public_output: Vec<B::Field>,
+) {
+    let return_type = match &compiled_circuit.main_info().kind {
+        FnKind::BuiltIn(_, _) => unreachable!(),
+        FnKind::Native(fn_def) => fn_def.sig.return_type.as_ref(),
+    };
+
+    match return_type {
+        None => println!(
+            "proof created at path `{proof_path}`. You can use `noname verify` to verify it. Note that you will need to pass the same JSON-encoded public inputs as you did when creating the proof. (If you didn't use the `--public-inputs` flag, then you don't need to pass any public inputs.)",
+        ),
+        Some(typ) => {
+            let public = compiled_circuit
+                .encode_single_output(&mut public_output.into_iter(), &typ.kind);
+            println!("proof created at path `{proof_path}`. Since running the proof produced a public output `{public}`, you will need to also pass the expected public output to the verifier (who can run `noname verify --public-output '{public}'`).");
+        }
+    }
 }
 
 #[derive(clap::Parser)]
@@ -75,15 +180,24 @@ pub struct CmdVerify {
     #[clap(short, long, value_parser)]
     path: Option<PathBuf>,
 
+    /// Backend the proof was created with. It must match the backend used by `noname build`.
+    #[clap(short, long, default_value = "kimchi-vesta")]
+    #[arg(value_enum)]
+    backend: BackendOpt,
+
+    /// Path to the verifier parameters produced by `noname build`. Defaults to `compiled/verifier.nope`.
+    #[clap(long, value_parser)]
+    verifier_params: Option<PathBuf>,
+
     /// Path to the proof to verify. Defaults to `proof.nope`.
     #[clap(short, long, value_parser)]
     proof_path: Option<PathBuf>,
 
     /// JSON encoding of the public inputs. For example: `--public-inputs {"a": "1", "b": ["2", "3"]}`.
-    #[clap(short, long, value_parser)]
+    #[clap(short, long, value_parser, default_value = "{}")]
     public_inputs: String,
 
-    /// An optional expected public output, in JSON format.
+    /// The expected public output in JSON format, if the main function returns a value. For example: `--public-output '["1", "2"]'`.
     #[clap(short, long, value_parser)]
     public_output: Option<String>,
 }
@@ -91,44 +205,80 @@ pub struct CmdVerify {
 pub fn cmd_verify(args: CmdVerify) -> miette::Result<()> {
     let curr_dir = args
         .path
+        .clone()
         .unwrap_or_else(|| std::env::current_dir().unwrap().try_into().unwrap());
 
-    let (_sources, _prover_index, verifier_index) = build(&curr_dir, false, false)?;
+    // the verifier parameters contain everything needed (no source code needed)
+    let verifier_params = args
+        .verifier_params
+        .clone()
+        .unwrap_or_else(|| curr_dir.join(COMPILED_DIR).join(VERIFIER_ARTIFACT));
 
-    // parse inputs
-    let mut public_inputs = parse_inputs(&args.public_inputs).unwrap();
-
-    if let Some(public_output) = &args.public_output {
-        let public_output = parse_inputs(public_output).unwrap();
-
-        // TODO: add it to the public input
-        todo!();
-    }
-
-    // get proof
     let proof_path = args
         .proof_path
+        .clone()
         .unwrap_or_else(|| curr_dir.join("proof.nope"));
 
+    match BackendKind::from(args.backend.clone()) {
+        BackendKind::KimchiVesta(_) => {
+            let (verifier_index, public_io) = VerifierIndex::from_artifact(&verifier_params)?;
+            let full_public_inputs = full_public_inputs(&args, &public_io)?;
+            let proof = read_proof(&proof_path)?;
+            verifier_index.verify(full_public_inputs, proof)?;
+        }
+        BackendKind::R1csBls12_381(_) => {
+            verify_groth16::<R1csBls12381Field>(&args, &verifier_params, &proof_path)?
+        }
+        BackendKind::R1csBn254(_) => {
+            verify_groth16::<R1csBn254Field>(&args, &verifier_params, &proof_path)?
+        }
+    }
+
+    println!("proof at path `{proof_path}` is valid");
+
+    Ok(())
+}
+
+fn verify_groth16<F: PairingField>(
+    args: &CmdVerify,
+    verifier_params: &PathBuf,
+    proof_path: &PathBuf,
+) -> miette::Result<()> {
+    let (verifier_index, public_io) = Groth16VerifierIndex::<F>::from_artifact(verifier_params)?;
+    let full_public_inputs = full_public_inputs(args, &public_io)?;
+    let proof = read_proof(proof_path)?;
+    verifier_index.verify(full_public_inputs, proof)
+}
+
+/// Encodes the public output followed by the public inputs, as expected by the verifier.
+fn full_public_inputs<F: BackendField>(
+    args: &CmdVerify,
+    public_io: &PublicIO,
+) -> miette::Result<Vec<F>> {
+    let public_inputs = parse_inputs(&args.public_inputs).unwrap();
+
+    let public_output: Option<serde_json::Value> = args
+        .public_output
+        .as_deref()
+        .map(serde_json::from_str)
+        .transpose()
+        .into_diagnostic()
+        .wrap_err("could not parse the given public output as JSON")?;
+
+    // (errors can't point to the source code, as we don't have it)
+    public_io
+        .full_public_inputs(public_inputs, public_output)
+        .map_err(|err| miette::miette!("{}", err.kind))
+}
+
+fn read_proof<P: DeserializeOwned>(proof_path: &PathBuf) -> miette::Result<P> {
     if !proof_path.exists() {
         miette::bail!("proof does not exist at path `{proof_path}`. Perhaps pass the correct path via the `--proof-path` flag?");
     }
 
-    let proof = rmp_serde::from_read(std::fs::File::open(&proof_path).unwrap())
+    rmp_serde::from_read(std::fs::File::open(proof_path).unwrap())
         .into_diagnostic()
         .wrap_err(format!(
             "could not deserialize the given proof at `{proof_path}`"
-        ))?;
-
-    // verify proof
-    unimplemented!();
-    /*
-        verifier_index
-            .verify(full_public_inputs, proof)
-            .into_diagnostic()
-            .wrap_err("Failed to verify proof")?;
-    */
-    //
-
-    Ok(())
+        ))
 }
@@ -12,6 +12,9 @@ use super::{
     NONAME_DIRECTORY, PACKAGE_DIRECTORY,
 };
 
+/// The name used to import the modules of the current package (e.g. `use self::merkle;`).
+pub const SELF_MODULE: &str = "self";
+
 /// A dependency is a Github `user/repo` pair.
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct UserRepo {
@@ -35,6 +38,52 @@ impl std::fmt::Display for UserRepo {
     }
 }
 
+/// The fully-qualified path of a module other than the main module of the package being built.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum QualifiedModule {
+    /// The `lib.no` file of a package (or a module of the standard library).
+    Package(UserRepo),
+
+    /// The file `src/<name>.no` of a package, imported with `use self::<name>;`.
+    /// The package is `None` for the modules of the package being built.
+    Submodule {
+        package: Option<UserRepo>,
+        name: String,
+    },
+}
+
+impl QualifiedModule {
+    /// Returns the package a module belongs to (`None` for the package being built).
+    pub(crate) fn package(&self) -> Option<&UserRepo> {
+        match self {
+            QualifiedModule::Package(package) => Some(package),
+            QualifiedModule::Submodule { package, .. } => package.as_ref(),
+        }
+    }
+}
+
+impl From<UserRepo> for QualifiedModule {
+    fn from(package: UserRepo) -> Self {
+        QualifiedModule::Package(package)
+    }
+}
+
+impl std::fmt::Display for QualifiedModule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QualifiedModule::Package(package) => write!(f, "{package}"),
+            QualifiedModule::Submodule {
+                package: Some(package),
+                name,
+            } => write!(f, "{package}::{name}"),
+            QualifiedModule::Submodule {
+                package: None,
+                name,
+            } => write!(f, "{SELF_MODULE}::{name}"),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct DependencyGraph {
     /// Name of this package.
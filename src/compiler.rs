@@ -9,9 +9,17 @@ use std::collections::HashMap;
 use miette::NamedSource;
 
 use crate::{
-    backends::Backend, circuit_writer::CircuitWriter, cli::packages::UserRepo, error::Result,
-    inputs::JsonInputs, lexer::Token, name_resolution::NAST, parser::AST,
-    type_checker::TypeChecker, witness::CompiledCircuit,
+    backends::Backend,
+    circuit_writer::CircuitWriter,
+    cli::packages::{QualifiedModule, UserRepo},
+    error::Result,
+    inputs::JsonInputs,
+    lexer::Token,
+    name_resolution::NAST,
+    parser::{types::RootKind, AST},
+    stdlib::{check_std_module, get_std_module, STD_MODULE},
+    type_checker::TypeChecker,
+    witness::CompiledCircuit,
 };
 
 /// Contains the association between a counter and the corresponding filename and source code.
@@ -74,7 +82,7 @@ impl<T> IntoMiette<T> for Result<T> {
 
 pub fn typecheck_next_file<B: Backend>(
     typechecker: &mut TypeChecker<B>,
-    this_module: Option<UserRepo>,
+    this_module: Option<QualifiedModule>,
     sources: &mut Sources,
     filename: String,
     code: String,
@@ -87,7 +95,7 @@ pub fn typecheck_next_file<B: Backend>(
 /// This should not be used directly. Check [get_tast] instead.
 pub fn typecheck_next_file_inner<B: Backend>(
     typechecker: &mut TypeChecker<B>,
-    this_module: Option<UserRepo>,
+    this_module: Option<QualifiedModule>,
     sources: &mut Sources,
     filename: String,
     code: String,
@@ -98,14 +106,50 @@ pub fn typecheck_next_file_inner<B: Backend>(
     // parsing to name resolution
     let (nast, new_node_id) = get_nast(this_module, sources, filename, code, node_id)?;
 
+    // the parts of the standard library written in noname that this file uses
+    let new_node_id = typecheck_std_modules(typechecker, sources, &nast, new_node_id)?;
+
     // type checker
     typechecker.analyze(nast, is_lib)?;
 
     Ok(new_node_id)
 }
 
+/// Type checks the modules of the standard library written in noname
+/// that are imported by a file (via `use std::module`), if they haven't been type checked yet.
+pub fn typecheck_std_modules<B: Backend>(
+    typechecker: &mut TypeChecker<B>,
+    sources: &mut Sources,
+    nast: &NAST<B>,
+    mut node_id: usize,
+) -> Result<usize> {
+    for root in &nast.ast.0 {
+        let use_path = match &root.kind {
+            RootKind::Use(use_path) if use_path.module.value == STD_MODULE => use_path,
+            _ => continue,
+        };
+
+        let name = &use_path.submodule.value;
+        if let Some(code) = get_std_module(name) {
+            check_std_module::<B>(name, use_path.submodule.span)?;
+            if typechecker.add_std_module(name) {
+                node_id = typecheck_next_file_inner(
+                    typechecker,
+                    Some(UserRepo::new(&format!("{STD_MODULE}/{name}")).into()),
+                    sources,
+                    format!("{STD_MODULE}/{name}.no"),
+                    code.to_string(),
+                    node_id,
+                )?;
+            }
+        }
+    }
+
+    Ok(node_id)
+}
+
 pub fn get_nast<B: Backend>(
-    this_module: Option<UserRepo>,
+    this_module: Option<QualifiedModule>,
     sources: &mut Sources,
     filename: String,
     code: String,
@@ -11,7 +11,7 @@ use crate::{
     var::{ConstOrCell, Var},
 };
 
-use super::field::sub;
+use super::field::{add, mul, sub};
 
 pub fn is_valid<F: Field>(f: F) -> bool {
     f.is_one() || f.is_zero()
@@ -105,3 +105,19 @@ pub fn or<B: Backend>(
     let both_false = and(compiler, &not_lhs[0], &not_rhs[0], span);
     not(compiler, &both_false[0], span)
 }
+
+/// Returns `lhs xor rhs`, computed as `lhs + rhs - 2 * lhs * rhs`.
+pub fn xor<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    lhs: &ConstOrCell<B::Field, B::Var>,
+    rhs: &ConstOrCell<B::Field, B::Var>,
+    span: Span,
+) -> Var<B::Field, B::Var> {
+    let both_true = and(compiler, lhs, rhs, span);
+    let sum = add(compiler, lhs, rhs, span);
+
+    let two = ConstOrCell::Const(B::Field::from(2u64));
+    let twice_both_true = mul(compiler, &both_true[0], &two, span);
+
+    sub(compiler, &sum[0], &twice_both_true[0], span)
+}
@@ -0,0 +1,141 @@
+//! Elliptic curve operations on the curve `y^2 = x^3 + 5`, outside of the circuit.
+//!
+//! Over the base field of Pallas (the circuit field of kimchi) this is the Pallas curve.
+//! These are used to compute the witness of the elliptic curve gates of kimchi,
+//! and to fold operations on constant points (see [crate::backends::kimchi::builtin]).
+//!
+//! Points are given in affine coordinates `[x, y]`, so the point at infinity can't be represented:
+//! an operation whose result would be the point at infinity fails.
+
+use std::sync::Arc;
+
+use ark_ff::Field;
+
+use crate::{
+    backends::Backend,
+    circuit_writer::CircuitWriter,
+    constants::Span,
+    error::Result,
+    var::{ConstOrCell, Value},
+    witness::WitnessEnv,
+};
+
+/// A point in affine coordinates.
+pub type Point<B> = [ConstOrCell<<B as Backend>::Field, <B as Backend>::Var>; 2];
+
+/// Returns the slope of the line through two points, or of the tangent if they are equal,
+/// or `None` if the sum of the points is the point at infinity.
+pub fn slope<F: Field>(lhs: [F; 2], rhs: [F; 2]) -> Option<F> {
+    let [x1, y1] = lhs;
+    let [x2, y2] = rhs;
+
+    if x1 != x2 {
+        return Some((y2 - y1) / (x2 - x1));
+    }
+
+    if y1 != y2 || y1.is_zero() {
+        return None;
+    }
+
+    let x1_squared = x1.square();
+    Some((x1_squared.double() + x1_squared) / y1.double())
+}
+
+/// Adds two points outside of the circuit,
+/// or returns `None` if the result is the point at infinity.
+pub fn add_values<F: Field>(lhs: [F; 2], rhs: [F; 2]) -> Option<[F; 2]> {
+    let s = slope(lhs, rhs)?;
+    let x3 = s.square() - lhs[0] - rhs[0];
+    let y3 = s * (lhs[0] - x3) - lhs[1];
+    Some([x3, y3])
+}
+
+/// Multiplies a point by a scalar outside of the circuit, given the bits of the scalar from the least significant bit,
+/// or returns `None` if the result is the point at infinity.
+pub fn scale_values<F: Field>(point: [F; 2], bits: &[bool]) -> Option<[F; 2]> {
+    // `None` is the point at infinity
+    let mut acc = None;
+    for bit in bits.iter().rev() {
+        acc = acc.and_then(|acc| add_values(acc, acc));
+        if *bit {
+            acc = match acc {
+                Some(acc) => add_values(acc, point),
+                None => Some(point),
+            };
+        }
+    }
+    acc
+}
+
+/// Returns whether a point is on the curve `y^2 = x^3 + 5`.
+pub fn is_on_curve<F: Field>(point: [F; 2]) -> bool {
+    let [x, y] = point;
+    y.square() == x.square() * x + F::from(5u64)
+}
+
+/// Creates a variable whose value is computed from the values of `inputs` during witness generation.
+/// The variable is not constrained.
+pub fn hint<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    inputs: &[ConstOrCell<B::Field, B::Var>],
+    compute: impl Fn(&[B::Field]) -> Result<B::Field> + 'static,
+    span: Span,
+) -> B::Var {
+    let inputs = inputs.to_vec();
+    compiler.backend.new_internal_var(
+        Value::Hint(Arc::new(
+            move |backend: &B, env: &mut WitnessEnv<B::Field>| {
+                let values = inputs
+                    .iter()
+                    .map(|input| match input {
+                        ConstOrCell::Const(cst) => Ok(*cst),
+                        ConstOrCell::Cell(var) => backend.compute_var(env, var),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                compute(&values)
+            },
+        )),
+        span,
+    )
+}
+
+/// Returns the coordinates of a point, if they are both constants.
+pub fn constant<B: Backend>(point: &Point<B>) -> Option<[B::Field; 2]> {
+    match point {
+        [ConstOrCell::Const(x), ConstOrCell::Const(y)] => Some([*x, *y]),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_ff::One;
+
+    use super::*;
+
+    use crate::backends::kimchi::VestaField;
+
+    #[test]
+    fn test_add_values() {
+        // the generator of Pallas
+        let generator = [-VestaField::one(), VestaField::from(2u64)];
+        assert!(is_on_curve(generator));
+        assert!(!is_on_curve([generator[0], generator[1].double()]));
+
+        let doubled = add_values(generator, generator).unwrap();
+        let tripled = add_values(doubled, generator).unwrap();
+        assert!(is_on_curve(doubled));
+        assert!(is_on_curve(tripled));
+        assert_eq!(add_values(generator, doubled), Some(tripled));
+        assert_eq!(scale_values(generator, &[true, true]), Some(tripled));
+        assert_eq!(
+            scale_values(generator, &[false, true, false]),
+            Some(doubled)
+        );
+        assert_eq!(scale_values(generator, &[false]), None);
+
+        // adding opposite points gives the point at infinity
+        let neg_generator = [generator[0], -generator[1]];
+        assert_eq!(add_values(generator, neg_generator), None);
+    }
+}
@@ -2,12 +2,14 @@ use crate::{
     backends::Backend,
     circuit_writer::CircuitWriter,
     constants::Span,
+    error::{ErrorKind, Result},
     var::{ConstOrCell, Value, Var},
 };
 
 use super::boolean;
 
-use ark_ff::{One, Zero};
+use ark_ff::{Field, One, Zero};
+use num_bigint::BigUint;
 
 use std::ops::Neg;
 
@@ -99,6 +101,48 @@ pub fn mul<B: Backend>(
     }
 }
 
+/// Divides two field elements
+pub fn div<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    lhs: &ConstOrCell<B::Field, B::Var>,
+    rhs: &ConstOrCell<B::Field, B::Var>,
+    span: Span,
+) -> Result<Var<B::Field, B::Var>> {
+    match (lhs, rhs) {
+        // dividing by a constant is multiplying by its inverse
+        (_, ConstOrCell::Const(cst)) => {
+            let inv = cst
+                .inverse()
+                .ok_or_else(|| compiler.error(ErrorKind::DivisionByZero, span))?;
+            Ok(mul(compiler, lhs, &ConstOrCell::Const(inv), span))
+        }
+
+        // dividing by a var
+        (_, ConstOrCell::Cell(cvar)) => {
+            // we need to constrain:
+            //
+            // 1. `rhs * rhs_inv = 1`
+            // 2. `res = lhs * rhs_inv`
+            //
+            // note that (1) can't be satisfied if rhs is zero,
+            // which is what we want
+            let one = B::Field::one();
+
+            // rhs_inv = 1 / rhs
+            let rhs_inv = compiler
+                .backend
+                .new_internal_var(Value::Inverse(cvar.clone()), span);
+
+            // 1. rhs * rhs_inv = 1
+            let rhs_mul_inv = compiler.backend.mul(cvar, &rhs_inv, span);
+            compiler.backend.assert_eq_const(&rhs_mul_inv, one, span);
+
+            // 2. res = lhs * rhs_inv
+            Ok(mul(compiler, lhs, &ConstOrCell::Cell(rhs_inv), span))
+        }
+    }
+}
+
 /// This takes variables that can be anything, and returns a boolean
 // TODO: so perhaps it's not really relevant in this file?
 pub fn equal<B: Backend>(
@@ -345,3 +389,110 @@ pub fn if_else_inner<B: Backend>(
     let temp = mul(compiler, &one_minus_cond[0], else_, span);
     add(compiler, &cond_then[0], &temp[0], span)
 }
+
+/// Decomposes a field element into `num_bits` booleans, from the least significant bit.
+/// The decomposition fails if the field element doesn't fit in `num_bits` bits.
+pub fn to_bits<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    var: &ConstOrCell<B::Field, B::Var>,
+    num_bits: usize,
+    span: Span,
+) -> Result<Vec<ConstOrCell<B::Field, B::Var>>> {
+    // the decomposition must be unique, so it can't wrap around the field
+    let max: BigUint = B::Field::one().neg().into();
+    if num_bits >= max.bits() as usize {
+        return Err(compiler.error(ErrorKind::InvalidBitLength(num_bits), span));
+    }
+
+    match var {
+        ConstOrCell::Const(cst) => {
+            let value: BigUint = (*cst).into();
+            if value.bits() > num_bits as u64 {
+                return Err(compiler.error(
+                    ErrorKind::BitDecompositionOverflow(value.to_string(), num_bits),
+                    span,
+                ));
+            }
+
+            let bits = (0..num_bits)
+                .map(|i| {
+                    let bit = if value.bit(i as u64) {
+                        B::Field::one()
+                    } else {
+                        B::Field::zero()
+                    };
+                    ConstOrCell::Const(bit)
+                })
+                .collect();
+
+            Ok(bits)
+        }
+        ConstOrCell::Cell(cvar) => {
+            let bits = compiler.backend.to_bits(cvar, num_bits, span);
+            Ok(bits.into_iter().map(ConstOrCell::Cell).collect())
+        }
+    }
+}
+
+/// Recomposes a field element from booleans, given from the least significant bit.
+pub fn from_bits<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    bits: &[ConstOrCell<B::Field, B::Var>],
+    span: Span,
+) -> Var<B::Field, B::Var> {
+    let mut res = Var::new_constant(B::Field::zero(), span);
+    let mut power = B::Field::one();
+
+    for bit in bits {
+        // res += bit * 2^i
+        let term = mul(compiler, bit, &ConstOrCell::Const(power), span);
+        res = add(compiler, &res[0], &term[0], span);
+        power.double_in_place();
+    }
+
+    res
+}
+
+/// Returns the element at index `idx` of an array made of elements of `elem_len` cells,
+/// where `idx` is only known at proving time.
+///
+/// Each element is selected via a one-hot vector: `sel_i = (idx == i)`,
+/// and the result is `sum(sel_i * array[i])`.
+/// Constraining `sum(sel_i) = 1` makes an out-of-range index unsatisfiable.
+pub fn multiplex<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    array: &Var<B::Field, B::Var>,
+    elem_len: usize,
+    idx: &ConstOrCell<B::Field, B::Var>,
+    span: Span,
+) -> Result<Var<B::Field, B::Var>> {
+    let zero = B::Field::zero();
+    let one = B::Field::one();
+
+    let mut res = vec![ConstOrCell::Const(zero); elem_len];
+    let mut sum_sel = ConstOrCell::Const(zero);
+
+    for (i, elem) in array.cvars.chunks(elem_len).enumerate() {
+        // sel_i = (idx - i == 0)
+        let i = ConstOrCell::Const(B::Field::from(i as u64));
+        let diff = sub(compiler, idx, &i, span);
+        let sel = is_zero_cell(compiler, &diff[0], span);
+        sum_sel = add(compiler, &sum_sel, &sel[0], span)[0].clone();
+
+        // res += sel_i * array[i]
+        for (res_cell, elem_cell) in res.iter_mut().zip(elem) {
+            let term = mul(compiler, &sel[0], elem_cell, span);
+            *res_cell = add(compiler, res_cell, &term[0], span)[0].clone();
+        }
+    }
+
+    // exactly one element must be selected
+    match sum_sel {
+        ConstOrCell::Const(cst) if cst.is_one() => (),
+        // an empty array, or a constant index (which should not use this function) out of range
+        ConstOrCell::Const(_) => return Err(compiler.error(ErrorKind::AssertionFailed, span)),
+        ConstOrCell::Cell(cvar) => compiler.backend.assert_eq_const(&cvar, one, span),
+    }
+
+    Ok(Var::new(res, span))
+}
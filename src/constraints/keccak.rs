@@ -0,0 +1,294 @@
+//! Keccak-256 (the hash function used by Ethereum, which pads messages differently than SHA3-256),
+//! on bytes that are decomposed in bits.
+//!
+//! A 64-bit lane is a vector of booleans, from the least significant bit,
+//! so that rotations are free.
+//! The state is made of 25 lanes, and the lane at position `(x, y)` is at index `x + 5 * y`.
+//!
+//! This is the default gadget of [Backend::keccak256]: kimchi has its own gadget,
+//! which works on packed lanes with its lookup-based XOR and rotation gates.
+
+use ark_ff::{One, Zero};
+
+use crate::{
+    backends::Backend,
+    circuit_writer::CircuitWriter,
+    constants::Span,
+    var::{ConstOrCell, Value},
+};
+
+use super::boolean;
+
+/// A 64-bit lane, as booleans from the least significant bit.
+type Lane<B> = Vec<ConstOrCell<<B as Backend>::Field, <B as Backend>::Var>>;
+
+/// The number of bytes absorbed per permutation.
+pub(crate) const RATE: usize = 136;
+
+/// The constants of the ι step.
+pub(crate) const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// The rotations of the ρ step, indexed by `[x][y]`.
+pub(crate) const ROTATIONS: [[u32; 5]; 5] = [
+    [0, 36, 3, 41, 18],
+    [1, 44, 10, 45, 2],
+    [62, 6, 43, 15, 61],
+    [28, 55, 25, 21, 56],
+    [27, 20, 39, 8, 14],
+];
+
+/// Returns the padding of a message of `len` bytes:
+/// a 1 bit, zeros, and a 1 bit at the end of the last block.
+pub(crate) fn padding(len: usize) -> Vec<u8> {
+    let mut padding = vec![0; RATE - len % RATE];
+    padding[0] |= 0x01;
+    *padding.last_mut().expect("the padding is never empty") |= 0x80;
+    padding
+}
+
+/// Hashes bytes, given as bits from the least significant bit of each byte,
+/// and returns the 32 bytes of the digest in the same form.
+pub fn hash<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    bytes: &[Vec<ConstOrCell<B::Field, B::Var>>],
+    span: Span,
+) -> Vec<Vec<ConstOrCell<B::Field, B::Var>>> {
+    let mut message = bytes.to_vec();
+    message.extend(
+        padding(bytes.len())
+            .into_iter()
+            .map(|byte| constant_bits::<B>(byte as u64, 8)),
+    );
+
+    let zero = ConstOrCell::Const(B::Field::zero());
+    let mut state: Vec<Lane<B>> = vec![vec![zero; 64]; 25];
+
+    for block in message.chunks(RATE) {
+        // the bytes of a lane are little-endian
+        for (lane, bytes) in state.iter_mut().zip(block.chunks(8)) {
+            let input: Lane<B> = bytes.iter().flatten().cloned().collect();
+            *lane = xor_lanes(compiler, lane, &input, span);
+        }
+
+        permute(compiler, &mut state, span);
+    }
+
+    // the digest is made of the first 32 bytes of the state
+    state[..4]
+        .iter()
+        .flat_map(|lane| lane.chunks(8).map(<[_]>::to_vec).collect::<Vec<_>>())
+        .collect()
+}
+
+/// The Keccak-f[1600] permutation.
+fn permute<B: Backend>(compiler: &mut CircuitWriter<B>, state: &mut [Lane<B>], span: Span) {
+    for round_constant in ROUND_CONSTANTS {
+        // θ
+        let mut columns = vec![];
+        for x in 0..5 {
+            let mut column = state[x].clone();
+            for y in 1..5 {
+                column = xor_lanes(compiler, &column, &state[x + 5 * y], span);
+            }
+            columns.push(column);
+        }
+
+        for x in 0..5 {
+            let rotated = rotl::<B>(&columns[(x + 1) % 5], 1);
+            let diff = xor_lanes(compiler, &columns[(x + 4) % 5], &rotated, span);
+            for y in 0..5 {
+                let lane = xor_lanes(compiler, &state[x + 5 * y], &diff, span);
+                state[x + 5 * y] = lane
+                    .iter()
+                    .map(|bit| materialize(compiler, bit, span))
+                    .collect();
+            }
+        }
+
+        // ρ and π
+        let mut permuted = vec![vec![]; 25];
+        for x in 0..5 {
+            for y in 0..5 {
+                permuted[y + 5 * ((2 * x + 3 * y) % 5)] =
+                    rotl::<B>(&state[x + 5 * y], ROTATIONS[x][y]);
+            }
+        }
+
+        // χ
+        for x in 0..5 {
+            for y in 0..5 {
+                let lane = &permuted[x + 5 * y];
+                let next = &permuted[(x + 1) % 5 + 5 * y];
+                let next_next = &permuted[(x + 2) % 5 + 5 * y];
+
+                state[x + 5 * y] = lane
+                    .iter()
+                    .zip(next)
+                    .zip(next_next)
+                    .map(|((bit, next), next_next)| {
+                        let not_next = boolean::not(compiler, next, span);
+                        let res = boolean::and(compiler, &not_next[0], next_next, span);
+                        boolean::xor(compiler, bit, &res[0], span)[0].clone()
+                    })
+                    .collect();
+            }
+        }
+
+        // ι
+        let round_constant = constant_bits::<B>(round_constant, 64);
+        state[0] = xor_lanes(compiler, &state[0], &round_constant, span);
+    }
+}
+
+/// Returns the bits of a constant, from the least significant bit.
+fn constant_bits<B: Backend>(value: u64, num_bits: usize) -> Vec<ConstOrCell<B::Field, B::Var>> {
+    (0..num_bits)
+        .map(|ii| {
+            let bit = if (value >> ii) & 1 == 1 {
+                B::Field::one()
+            } else {
+                B::Field::zero()
+            };
+            ConstOrCell::Const(bit)
+        })
+        .collect()
+}
+
+fn rotl<B: Backend>(lane: &Lane<B>, amount: u32) -> Lane<B> {
+    let mut res = lane.clone();
+    res.rotate_right(amount as usize);
+    res
+}
+
+fn xor_lanes<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    lhs: &Lane<B>,
+    rhs: &Lane<B>,
+    span: Span,
+) -> Lane<B> {
+    lhs.iter()
+        .zip(rhs)
+        .map(|(lhs, rhs)| boolean::xor(compiler, lhs, rhs, span)[0].clone())
+        .collect()
+}
+
+/// Returns a new variable equal to the given bit.
+/// On R1CS, the result of a xor is a linear combination of its operands,
+/// so this prevents linear combinations from growing at every round.
+fn materialize<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    bit: &ConstOrCell<B::Field, B::Var>,
+    span: Span,
+) -> ConstOrCell<B::Field, B::Var> {
+    match bit {
+        ConstOrCell::Const(_) => bit.clone(),
+        ConstOrCell::Cell(var) => {
+            // unlike the value of a scaled variable, the value of a linear combination is cached,
+            // which matters as each bit is computed from the bits of the previous round
+            let value =
+                Value::LinearCombination(vec![(B::Field::one(), var.clone())], B::Field::zero());
+            let res = compiler.backend.new_internal_var(value, span);
+            compiler.backend.assert_eq_var(&res, var, span);
+            ConstOrCell::Cell(res)
+        }
+    }
+}
+
+/// Hashes bytes with Keccak-256, outside of the circuit.
+pub fn keccak256(bytes: &[u8]) -> [u8; 32] {
+    let mut message = bytes.to_vec();
+    message.extend(padding(bytes.len()));
+
+    let mut state = [0u64; 25];
+    for block in message.chunks(RATE) {
+        for (lane, bytes) in state.iter_mut().zip(block.chunks(8)) {
+            *lane ^= u64::from_le_bytes(bytes.try_into().expect("a lane is 8 bytes"));
+        }
+
+        permute_native(&mut state);
+    }
+
+    let mut digest = [0; 32];
+    for (bytes, lane) in digest.chunks_mut(8).zip(state) {
+        bytes.copy_from_slice(&lane.to_le_bytes());
+    }
+    digest
+}
+
+fn permute_native(state: &mut [u64; 25]) {
+    for round_constant in ROUND_CONSTANTS {
+        // θ
+        let mut columns = [0u64; 5];
+        for (x, column) in columns.iter_mut().enumerate() {
+            *column = (0..5).fold(0, |acc, y| acc ^ state[x + 5 * y]);
+        }
+        for x in 0..5 {
+            let diff = columns[(x + 4) % 5] ^ columns[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                state[x + 5 * y] ^= diff;
+            }
+        }
+
+        // ρ and π
+        let mut permuted = [0u64; 25];
+        for x in 0..5 {
+            for y in 0..5 {
+                permuted[y + 5 * ((2 * x + 3 * y) % 5)] =
+                    state[x + 5 * y].rotate_left(ROTATIONS[x][y]);
+            }
+        }
+
+        // χ
+        for x in 0..5 {
+            for y in 0..5 {
+                state[x + 5 * y] = permuted[x + 5 * y]
+                    ^ (!permuted[(x + 1) % 5 + 5 * y] & permuted[(x + 2) % 5 + 5 * y]);
+            }
+        }
+
+        // ι
+        state[0] ^= round_constant;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sha3::{Digest, Keccak256};
+
+    use super::*;
+
+    #[test]
+    fn test_keccak256() {
+        // this covers paddings of a single byte (135 bytes) and of a whole block (136 bytes)
+        for len in 0..=2 * RATE + 1 {
+            let preimage: Vec<u8> = (0..len).map(|ii| (ii * 7 + 3) as u8).collect();
+            let expected: [u8; 32] = Keccak256::digest(&preimage).into();
+            assert_eq!(keccak256(&preimage), expected, "length {len}");
+        }
+    }
+}
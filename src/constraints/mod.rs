@@ -1,2 +1,6 @@
 pub mod boolean;
+pub mod ec;
 pub mod field;
+pub mod keccak;
+pub mod sha256;
+pub mod uint;
@@ -0,0 +1,291 @@
+//! SHA-256, on bytes that are decomposed in bits.
+//!
+//! A 32-bit word is a vector of booleans, from the least significant bit,
+//! so that rotations and shifts are free.
+//! Additions modulo `2^32` are done on the field elements recomposed from the words,
+//! and the result is decomposed again (keeping only its 32 least significant bits).
+//! The bitwise functions (`xor`, `ch` and `maj`) cost one or two multiplications per bit.
+//!
+//! This is the default gadget of [Backend::sha256]: kimchi has its own gadget,
+//! which works on packed words with its lookup-based XOR gates.
+
+use ark_ff::{One, Zero};
+
+use crate::{
+    backends::Backend,
+    circuit_writer::CircuitWriter,
+    constants::Span,
+    error::Result,
+    var::{ConstOrCell, Var},
+};
+
+use super::{boolean, field};
+
+/// A 32-bit word, as booleans from the least significant bit.
+type Word<B> = Vec<ConstOrCell<<B as Backend>::Field, <B as Backend>::Var>>;
+
+/// The initial hash value.
+pub(crate) const H0: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// The round constants.
+pub(crate) const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// Hashes bytes, given as bits from the least significant bit of each byte,
+/// and returns the 32 bytes of the digest in the same form.
+pub fn hash<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    bytes: &[Vec<ConstOrCell<B::Field, B::Var>>],
+    span: Span,
+) -> Result<Vec<Vec<ConstOrCell<B::Field, B::Var>>>> {
+    let mut message = bytes.to_vec();
+    for byte in padding(bytes.len()) {
+        message.push(constant_bits::<B>(byte as u32, 8));
+    }
+
+    let mut state: Vec<Word<B>> = H0.iter().map(|hh| constant_bits::<B>(*hh, 32)).collect();
+
+    for block in message.chunks(64) {
+        state = compress(compiler, &state, block, span)?;
+    }
+
+    // the words of the state are big-endian
+    let digest = state
+        .iter()
+        .flat_map(|word| word.chunks(8).rev().map(<[_]>::to_vec).collect::<Vec<_>>())
+        .collect();
+
+    Ok(digest)
+}
+
+/// Returns the bytes appended to a message of `len` bytes, so that its length is a multiple of 64:
+/// a 1 bit, zeros, and the length of the message in bits (as a big-endian u64).
+pub(crate) fn padding(len: usize) -> Vec<u8> {
+    let mut padding = vec![0x80];
+    while (len + padding.len()) % 64 != 56 {
+        padding.push(0);
+    }
+    let len_in_bits = 8 * len as u64;
+    padding.extend(len_in_bits.to_be_bytes());
+    padding
+}
+
+/// Processes a block of 64 bytes.
+fn compress<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    state: &[Word<B>],
+    block: &[Vec<ConstOrCell<B::Field, B::Var>>],
+    span: Span,
+) -> Result<Vec<Word<B>>> {
+    // message schedule (the bytes of a word are big-endian)
+    let mut schedule: Vec<Word<B>> = block
+        .chunks(4)
+        .map(|bytes| bytes.iter().rev().flatten().cloned().collect())
+        .collect();
+
+    for tt in 16..64 {
+        let s0 = xor3(
+            compiler,
+            &rotr::<B>(&schedule[tt - 15], 7),
+            &rotr::<B>(&schedule[tt - 15], 18),
+            &shr::<B>(&schedule[tt - 15], 3),
+            span,
+        );
+        let s1 = xor3(
+            compiler,
+            &rotr::<B>(&schedule[tt - 2], 17),
+            &rotr::<B>(&schedule[tt - 2], 19),
+            &shr::<B>(&schedule[tt - 2], 10),
+            span,
+        );
+
+        let sum = sum_words(
+            compiler,
+            &[&s1, &schedule[tt - 7], &s0, &schedule[tt - 16]],
+            span,
+        );
+        let word = truncate(compiler, &sum, 4, span)?;
+        schedule.push(word);
+    }
+
+    // rounds
+    let [mut aa, mut bb, mut cc, mut dd, mut ee, mut ff, mut gg, mut hh] =
+        <[Word<B>; 8]>::try_from(state.to_vec())
+            .unwrap_or_else(|_| unreachable!("the state has 8 words"));
+
+    for (round_constant, word) in K.iter().zip(&schedule) {
+        let sigma1 = xor3(
+            compiler,
+            &rotr::<B>(&ee, 6),
+            &rotr::<B>(&ee, 11),
+            &rotr::<B>(&ee, 25),
+            span,
+        );
+        let choice = ch(compiler, &ee, &ff, &gg, span);
+        let round_constant = constant_bits::<B>(*round_constant, 32);
+        let temp1 = sum_words(
+            compiler,
+            &[&hh, &sigma1, &choice, &round_constant, word],
+            span,
+        );
+
+        let sigma0 = xor3(
+            compiler,
+            &rotr::<B>(&aa, 2),
+            &rotr::<B>(&aa, 13),
+            &rotr::<B>(&aa, 22),
+            span,
+        );
+        let majority = maj(compiler, &aa, &bb, &cc, span);
+        let temp2 = sum_words(compiler, &[&sigma0, &majority], span);
+
+        hh = gg;
+        gg = ff;
+        ff = ee;
+
+        // d + temp1 is the sum of 6 words
+        let packed_dd = sum_words(compiler, &[&dd], span);
+        let new_ee = field::add(compiler, &packed_dd, &temp1, span);
+        ee = truncate(compiler, &new_ee[0], 6, span)?;
+
+        dd = cc;
+        cc = bb;
+        bb = aa;
+
+        // temp1 + temp2 is the sum of 7 words
+        let new_aa = field::add(compiler, &temp1, &temp2, span);
+        aa = truncate(compiler, &new_aa[0], 7, span)?;
+    }
+
+    let mut new_state = vec![];
+    for (word, var) in state.iter().zip([aa, bb, cc, dd, ee, ff, gg, hh]) {
+        let sum = sum_words(compiler, &[word, &var], span);
+        new_state.push(truncate(compiler, &sum, 2, span)?);
+    }
+
+    Ok(new_state)
+}
+
+/// Returns the bits of a constant, from the least significant bit.
+fn constant_bits<B: Backend>(value: u32, num_bits: usize) -> Vec<ConstOrCell<B::Field, B::Var>> {
+    (0..num_bits)
+        .map(|ii| {
+            let bit = if (value >> ii) & 1 == 1 {
+                B::Field::one()
+            } else {
+                B::Field::zero()
+            };
+            ConstOrCell::Const(bit)
+        })
+        .collect()
+}
+
+fn rotr<B: Backend>(word: &Word<B>, amount: usize) -> Word<B> {
+    let mut res = word.clone();
+    res.rotate_left(amount);
+    res
+}
+
+fn shr<B: Backend>(word: &Word<B>, amount: usize) -> Word<B> {
+    let zero = ConstOrCell::Const(B::Field::zero());
+    word[amount..]
+        .iter()
+        .cloned()
+        .chain(std::iter::repeat(zero).take(amount))
+        .collect()
+}
+
+fn xor3<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    aa: &Word<B>,
+    bb: &Word<B>,
+    cc: &Word<B>,
+    span: Span,
+) -> Word<B> {
+    aa.iter()
+        .zip(bb)
+        .zip(cc)
+        .map(|((aa, bb), cc)| {
+            let res = boolean::xor(compiler, aa, bb, span);
+            boolean::xor(compiler, &res[0], cc, span)[0].clone()
+        })
+        .collect()
+}
+
+/// `ch(e, f, g) = (e and f) xor (not e and g)`, computed as `e * (f - g) + g`.
+fn ch<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    ee: &Word<B>,
+    ff: &Word<B>,
+    gg: &Word<B>,
+    span: Span,
+) -> Word<B> {
+    ee.iter()
+        .zip(ff)
+        .zip(gg)
+        .map(|((ee, ff), gg)| {
+            let diff = field::sub(compiler, ff, gg, span);
+            let res = field::mul(compiler, ee, &diff[0], span);
+            field::add(compiler, &res[0], gg, span)[0].clone()
+        })
+        .collect()
+}
+
+/// `maj(a, b, c) = (a and b) xor (a and c) xor (b and c)`, computed as `a * b + c * (a xor b)`
+/// (if `a` and `b` are equal the result is `a`, otherwise it is `c`).
+fn maj<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    aa: &Word<B>,
+    bb: &Word<B>,
+    cc: &Word<B>,
+    span: Span,
+) -> Word<B> {
+    aa.iter()
+        .zip(bb)
+        .zip(cc)
+        .map(|((aa, bb), cc)| {
+            let both = boolean::and(compiler, aa, bb, span);
+            let different = boolean::xor(compiler, aa, bb, span);
+            let res = field::mul(compiler, cc, &different[0], span);
+            field::add(compiler, &both[0], &res[0], span)[0].clone()
+        })
+        .collect()
+}
+
+/// Recomposes words into field elements, and returns their sum.
+fn sum_words<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    words: &[&Word<B>],
+    span: Span,
+) -> ConstOrCell<B::Field, B::Var> {
+    let mut sum: Var<B::Field, B::Var> = Var::new_constant(B::Field::zero(), span);
+    for word in words {
+        let packed = field::from_bits(compiler, word, span);
+        sum = field::add(compiler, &sum[0], &packed[0], span);
+    }
+    sum[0].clone()
+}
+
+/// Returns the 32 least significant bits of a sum of `num_words` words.
+fn truncate<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    sum: &ConstOrCell<B::Field, B::Var>,
+    num_words: usize,
+    span: Span,
+) -> Result<Word<B>> {
+    // the sum fits in `32 + log2(num_words)` bits
+    let carry_bits = usize::BITS - (num_words - 1).leading_zeros();
+    let mut bits = field::to_bits(compiler, sum, 32 + carry_bits as usize, span)?;
+    bits.truncate(32);
+    Ok(bits)
+}
@@ -0,0 +1,337 @@
+//! Unsigned integers (`u8`, `u16`, `u32`, `u64`).
+//!
+//! They are field elements that are range-checked to fit in their number of bits.
+//! Arithmetic is overflow-checked: the result of every operation is range-checked,
+//! so that a result that over- or under-flows makes the circuit unsatisfiable.
+//! (As operands fit in 64 bits, results can't wrap around the field.)
+
+use ark_ff::{Field, One, Zero};
+use num_bigint::BigUint;
+
+use crate::{
+    backends::Backend,
+    circuit_writer::CircuitWriter,
+    constants::Span,
+    error::{ErrorKind, Result},
+    parser::types::TyKind,
+    var::{ConstOrCell, Value, Var},
+};
+
+use super::{boolean, field};
+
+/// Returns the number of bits of the given unsigned integer type.
+fn num_bits(typ: &TyKind) -> usize {
+    typ.uint_bits()
+        .expect("compiler bug: expected an unsigned integer type")
+}
+
+/// Constrains a value to fit in the given unsigned integer type.
+/// Constants are checked at compile time.
+pub fn check<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    xx: &ConstOrCell<B::Field, B::Var>,
+    typ: &TyKind,
+    span: Span,
+) -> Result<()> {
+    let num_bits = num_bits(typ);
+
+    match xx {
+        ConstOrCell::Const(ff) => {
+            let value: BigUint = (*ff).into();
+            if value.bits() > num_bits as u64 {
+                return Err(compiler.error(
+                    ErrorKind::UintOverflow(value.to_string(), typ.clone()),
+                    span,
+                ));
+            }
+        }
+        ConstOrCell::Cell(x) => compiler.backend.range_check(x, num_bits, span),
+    };
+
+    Ok(())
+}
+
+/// Adds two unsigned integers
+pub fn add<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    lhs: &ConstOrCell<B::Field, B::Var>,
+    rhs: &ConstOrCell<B::Field, B::Var>,
+    typ: &TyKind,
+    span: Span,
+) -> Result<Var<B::Field, B::Var>> {
+    let res = field::add(compiler, lhs, rhs, span);
+    check(compiler, &res[0], typ, span)?;
+    Ok(res)
+}
+
+/// Subtracts two unsigned integers
+pub fn sub<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    lhs: &ConstOrCell<B::Field, B::Var>,
+    rhs: &ConstOrCell<B::Field, B::Var>,
+    typ: &TyKind,
+    span: Span,
+) -> Result<Var<B::Field, B::Var>> {
+    // an underflow wraps around the field, which fails the range check
+    let res = field::sub(compiler, lhs, rhs, span);
+    check(compiler, &res[0], typ, span)?;
+    Ok(res)
+}
+
+/// Multiplies two unsigned integers
+pub fn mul<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    lhs: &ConstOrCell<B::Field, B::Var>,
+    rhs: &ConstOrCell<B::Field, B::Var>,
+    typ: &TyKind,
+    span: Span,
+) -> Result<Var<B::Field, B::Var>> {
+    let res = field::mul(compiler, lhs, rhs, span);
+    check(compiler, &res[0], typ, span)?;
+    Ok(res)
+}
+
+/// Returns whether `lhs < rhs`, as a boolean.
+/// The type of the operands can only be `None` if they are both constants.
+pub fn less_than<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    lhs: &ConstOrCell<B::Field, B::Var>,
+    rhs: &ConstOrCell<B::Field, B::Var>,
+    typ: Option<&TyKind>,
+    span: Span,
+) -> Result<Var<B::Field, B::Var>> {
+    // two constants are compared at compile time
+    if let (ConstOrCell::Const(lhs), ConstOrCell::Const(rhs)) = (lhs, rhs) {
+        let lhs: BigUint = (*lhs).into();
+        let rhs: BigUint = (*rhs).into();
+        let res = if lhs < rhs {
+            B::Field::one()
+        } else {
+            B::Field::zero()
+        };
+        return Ok(Var::new_constant(res, span));
+    }
+
+    let typ = typ.expect("compiler bug: comparing variables that are not unsigned integers");
+    let num_bits = num_bits(typ);
+
+    // constants are not range-checked yet
+    for operand in [lhs, rhs] {
+        if operand.is_const() {
+            check(compiler, operand, typ, span)?;
+        }
+    }
+
+    // as both operands fit in `num_bits` bits,
+    // `diff = lhs - rhs + 2^num_bits` fits in `num_bits + 1` bits,
+    // and its most significant bit is set iff `lhs >= rhs`
+    let two_to_n = B::Field::from(2u64).pow([num_bits as u64]);
+    let diff = field::sub(compiler, lhs, rhs, span);
+    let diff = field::add(compiler, &diff[0], &ConstOrCell::Const(two_to_n), span);
+    let diff = diff[0].cvar().expect("expected a variable");
+
+    // extract the most significant bit
+    let msb = compiler
+        .backend
+        .new_internal_var(Value::Bits(diff.clone(), num_bits, 1), span);
+    let msb = ConstOrCell::Cell(msb);
+    boolean::check(compiler, &msb, span);
+
+    // and make sure that the rest fits in `num_bits` bits
+    let high = field::mul(compiler, &msb, &ConstOrCell::Const(two_to_n), span);
+    let low = field::sub(compiler, &ConstOrCell::Cell(diff.clone()), &high[0], span);
+    let low = low[0].cvar().expect("expected a variable");
+    compiler.backend.range_check(low, num_bits, span);
+
+    Ok(boolean::not(compiler, &msb, span))
+}
+
+/// Returns whether `lhs <= rhs`, as a boolean.
+pub fn less_than_or_equal<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    lhs: &ConstOrCell<B::Field, B::Var>,
+    rhs: &ConstOrCell<B::Field, B::Var>,
+    typ: Option<&TyKind>,
+    span: Span,
+) -> Result<Var<B::Field, B::Var>> {
+    let greater_than = less_than(compiler, rhs, lhs, typ, span)?;
+    Ok(boolean::not(compiler, &greater_than[0], span))
+}
+
+/// Returns the bitwise `and` of two unsigned integers.
+pub fn and<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    lhs: &ConstOrCell<B::Field, B::Var>,
+    rhs: &ConstOrCell<B::Field, B::Var>,
+    typ: Option<&TyKind>,
+    span: Span,
+) -> Result<Var<B::Field, B::Var>> {
+    bitwise(compiler, lhs, rhs, typ, span, |a, b| a & b, boolean::and)
+}
+
+/// Returns the bitwise `or` of two unsigned integers.
+pub fn or<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    lhs: &ConstOrCell<B::Field, B::Var>,
+    rhs: &ConstOrCell<B::Field, B::Var>,
+    typ: Option<&TyKind>,
+    span: Span,
+) -> Result<Var<B::Field, B::Var>> {
+    bitwise(compiler, lhs, rhs, typ, span, |a, b| a | b, boolean::or)
+}
+
+/// Returns the bitwise `xor` of two unsigned integers.
+pub fn xor<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    lhs: &ConstOrCell<B::Field, B::Var>,
+    rhs: &ConstOrCell<B::Field, B::Var>,
+    typ: Option<&TyKind>,
+    span: Span,
+) -> Result<Var<B::Field, B::Var>> {
+    bitwise(compiler, lhs, rhs, typ, span, |a, b| a ^ b, boolean::xor)
+}
+
+/// Applies a bitwise operation to two unsigned integers:
+/// `on_constants` if they are both constants,
+/// or `on_bits` to each pair of bits of their decompositions otherwise.
+/// The type of the operands can only be `None` if they are both constants.
+fn bitwise<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    lhs: &ConstOrCell<B::Field, B::Var>,
+    rhs: &ConstOrCell<B::Field, B::Var>,
+    typ: Option<&TyKind>,
+    span: Span,
+    on_constants: fn(BigUint, BigUint) -> BigUint,
+    on_bits: fn(
+        &mut CircuitWriter<B>,
+        &ConstOrCell<B::Field, B::Var>,
+        &ConstOrCell<B::Field, B::Var>,
+        Span,
+    ) -> Var<B::Field, B::Var>,
+) -> Result<Var<B::Field, B::Var>> {
+    // two constants are computed at compile time
+    if let (ConstOrCell::Const(lhs), ConstOrCell::Const(rhs)) = (lhs, rhs) {
+        let res = on_constants((*lhs).into(), (*rhs).into());
+        let res = B::Field::try_from(res)
+            .unwrap_or_else(|_| unreachable!("the result is smaller than the operands"));
+        return Ok(Var::new_constant(res, span));
+    }
+
+    let typ =
+        typ.expect("compiler bug: bitwise operation on values that are not unsigned integers");
+    let num_bits = num_bits(typ);
+
+    // constants are not range-checked yet
+    for operand in [lhs, rhs] {
+        if operand.is_const() {
+            check(compiler, operand, typ, span)?;
+        }
+    }
+
+    let lhs_bits = field::to_bits(compiler, lhs, num_bits, span)?;
+    let rhs_bits = field::to_bits(compiler, rhs, num_bits, span)?;
+
+    let res_bits: Vec<_> = lhs_bits
+        .iter()
+        .zip(&rhs_bits)
+        .map(|(lhs, rhs)| on_bits(compiler, lhs, rhs, span)[0].clone())
+        .collect();
+
+    Ok(field::from_bits(compiler, &res_bits, span))
+}
+
+/// Returns the amount of a shift, which must be a constant.
+fn shift_amount<B: Backend>(
+    compiler: &CircuitWriter<B>,
+    amount: &ConstOrCell<B::Field, B::Var>,
+    typ: Option<&TyKind>,
+    span: Span,
+) -> Result<usize> {
+    let amount: BigUint = amount
+        .cst()
+        .ok_or_else(|| compiler.error(ErrorKind::ExpectedConstant, span))?
+        .into();
+
+    // shifting an unsigned integer by its number of bits or more is an error
+    let max = typ.map(num_bits).unwrap_or(usize::MAX);
+    match usize::try_from(&amount) {
+        Ok(amount) if amount < max => Ok(amount),
+        _ => Err(compiler.error(
+            ErrorKind::InvalidShift(amount.to_string(), typ.cloned().unwrap_or(TyKind::BigInt)),
+            span,
+        )),
+    }
+}
+
+/// Shifts an unsigned integer to the left by a constant amount.
+/// Like in Rust, the bits shifted out are discarded.
+/// The type can only be `None` if the operand is a constant.
+pub fn shift_left<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    lhs: &ConstOrCell<B::Field, B::Var>,
+    rhs: &ConstOrCell<B::Field, B::Var>,
+    typ: Option<&TyKind>,
+    span: Span,
+) -> Result<Var<B::Field, B::Var>> {
+    let amount = shift_amount(compiler, rhs, typ, span)?;
+    let two_to_amount = B::Field::from(2u64).pow([amount as u64]);
+
+    match (lhs, typ) {
+        // a constant (that is not an unsigned integer) is simply multiplied
+        (ConstOrCell::Const(cst), None) => Ok(Var::new_constant(*cst * two_to_amount, span)),
+
+        (lhs, Some(typ)) => {
+            let num_bits = num_bits(typ);
+            check(compiler, lhs, typ, span)?;
+
+            // the most significant bits are discarded
+            let bits = field::to_bits(compiler, lhs, num_bits, span)?;
+            let zeros = vec![ConstOrCell::Const(B::Field::zero()); amount];
+            let res_bits: Vec<_> = zeros
+                .into_iter()
+                .chain(bits[..num_bits - amount].iter().cloned())
+                .collect();
+
+            Ok(field::from_bits(compiler, &res_bits, span))
+        }
+
+        (ConstOrCell::Cell(_), None) => {
+            unreachable!("compiler bug: shifting a value that is not an unsigned integer")
+        }
+    }
+}
+
+/// Shifts an unsigned integer to the right by a constant amount.
+/// The type can only be `None` if the operand is a constant.
+pub fn shift_right<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    lhs: &ConstOrCell<B::Field, B::Var>,
+    rhs: &ConstOrCell<B::Field, B::Var>,
+    typ: Option<&TyKind>,
+    span: Span,
+) -> Result<Var<B::Field, B::Var>> {
+    let amount = shift_amount(compiler, rhs, typ, span)?;
+
+    match (lhs, typ) {
+        (ConstOrCell::Const(cst), None) => {
+            let value: BigUint = (*cst).into();
+            let res = B::Field::try_from(value >> amount)
+                .unwrap_or_else(|_| unreachable!("the result is smaller than the operand"));
+            Ok(Var::new_constant(res, span))
+        }
+
+        (lhs, Some(typ)) => {
+            let num_bits = num_bits(typ);
+            check(compiler, lhs, typ, span)?;
+
+            // the least significant bits are discarded
+            let bits = field::to_bits(compiler, lhs, num_bits, span)?;
+
+            Ok(field::from_bits(compiler, &bits[amount..], span))
+        }
+
+        (ConstOrCell::Cell(_), None) => {
+            unreachable!("compiler bug: shifting a value that is not an unsigned integer")
+        }
+    }
+}
@@ -112,6 +112,21 @@ pub enum ErrorKind {
     #[error("invalid end of line")]
     InvalidEndOfLine,
 
+    #[error("a tuple type must contain at least two types (e.g. `(Field, Bool)`)")]
+    InvalidTupleType,
+
+    #[error("expected variable names to destructure a tuple (e.g. `let (aa, mut bb) = ...;`)")]
+    InvalidTupleDestructuring,
+
+    #[error("the enum `{0}` must have at least one variant")]
+    EmptyEnum(String),
+
+    #[error("invalid enum variant `{0}`, variants must start with an uppercase letter")]
+    InvalidEnumVariant(String),
+
+    #[error("invalid pattern, expected `_` or an enum variant (e.g. `Shape::Square(side)`)")]
+    InvalidMatchPattern,
+
     #[error("invalid function signature: {0}")]
     InvalidFunctionSignature(&'static str),
 
@@ -127,8 +142,8 @@ pub enum ErrorKind {
     #[error("the custom type name used: `{0}` is a reserved type name")]
     ReservedType(String),
 
-    #[error("invalid array size, expected [_; x] with x in [0,2^32]")]
-    InvalidArraySize,
+    #[error("range bounds and array sizes must be integers in [0,2^32]")]
+    InvalidConstExpr,
 
     #[error("the value passed could not be converted to a field element")]
     InvalidField(String),
@@ -183,6 +198,12 @@ pub enum ErrorKind {
     #[error("you need to pass the following private argument: `{0}`")]
     MissingPrivateArg(String),
 
+    #[error("you need to pass the expected public output, as the main function returns a value")]
+    MissingPublicOutput,
+
+    #[error("a public output was given, but the main function does not return a value")]
+    UnexpectedPublicOutput,
+
     #[error("cannot convert `{0}` to field element")]
     CannotConvertToField(String),
 
@@ -219,6 +240,12 @@ pub enum ErrorKind {
     #[error("module `{0}` not present in scope (are you sure you imported it?)")]
     UndefinedModule(String),
 
+    #[error("module `self::{0}` not found (expected a file at `{1}`)")]
+    SubmoduleNotFound(String, String),
+
+    #[error("module `self::{0}` imports itself (directly or through other modules)")]
+    CyclicSubmodule(String),
+
     #[error("attribute not recognized: `{0:?}`")]
     InvalidAttribute(AttributeKind),
 
@@ -233,9 +260,15 @@ pub enum ErrorKind {
     )]
     NoOneLetterVariable,
 
-    #[error("array indexes must be constants in circuits")]
+    #[error("expected a constant value (known at compile time)")]
     ExpectedConstant,
 
+    #[error("array indexes must be field elements or unsigned integers, not `{0}`")]
+    InvalidArrayIndex(TyKind),
+
+    #[error("array elements can only be assigned at constant indexes")]
+    DynamicIndexAssignment,
+
     #[error("kimchi setup: {0}")]
     KimchiSetup(#[from] kimchi::error::SetupError),
 
@@ -320,6 +353,108 @@ pub enum ErrorKind {
     #[error("`if` branch and `else` branch must have matching types")]
     IfElseMismatchingBranchesTypes(),
 
+    #[error("{0} can't be used in an `if` statement, as the constraints it creates can't be made conditional")]
+    UnconditionalConstraints(&'static str),
+
+    #[error("early returns are not supported, so `return` can't be used in an `if` statement")]
+    ReturnInIfStatement,
+
+    #[error("functions and methods can't be called in an `if` statement, even if they don't create constraints: they are compiled on their own, so the constraints they could create can't be made conditional. Call them before the `if` statement instead")]
+    CallInIfStatement,
+
     #[error("invalid range, the end value can't be smaller than the start value")]
     InvalidRange,
+
+    #[error("`for` loops can only iterate over ranges or arrays, not over `{0}`")]
+    InvalidIterator(TyKind),
+
+    #[error("there is no element at index {0} in `{1}`")]
+    InvalidTupleIndex(usize, TyKind),
+
+    #[error("cannot destructure `{0}` into {1} variables")]
+    TupleDestructuringMismatch(TyKind, usize),
+
+    #[error("enum `{0}` does not exist (are you sure it is defined?)")]
+    UndefinedEnum(String),
+
+    #[error("enum `{0}` does not have a variant called `{1}`")]
+    UndefinedVariant(String, String),
+
+    #[error("the variant `{0}` holds {1} values, but {2} were given")]
+    MismatchVariantValues(String, usize, usize),
+
+    #[error("`match` can only be used on enums, not on `{0}`")]
+    MatchOnNonEnum(TyKind),
+
+    #[error("expected a variant of `{0}`, but found a variant of the enum `{1}`")]
+    MismatchMatchPattern(TyKind, String),
+
+    #[error("this arm of the `match` can never be reached, as its variants are already covered")]
+    UnreachableMatchArm,
+
+    #[error("the `match` does not cover the variant `{0}`")]
+    NonExhaustiveMatch(String),
+
+    #[error("the arms of the `match` have different types: `{0}` and `{1}`")]
+    MatchArmsTypeMismatch(TyKind, TyKind),
+
+    #[error("enums passed to the main function or returned by a hint can only hold field elements, not `{0}`")]
+    InvalidEnumInput(TyKind),
+
+    #[error("division by zero")]
+    DivisionByZero,
+
+    #[error("this operation is not supported on values of type `{0}` (only `+`, `-`, `*`, comparisons and bitwise operations are)")]
+    UnsupportedUintOperation(TyKind),
+
+    #[error("values of type `{0}` can't be compared, as they are not bounded (only unsigned integers like `u32` can be compared)")]
+    UnboundedComparison(TyKind),
+
+    #[error("bitwise operations can't be used on values of type `{0}`, as they are not bounded (only unsigned integers like `u32` can)")]
+    UnboundedBitwiseOperation(TyKind),
+
+    #[error("can't shift a value of type `{1}` by `{0}` bits")]
+    InvalidShift(String, TyKind),
+
+    #[error("can't decompose a field element in `{0}` bits (it must be less than the bit size of the field)")]
+    InvalidBitLength(usize),
+
+    #[error("the constant `{0}` does not fit in {1} bits")]
+    BitDecompositionOverflow(String, usize),
+
+    #[error("array sizes can't be generic in struct fields or in the main function")]
+    UnexpectedGenericSize,
+
+    #[error("`{0}` is not a constant, a generic array size or a `const` argument of the function")]
+    UndefinedGenericSize(String),
+
+    #[error("the size of the array type `{0}` could not be determined at compile time")]
+    UnresolvedGenericSize(TyKind),
+
+    #[error("the constant `{0}` does not fit in a {1}")]
+    UintOverflow(String, TyKind),
+
+    #[error("only constants whose value is known at type checking can be used as a {0}, as they are not range checked")]
+    UnknownUintConstant(TyKind),
+
+    #[error("the variable `{0}` holds the output of a hint but is never checked (the values computed by hints are not constrained, so they must be checked with `assert` or `assert_eq`)")]
+    UnusedHintOutput(String),
+
+    #[error("the output of a hint must be stored in a variable with `let`, so that it can be checked with `assert` or `assert_eq`")]
+    HintCallOutsideLet,
+
+    #[error("`{0}` can't be called from a hint function")]
+    InvalidHintCall(String),
+
+    #[error("the mode of a sponge must be known at compile time, and be one of the modes set by `Sponge.new`, `Sponge.absorb` or `Sponge.squeeze`")]
+    InvalidSpongeMode,
+
+    #[error("the result of this elliptic curve operation is the point at infinity, which can't be represented in affine coordinates")]
+    PointAtInfinity,
+
+    #[error("this point is not on the curve `y^2 = x^3 + 5`")]
+    PointNotOnCurve,
+
+    #[error("`std::ec` is only available with the kimchi backend, as its curve (Pallas) is defined over the circuit field of kimchi")]
+    UnsupportedEcBackend,
 }
@@ -9,7 +9,7 @@ use crate::{
     error::Result,
     parser::types::{FnSig, FunctionDef},
     type_checker::{FnInfo, TypeChecker},
-    var::Var,
+    var::{ConstOrCell, Var},
 };
 
 #[derive(Debug)]
@@ -68,6 +68,11 @@ pub type FnHandle<B: Backend> = fn(
     Span,
 ) -> Result<Option<Var<B::Field, B::Var>>>;
 
+/// A handle to the poseidon permutation of a backend, on a state of 3 field elements.
+/// It constrains the permutation of the given state, and returns the resulting state.
+pub type PermutationHandle<B: Backend> =
+    fn(&mut CircuitWriter<B>, &[ConstOrCell<B::Field, B::Var>], Span) -> Result<Vec<B::Var>>;
+
 /// The different types of a noname function.
 #[derive(Clone, Serialize, Deserialize)]
 pub enum FnKind<B>
@@ -2,15 +2,20 @@
 
 use std::{collections::HashMap, fs::File, io::Read, str::FromStr};
 
-use ark_ff::{One, PrimeField, Zero};
+use ark_ff::One;
+use itertools::chain;
 use miette::Diagnostic;
 use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{
-    backends::{kimchi::VestaField, Backend},
-    parser::types::TyKind,
-    type_checker::FullyQualified,
+    backends::{Backend, BackendField},
+    constants::Span,
+    error::{self, Error, ErrorKind},
+    imports::FnKind,
+    parser::types::{FnArg, Ty, TyKind},
+    type_checker::{size_of, FullyQualified},
     witness::CompiledCircuit,
 };
 
@@ -32,8 +37,14 @@ pub enum ParsingError {
     #[error("couldn't convert given field element `{0}`")]
     InvalidField(String),
 
+    #[error("the given value `{1}` does not fit in a {0}")]
+    InvalidUint(TyKind, String),
+
     #[error("mismatch between expected argument format ({0}), and given argument in JSON (`{1}`)")]
     MismatchJsonArgument(TyKind, serde_json::Value),
+
+    #[error("the enum `{0}` does not have a variant called `{1}`")]
+    InvalidVariant(String, String),
 }
 
 //
@@ -73,77 +84,440 @@ impl<B: Backend> CompiledCircuit<B> {
         input: serde_json::Value,
         expected_input: &TyKind,
     ) -> Result<Vec<B::Field>, ParsingError> {
+        parse_single_input(
+            input,
+            expected_input,
+            &|qualified| {
+                self.circuit
+                    .struct_info(qualified)
+                    .map(|struct_info| struct_info.fields.as_slice())
+            },
+            &|qualified| {
+                self.circuit
+                    .enum_info(qualified)
+                    .map(|enum_info| enum_info.variants.as_slice())
+            },
+        )
+    }
+
+    /// Encodes field elements in JSON, following the given type.
+    /// This is the inverse of [Self::parse_single_input].
+    pub fn encode_single_output(
+        &self,
+        fields: &mut impl Iterator<Item = B::Field>,
+        typ: &TyKind,
+    ) -> serde_json::Value {
         use serde_json::Value;
 
-        match (expected_input, input) {
-            (TyKind::BigInt, _) => unreachable!(),
-            (TyKind::Field, Value::String(ss)) => {
-                let cell_value =
-                    B::Field::from_str(&ss).map_err(|_| ParsingError::InvalidField(ss))?;
-                Ok(vec![cell_value])
-            }
-            (TyKind::Bool, Value::Bool(bb)) => {
-                let ff = if bb {
-                    B::Field::one()
-                } else {
-                    B::Field::zero()
-                };
-                Ok(vec![ff])
+        match typ {
+            TyKind::BigInt | TyKind::GenericSizedArray(..) => unreachable!(),
+            TyKind::Field | TyKind::U8 | TyKind::U16 | TyKind::U32 | TyKind::U64 => {
+                let field: BigUint = fields.next().expect("not enough field elements").into();
+                Value::String(field.to_string())
             }
+            TyKind::Bool => Value::Bool(fields.next().expect("not enough field elements").is_one()),
+            TyKind::Array(el_typ, size) => Value::Array(
+                (0..*size)
+                    .map(|_| self.encode_single_output(fields, el_typ))
+                    .collect(),
+            ),
+            TyKind::Tuple(types) => Value::Array(
+                types
+                    .iter()
+                    .map(|typ| self.encode_single_output(fields, typ))
+                    .collect(),
+            ),
+            TyKind::Custom {
+                module,
+                name: struct_name,
+            } => {
+                let qualified = FullyQualified::new(module, struct_name);
 
-            (TyKind::Array(el_typ, size), Value::Array(values)) => {
-                if values.len() != (*size as usize) {
-                    panic!("wrong size of array");
-                }
-                let mut res = vec![];
-                for value in values {
-                    let el = self.parse_single_input(value, el_typ)?;
-                    res.extend(el);
+                // enums are encoded as `{"Variant": [values]}`, or `"Variant"` if there are no values
+                if let Some(enum_info) = self.circuit.enum_info(&qualified) {
+                    let tag: BigUint = fields.next().expect("not enough field elements").into();
+                    let tag = usize::try_from(tag).expect("invalid enum tag");
+                    let (variant, types) = &enum_info.variants[tag];
+
+                    let values: Vec<_> = types
+                        .iter()
+                        .map(|typ| self.encode_single_output(fields, typ))
+                        .collect();
+
+                    // skip the padding
+                    let padding = self.circuit.size_of(typ)
+                        - 1
+                        - types
+                            .iter()
+                            .map(|typ| self.circuit.size_of(typ))
+                            .sum::<usize>();
+                    fields.take(padding).for_each(drop);
+
+                    if values.is_empty() {
+                        return Value::String(variant.clone());
+                    }
+                    let map = [(variant.clone(), Value::Array(values))]
+                        .into_iter()
+                        .collect();
+                    return Value::Object(map);
                 }
 
-                Ok(res)
-            }
-            (
-                TyKind::Custom {
-                    module,
-                    name: struct_name,
-                },
-                Value::Object(mut map),
-            ) => {
-                // get fields of struct
-                let qualified = FullyQualified::new(module, struct_name);
                 let struct_info = self
                     .circuit
                     .struct_info(&qualified)
-                    .expect("compiler bug: couldn't find struct given as input");
-                let fields = &struct_info.fields;
-
-                // make sure that they're the same length
-                if fields.len() != map.len() {
-                    panic!("wrong number of fields in struct (TODO: better error)");
-                }
+                    .expect("compiler bug: couldn't find struct given as output");
 
-                // parse each field
-                let mut res = vec![];
-                for (field_name, field_ty) in fields {
-                    let value = map
-                    .remove(field_name)
-                    .ok_or_else(|| {
-                        format!("couldn't find field `{field_name}` in given JSON input (TODO: better error)")
+                let map = struct_info
+                    .fields
+                    .iter()
+                    .map(|(field_name, field_ty)| {
+                        (
+                            field_name.clone(),
+                            self.encode_single_output(fields, field_ty),
+                        )
                     })
-                    .unwrap();
-                    let parsed = self.parse_single_input(value, field_ty)?;
-                    res.extend(parsed);
-                }
+                    .collect();
+
+                Value::Object(map)
+            }
+        }
+    }
+}
+
+/// Looks up the fields of a custom struct.
+pub type StructFields<'a> = dyn Fn(&FullyQualified) -> Option<&'a [(String, TyKind)]> + 'a;
+
+/// Looks up the variants of a custom enum.
+pub type EnumVariants<'a> = dyn Fn(&FullyQualified) -> Option<&'a [(String, Vec<TyKind>)]> + 'a;
+
+/// Parses a JSON-encoded input of the given type into field elements.
+/// The fields of custom structs are looked up via `struct_fields`,
+/// and the variants of custom enums via `enum_variants`.
+pub fn parse_single_input<'a, F: BackendField>(
+    input: serde_json::Value,
+    expected_input: &TyKind,
+    struct_fields: &StructFields<'a>,
+    enum_variants: &EnumVariants<'a>,
+) -> Result<Vec<F>, ParsingError> {
+    use serde_json::Value;
+
+    // enums are encoded as `{"Variant": [values]}`, or `"Variant"` if there are no values
+    if let TyKind::Custom { module, name } = expected_input {
+        if let Some(variants) = enum_variants(&FullyQualified::new(module, name)) {
+            let mismatch =
+                |input| ParsingError::MismatchJsonArgument(expected_input.clone(), input);
+
+            let (variant, values) = match input {
+                Value::String(variant) => (variant, vec![]),
+                Value::Object(map) if map.len() == 1 => match map.into_iter().next().unwrap() {
+                    (variant, Value::Array(values)) => (variant, values),
+                    (variant, values) => {
+                        return Err(mismatch(Value::Object(
+                            [(variant, values)].into_iter().collect(),
+                        )))
+                    }
+                },
+                input => return Err(mismatch(input)),
+            };
+
+            let tag = variants
+                .iter()
+                .position(|(name, _)| name == &variant)
+                .ok_or_else(|| ParsingError::InvalidVariant(name.clone(), variant.clone()))?;
+            let types = &variants[tag].1;
+            if types.len() != values.len() {
+                return Err(mismatch(Value::Array(values)));
+            }
+
+            // the tag, followed by the values of the variant
+            let mut res = vec![F::from(tag as u64)];
+            for (value, typ) in values.into_iter().zip(types) {
+                let el = parse_single_input::<F>(value, typ, struct_fields, enum_variants)?;
+                res.extend(el);
+            }
+
+            // padded with zeros to fit the values of any variant
+            res.resize(
+                size_of(expected_input, struct_fields, enum_variants),
+                F::zero(),
+            );
+
+            return Ok(res);
+        }
+    }
+
+    match (expected_input, input) {
+        (TyKind::BigInt, _) => unreachable!(),
+        (TyKind::Field, Value::String(ss)) => {
+            let cell_value = F::from_str(&ss).map_err(|_| ParsingError::InvalidField(ss))?;
+            Ok(vec![cell_value])
+        }
+        (uint, Value::String(ss)) if uint.uint_bits().is_some() => Ok(vec![parse_uint(uint, &ss)?]),
+        (uint, Value::Number(nn)) if uint.uint_bits().is_some() => {
+            Ok(vec![parse_uint(uint, &nn.to_string())?])
+        }
+        (TyKind::Bool, Value::Bool(bb)) => {
+            let ff = if bb { F::one() } else { F::zero() };
+            Ok(vec![ff])
+        }
+
+        (TyKind::Array(el_typ, size), Value::Array(values)) => {
+            if values.len() != (*size as usize) {
+                panic!("wrong size of array");
+            }
+            let mut res = vec![];
+            for value in values {
+                let el = parse_single_input::<F>(value, el_typ, struct_fields, enum_variants)?;
+                res.extend(el);
+            }
+
+            Ok(res)
+        }
+        // tuples are encoded as JSON arrays
+        (TyKind::Tuple(types), Value::Array(values)) if types.len() == values.len() => {
+            let mut res = vec![];
+            for (value, typ) in values.into_iter().zip(types) {
+                let el = parse_single_input::<F>(value, typ, struct_fields, enum_variants)?;
+                res.extend(el);
+            }
+
+            Ok(res)
+        }
+        (
+            TyKind::Custom {
+                module,
+                name: struct_name,
+            },
+            Value::Object(mut map),
+        ) => {
+            // get fields of struct
+            let qualified = FullyQualified::new(module, struct_name);
+            let fields = struct_fields(&qualified)
+                .expect("compiler bug: couldn't find struct given as input");
+
+            // make sure that they're the same length
+            if fields.len() != map.len() {
+                panic!("wrong number of fields in struct (TODO: better error)");
+            }
+
+            // parse each field
+            let mut res = vec![];
+            for (field_name, field_ty) in fields {
+                let value = map
+                .remove(field_name)
+                .ok_or_else(|| {
+                    format!("couldn't find field `{field_name}` in given JSON input (TODO: better error)")
+                })
+                .unwrap();
+                let parsed =
+                    parse_single_input::<F>(value, field_ty, struct_fields, enum_variants)?;
+                res.extend(parsed);
+            }
+
+            Ok(res)
+        }
+        (expected, observed) => {
+            return Err(ParsingError::MismatchJsonArgument(
+                expected.clone(),
+                observed,
+            ));
+        }
+    }
+}
+
+/// Parses a decimal string into an unsigned integer of the given type,
+/// making sure that it fits.
+fn parse_uint<F: BackendField>(uint: &TyKind, ss: &str) -> Result<F, ParsingError> {
+    let num_bits = uint.uint_bits().expect("expected an unsigned integer type");
+    let invalid = || ParsingError::InvalidUint(uint.clone(), ss.to_string());
+
+    let value = u64::from_str(ss).map_err(|_| invalid())?;
+    if num_bits < 64 && value >> num_bits != 0 {
+        return Err(invalid());
+    }
+
+    Ok(F::from(value))
+}
+
+//
+// Public input layout
+//
+
+/// The types of the public inputs and of the public output of a circuit.
+/// This is all a verifier needs to encode the JSON-encoded public inputs and output,
+/// so that proofs can be verified without access to the source code.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PublicIO {
+    /// The type returned by the main function, if any.
+    pub return_type: Option<Ty>,
+
+    /// The public arguments of the main function, in order.
+    pub public_args: Vec<FnArg>,
+
+    /// The fields of all the structs appearing in the public inputs and output.
+    pub structs: Vec<(FullyQualified, Vec<(String, TyKind)>)>,
+
+    /// The variants of all the enums appearing in the public inputs and output.
+    pub enums: Vec<(FullyQualified, Vec<(String, Vec<TyKind>)>)>,
+
+    /// The span of the main function (used for errors).
+    pub main_span: Span,
+}
+
+impl PublicIO {
+    fn struct_fields(&self, qualified: &FullyQualified) -> Option<&[(String, TyKind)]> {
+        self.structs
+            .iter()
+            .find(|(name, _)| name == qualified)
+            .map(|(_, fields)| fields.as_slice())
+    }
+
+    fn enum_variants(&self, qualified: &FullyQualified) -> Option<&[(String, Vec<TyKind>)]> {
+        self.enums
+            .iter()
+            .find(|(name, _)| name == qualified)
+            .map(|(_, variants)| variants.as_slice())
+    }
+
+    /// Returns the full public input expected by a verifier:
+    /// the public output (if any) followed by the public inputs,
+    /// in the order in which they appear in the main function.
+    pub fn full_public_inputs<F: BackendField>(
+        &self,
+        mut public_inputs: JsonInputs,
+        public_output: Option<serde_json::Value>,
+    ) -> error::Result<Vec<F>> {
+        let struct_fields = |qualified: &FullyQualified| self.struct_fields(qualified);
+        let enum_variants = |qualified: &FullyQualified| self.enum_variants(qualified);
+
+        let mut res = vec![];
 
-                Ok(res)
+        // the public output comes first
+        match (&self.return_type, public_output) {
+            (Some(typ), Some(output)) => {
+                let fields =
+                    parse_single_input::<F>(output, &typ.kind, &struct_fields, &enum_variants)
+                        .map_err(|e| Error::new("runtime", ErrorKind::ParsingError(e), typ.span))?;
+                res.extend(fields);
             }
-            (expected, observed) => {
-                return Err(ParsingError::MismatchJsonArgument(
-                    expected.clone(),
-                    observed,
-                ));
+            (Some(typ), None) => {
+                return Err(Error::new(
+                    "runtime",
+                    ErrorKind::MissingPublicOutput,
+                    typ.span,
+                ))
             }
+            (None, Some(_)) => {
+                return Err(Error::new(
+                    "runtime",
+                    ErrorKind::UnexpectedPublicOutput,
+                    self.main_span,
+                ))
+            }
+            (None, None) => (),
+        }
+
+        // then the public inputs
+        for arg in &self.public_args {
+            let name = &arg.name.value;
+
+            let input = public_inputs.0.remove(name).ok_or_else(|| {
+                Error::new(
+                    "runtime",
+                    ErrorKind::MissingPublicArg(name.clone()),
+                    arg.span,
+                )
+            })?;
+
+            let fields =
+                parse_single_input::<F>(input, &arg.typ.kind, &struct_fields, &enum_variants)
+                    .map_err(|e| Error::new("runtime", ErrorKind::ParsingError(e), arg.span))?;
+
+            res.extend(fields);
+        }
+
+        // ensure that we've used all of the inputs provided
+        if let Some(name) = public_inputs.0.keys().next() {
+            return Err(Error::new(
+                "runtime",
+                ErrorKind::UnusedInput(name.clone()),
+                self.main_span,
+            ));
+        }
+
+        Ok(res)
+    }
+}
+
+impl<B: Backend> CompiledCircuit<B> {
+    /// Extracts the [PublicIO] of the main function.
+    pub fn public_io(&self) -> PublicIO {
+        let main_info = self.main_info();
+        let main_sig = match &main_info.kind {
+            FnKind::BuiltIn(_, _) => unreachable!(),
+            FnKind::Native(fn_def) => &fn_def.sig,
+        };
+
+        let return_type = main_sig.return_type.clone();
+        let public_args: Vec<_> = main_sig
+            .arguments
+            .iter()
+            .filter(|arg| arg.is_public())
+            .cloned()
+            .collect();
+
+        // collect the structs and enums used, including the ones nested in other types
+        let mut structs: Vec<(FullyQualified, Vec<(String, TyKind)>)> = vec![];
+        let mut enums: Vec<(FullyQualified, Vec<(String, Vec<TyKind>)>)> = vec![];
+        let mut to_visit: Vec<&TyKind> = chain![
+            public_args.iter().map(|arg| &arg.typ.kind),
+            return_type.iter().map(|typ| &typ.kind)
+        ]
+        .collect();
+
+        while let Some(typ) = to_visit.pop() {
+            match typ {
+                TyKind::Field
+                | TyKind::BigInt
+                | TyKind::Bool
+                | TyKind::U8
+                | TyKind::U16
+                | TyKind::U32
+                | TyKind::U64 => (),
+                TyKind::Array(el_typ, _) | TyKind::GenericSizedArray(el_typ, _) => {
+                    to_visit.push(el_typ)
+                }
+                TyKind::Tuple(types) => to_visit.extend(types),
+                TyKind::Custom { module, name } => {
+                    let qualified = FullyQualified::new(module, name);
+                    if structs.iter().any(|(seen, _)| seen == &qualified)
+                        || enums.iter().any(|(seen, _)| seen == &qualified)
+                    {
+                        continue;
+                    }
+
+                    if let Some(enum_info) = self.circuit.enum_info(&qualified) {
+                        to_visit.extend(enum_info.variants.iter().flat_map(|(_, types)| types));
+                        enums.push((qualified, enum_info.variants.clone()));
+                        continue;
+                    }
+
+                    let struct_info = self
+                        .circuit
+                        .struct_info(&qualified)
+                        .expect("compiler bug: couldn't find struct used in main");
+                    to_visit.extend(struct_info.fields.iter().map(|(_, typ)| typ));
+                    structs.push((qualified, struct_info.fields.clone()));
+                }
+            }
+        }
+
+        PublicIO {
+            return_type,
+            public_args,
+            structs,
+            enums,
+            main_span: main_info.span,
         }
     }
 }
@@ -156,15 +530,17 @@ pub trait ExtField /* : PrimeField*/ {
     fn to_dec_string(&self) -> String;
 }
 
-impl ExtField for VestaField {
+impl<F: BackendField> ExtField for F {
     fn to_dec_string(&self) -> String {
-        let biguint: BigUint = self.into_repr().into();
+        let biguint: BigUint = (*self).into();
         biguint.to_str_radix(10)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::{backends::kimchi::VestaField, parser::types::ModulePath};
+
     use super::*;
 
     #[test]
@@ -172,4 +548,112 @@ mod tests {
         let field = VestaField::from(42);
         assert_eq!(field.to_dec_string(), "42");
     }
+
+    #[test]
+    fn test_parse_uint() {
+        let no_structs = |_: &FullyQualified| -> Option<&'static [(String, TyKind)]> { None };
+        let no_enums = |_: &FullyQualified| -> Option<&'static [(String, Vec<TyKind>)]> { None };
+        let parse = |value: serde_json::Value, typ: &TyKind| {
+            parse_single_input::<VestaField>(value, typ, &no_structs, &no_enums)
+        };
+
+        assert_eq!(
+            parse(serde_json::json!("255"), &TyKind::U8).unwrap(),
+            vec![VestaField::from(255)]
+        );
+        assert_eq!(
+            parse(serde_json::json!(65535), &TyKind::U16).unwrap(),
+            vec![VestaField::from(65535)]
+        );
+        assert_eq!(
+            parse(serde_json::json!("18446744073709551615"), &TyKind::U64).unwrap(),
+            vec![VestaField::from(u64::MAX)]
+        );
+
+        // values that don't fit are rejected
+        assert!(matches!(
+            parse(serde_json::json!("256"), &TyKind::U8),
+            Err(ParsingError::InvalidUint(..))
+        ));
+        assert!(matches!(
+            parse(serde_json::json!("-1"), &TyKind::U32),
+            Err(ParsingError::InvalidUint(..))
+        ));
+    }
+
+    #[test]
+    fn test_parse_tuple() {
+        let no_structs = |_: &FullyQualified| -> Option<&'static [(String, TyKind)]> { None };
+        let no_enums = |_: &FullyQualified| -> Option<&'static [(String, Vec<TyKind>)]> { None };
+        let parse = |value: serde_json::Value, typ: &TyKind| {
+            parse_single_input::<VestaField>(value, typ, &no_structs, &no_enums)
+        };
+        let typ = TyKind::Tuple(vec![TyKind::Field, TyKind::Bool]);
+
+        // tuples are encoded as JSON arrays
+        assert_eq!(
+            parse(serde_json::json!(["3", true]), &typ).unwrap(),
+            vec![VestaField::from(3), VestaField::from(1)]
+        );
+
+        // with exactly one value per element
+        assert!(matches!(
+            parse(serde_json::json!(["3"]), &typ),
+            Err(ParsingError::MismatchJsonArgument(..))
+        ));
+    }
+
+    #[test]
+    fn test_parse_enum() {
+        let variants = vec![
+            ("Square".to_string(), vec![TyKind::Field]),
+            ("Rect".to_string(), vec![TyKind::Field, TyKind::Field]),
+            ("Empty".to_string(), vec![]),
+        ];
+        let no_structs: &StructFields = &|_| None;
+        let enums: &EnumVariants = &|_| Some(variants.as_slice());
+        let parse = |value: serde_json::Value| {
+            let typ = TyKind::Custom {
+                module: ModulePath::Local,
+                name: "Shape".to_string(),
+            };
+            parse_single_input::<VestaField>(value, &typ, no_structs, enums)
+        };
+
+        // the tag is followed by the values of the variant, padded with zeros
+        assert_eq!(
+            parse(serde_json::json!({"Square": ["3"]})).unwrap(),
+            vec![
+                VestaField::from(0),
+                VestaField::from(3),
+                VestaField::from(0)
+            ]
+        );
+        assert_eq!(
+            parse(serde_json::json!({"Rect": ["2", "5"]})).unwrap(),
+            vec![
+                VestaField::from(1),
+                VestaField::from(2),
+                VestaField::from(5)
+            ]
+        );
+        assert_eq!(
+            parse(serde_json::json!("Empty")).unwrap(),
+            vec![
+                VestaField::from(2),
+                VestaField::from(0),
+                VestaField::from(0)
+            ]
+        );
+
+        // unknown variants, or a wrong number of values, are rejected
+        assert!(matches!(
+            parse(serde_json::json!({"Circle": ["3"]})),
+            Err(ParsingError::InvalidVariant(..))
+        ));
+        assert!(matches!(
+            parse(serde_json::json!({"Rect": ["2"]})),
+            Err(ParsingError::MismatchJsonArgument(..))
+        ));
+    }
 }
@@ -3,7 +3,7 @@ use std::fmt::Display;
 use crate::{
     constants::Span,
     error::{Error, ErrorKind, Result},
-    syntax::is_identifier_or_type,
+    syntax::{is_generic_parameter, is_identifier_or_type},
 };
 
 use num_bigint::BigUint;
@@ -68,6 +68,12 @@ pub enum Keyword {
     Struct,
     /// Allows constants to be defined
     Const,
+    /// Allows custom enums to be defined
+    Enum,
+    /// The `match` keyword for matching on enums
+    Match,
+    /// A hint function, computed outside of the circuit
+    Hint,
 }
 
 impl Keyword {
@@ -87,6 +93,9 @@ impl Keyword {
             "in" => Some(Self::In),
             "struct" => Some(Self::Struct),
             "const" => Some(Self::Const),
+            "enum" => Some(Self::Enum),
+            "match" => Some(Self::Match),
+            "hint" => Some(Self::Hint),
             _ => None,
         }
     }
@@ -109,6 +118,9 @@ impl Display for Keyword {
             Self::In => "in",
             Self::Struct => "struct",
             Self::Const => "const",
+            Self::Enum => "enum",
+            Self::Match => "match",
+            Self::Hint => "hint",
         };
 
         write!(f, "{}", desc)
@@ -136,19 +148,29 @@ pub enum TokenKind {
     Comment(String),    // // comment
     Greater,            // >
     Less,               // <
+    GreaterEqual,       // >=
+    LessEqual,          // <=
+    DoubleGreater,      // >>
+    DoubleLess,         // <<
     Equal,              // =
     DoubleEqual,        // ==
+    FatArrow,           // =>
     NotEqual,           // !=
     Plus,               // +
+    PlusEqual,          // +=
     Minus,              // -
+    MinusEqual,         // -=
     RightArrow,         // ->
     Star,               // *
+    StarEqual,          // *=
     Ampersand,          // &
     DoubleAmpersand,    // &&
     Pipe,               // |
     DoublePipe,         // ||
+    Caret,              // ^
     Exclamation,        // !
     Question,           // ?
+    Underscore,         // _
                         //    Literal,               // "thing"
 }
 
@@ -177,19 +199,29 @@ impl Display for TokenKind {
             Comment(_) => "`//`",
             Greater => "`>`",
             Less => "`<`",
+            GreaterEqual => "`>=`",
+            LessEqual => "`<=`",
+            DoubleGreater => "`>>`",
+            DoubleLess => "`<<`",
             Equal => "`=`",
             DoubleEqual => "`==`",
+            FatArrow => "`=>`",
             NotEqual => "`!=`",
             Plus => "`+`",
+            PlusEqual => "`+=`",
             Minus => "`-`",
+            MinusEqual => "`-=`",
             RightArrow => "`->`",
             Star => "`*`",
+            StarEqual => "`*=`",
             Ampersand => "`&`",
             DoubleAmpersand => "`&&`",
             Pipe => "`|`",
             DoublePipe => "`||`",
+            Caret => "`^`",
             Exclamation => "`!`",
             Question => "`?`",
+            Underscore => "`_`",
             //            TokenType::Literal => "`\"something\"",
         };
 
@@ -230,6 +262,8 @@ impl Token {
             let len = ident_or_number.len();
             if let Some(keyword) = Keyword::parse(&ident_or_number) {
                 tokens.push(TokenKind::Keyword(keyword).new_token(ctx, len));
+            } else if ident_or_number == "_" {
+                tokens.push(TokenKind::Underscore.new_token(ctx, len));
             } else {
                 let token_type = if let Ok(big_uint) = BigUint::from_str_radix(&ident_or_number, 10)
                 {
@@ -245,7 +279,9 @@ impl Token {
                             ));
                         }
                     }
-                } else if is_identifier_or_type(&ident_or_number) {
+                } else if is_identifier_or_type(&ident_or_number)
+                    || is_generic_parameter(&ident_or_number)
+                {
                     if ident_or_number.len() < 2 {
                         return Err(ctx.error(
                             ErrorKind::NoOneLetterVariable,
@@ -361,34 +397,70 @@ impl Token {
                     }
                 }
                 '>' => {
-                    tokens.push(TokenKind::Greater.new_token(ctx, 1));
+                    let next_c = chars.peek();
+                    if matches!(next_c, Some(&'=')) {
+                        tokens.push(TokenKind::GreaterEqual.new_token(ctx, 2));
+                        chars.next();
+                    } else if matches!(next_c, Some(&'>')) {
+                        tokens.push(TokenKind::DoubleGreater.new_token(ctx, 2));
+                        chars.next();
+                    } else {
+                        tokens.push(TokenKind::Greater.new_token(ctx, 1));
+                    }
                 }
                 '<' => {
-                    tokens.push(TokenKind::Less.new_token(ctx, 1));
+                    let next_c = chars.peek();
+                    if matches!(next_c, Some(&'=')) {
+                        tokens.push(TokenKind::LessEqual.new_token(ctx, 2));
+                        chars.next();
+                    } else if matches!(next_c, Some(&'<')) {
+                        tokens.push(TokenKind::DoubleLess.new_token(ctx, 2));
+                        chars.next();
+                    } else {
+                        tokens.push(TokenKind::Less.new_token(ctx, 1));
+                    }
                 }
                 '=' => {
                     let next_c = chars.peek();
                     if matches!(next_c, Some(&'=')) {
                         tokens.push(TokenKind::DoubleEqual.new_token(ctx, 2));
                         chars.next();
+                    } else if matches!(next_c, Some(&'>')) {
+                        tokens.push(TokenKind::FatArrow.new_token(ctx, 2));
+                        chars.next();
                     } else {
                         tokens.push(TokenKind::Equal.new_token(ctx, 1));
                     }
                 }
                 '+' => {
-                    tokens.push(TokenKind::Plus.new_token(ctx, 1));
+                    let next_c = chars.peek();
+                    if matches!(next_c, Some(&'=')) {
+                        tokens.push(TokenKind::PlusEqual.new_token(ctx, 2));
+                        chars.next();
+                    } else {
+                        tokens.push(TokenKind::Plus.new_token(ctx, 1));
+                    }
                 }
                 '-' => {
                     let next_c = chars.peek();
                     if matches!(next_c, Some(&'>')) {
                         tokens.push(TokenKind::RightArrow.new_token(ctx, 2));
                         chars.next();
+                    } else if matches!(next_c, Some(&'=')) {
+                        tokens.push(TokenKind::MinusEqual.new_token(ctx, 2));
+                        chars.next();
                     } else {
                         tokens.push(TokenKind::Minus.new_token(ctx, 1));
                     }
                 }
                 '*' => {
-                    tokens.push(TokenKind::Star.new_token(ctx, 1));
+                    let next_c = chars.peek();
+                    if matches!(next_c, Some(&'=')) {
+                        tokens.push(TokenKind::StarEqual.new_token(ctx, 2));
+                        chars.next();
+                    } else {
+                        tokens.push(TokenKind::Star.new_token(ctx, 1));
+                    }
                 }
                 '&' => {
                     let next_c = chars.peek();
@@ -421,6 +493,9 @@ impl Token {
                 '?' => {
                     tokens.push(TokenKind::Question.new_token(ctx, 1));
                 }
+                '^' => {
+                    tokens.push(TokenKind::Caret.new_token(ctx, 1));
+                }
                 ' ' => ctx.offset += 1,
                 _ => {
                     return Err(ctx.error(
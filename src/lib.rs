@@ -68,4 +68,18 @@ pub mod helpers {
         sponge.absorb(&input);
         sponge.squeeze()
     }
+
+    /// Returns the sponge that `std::crypto::Sponge` mirrors.
+    pub fn poseidon_sponge() -> ArithmeticSponge<VestaField, PlonkSpongeConstantsKimchi> {
+        ArithmeticSponge::new(fp_kimchi::static_params())
+    }
+
+    /// Hashes field elements like `std::crypto::hash`,
+    /// which absorbs the number of elements before the elements.
+    pub fn poseidon_hash(input: &[VestaField]) -> VestaField {
+        let mut sponge = poseidon_sponge();
+        sponge.absorb(&[VestaField::from(input.len() as u64)]);
+        sponge.absorb(input);
+        sponge.squeeze()
+    }
 }
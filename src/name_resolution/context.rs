@@ -3,25 +3,25 @@ use std::collections::HashMap;
 use ark_ff::Field;
 
 use crate::{
-    cli::packages::UserRepo,
+    cli::packages::{QualifiedModule, SELF_MODULE},
     constants::Span,
     error::{Error, ErrorKind, Result},
     parser::{
-        types::{FnArg, FnSig, FuncOrMethod, ModulePath, Stmt, StmtKind, TyKind},
-        ConstDef, CustomType, FunctionDef, StructDef, UsePath,
+        types::{FnArg, FnSig, ForLoopArgument, FuncOrMethod, ModulePath, Stmt, StmtKind, TyKind},
+        ConstDef, CustomType, EnumDef, FunctionDef, StructDef, UsePath,
     },
 };
 
 pub struct NameResCtx {
     /// Set only if this module is a third-party library.
-    pub this_module: Option<UserRepo>,
+    pub this_module: Option<QualifiedModule>,
 
     /// maps `module` to its original `use a::module`
     pub modules: HashMap<String, UsePath>,
 }
 
 impl NameResCtx {
-    pub(crate) fn new(this_module: Option<UserRepo>) -> Self {
+    pub(crate) fn new(this_module: Option<QualifiedModule>) -> Self {
         Self {
             this_module,
             modules: HashMap::new(),
@@ -50,7 +50,7 @@ impl NameResCtx {
             // if this is a third-party module, qualify it with its `user::repo` name
             ModulePath::Alias(alias) => {
                 if let Some(use_path) = self.modules.get(&alias.value) {
-                    *module = ModulePath::Absolute(use_path.into());
+                    *module = ModulePath::Absolute(self.qualify(use_path));
                 } else {
                     return Err(
                         self.error(ErrorKind::UndefinedModule(alias.value.clone()), alias.span)
@@ -65,8 +65,27 @@ impl NameResCtx {
         Ok(())
     }
 
+    /// Returns the fully-qualified path of an imported module.
+    /// `use self::module` refers to the file `src/module.no` of the current package.
+    pub(crate) fn qualify(&self, use_path: &UsePath) -> QualifiedModule {
+        if use_path.module.value == SELF_MODULE {
+            let package = self.this_module.as_ref().and_then(QualifiedModule::package);
+            QualifiedModule::Submodule {
+                package: package.cloned(),
+                name: use_path.submodule.value.clone(),
+            }
+        } else {
+            QualifiedModule::Package(use_path.into())
+        }
+    }
+
     pub(crate) fn resolve_fn_def(&self, fn_def: &mut FunctionDef) -> Result<()> {
-        let FunctionDef { sig, body, span: _ } = fn_def;
+        let FunctionDef {
+            sig,
+            body,
+            span: _,
+            is_hint: _,
+        } = fn_def;
 
         //
         // signature
@@ -130,8 +149,15 @@ impl NameResCtx {
                 self.resolve(module, false)?;
             }
             TyKind::BigInt => (),
-            TyKind::Array(typ_kind, _) => self.resolve_typ_kind(typ_kind)?,
-            TyKind::Bool => (),
+            TyKind::Array(typ_kind, _) | TyKind::GenericSizedArray(typ_kind, _) => {
+                self.resolve_typ_kind(typ_kind)?
+            }
+            TyKind::Bool | TyKind::U8 | TyKind::U16 | TyKind::U32 | TyKind::U64 => (),
+            TyKind::Tuple(typ_kinds) => {
+                for typ_kind in typ_kinds {
+                    self.resolve_typ_kind(typ_kind)?;
+                }
+            }
         };
 
         Ok(())
@@ -156,6 +182,27 @@ impl NameResCtx {
         Ok(())
     }
 
+    pub(crate) fn resolve_enum_def(&self, enum_def: &mut EnumDef) -> Result<()> {
+        let EnumDef {
+            module,
+            name: _,
+            variants,
+            span: _,
+        } = enum_def;
+
+        // we set the fully-qualified name of the enum
+        self.resolve(module, true)?;
+
+        // we resolve the fully-qualified types of the values held by the variants
+        for (_variant, types) in variants {
+            for typ in types {
+                self.resolve_typ_kind(&mut typ.kind)?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn resolve_const_def<F: Field>(&self, cst_def: &mut ConstDef<F>) -> Result<()> {
         let ConstDef {
             module,
@@ -180,18 +227,30 @@ impl NameResCtx {
             } => {
                 self.resolve_expr(rhs)?;
             }
+            StmtKind::TupleAssign { lhs: _, rhs } => self.resolve_expr(rhs)?,
             StmtKind::Expr(expr) => self.resolve_expr(expr)?,
             StmtKind::Return(expr) => self.resolve_expr(expr)?,
             StmtKind::Comment(_) => (),
             StmtKind::ForLoop {
                 var: _,
-                range: _,
+                argument,
                 body,
             } => {
+                if let ForLoopArgument::Iterator(iterator) = argument {
+                    self.resolve_expr(iterator)?;
+                }
+
                 for stmt in body {
                     self.resolve_stmt(stmt)?;
                 }
             }
+            StmtKind::If { cond, then_, else_ } => {
+                self.resolve_expr(cond)?;
+
+                for stmt in then_.iter_mut().chain(else_) {
+                    self.resolve_stmt(stmt)?;
+                }
+            }
         };
 
         Ok(())
@@ -1,7 +1,7 @@
 use crate::{
     cli::packages::UserRepo,
     error::Result,
-    parser::{types::ModulePath, CustomType, Expr, ExprKind},
+    parser::{types::ModulePath, CustomType, Expr, ExprKind, MatchArm, MatchPattern},
     stdlib::{BUILTIN_FN_NAMES, QUALIFIED_BUILTINS},
 };
 
@@ -24,7 +24,7 @@ impl NameResCtx {
                 if matches!(module, ModulePath::Local) && BUILTIN_FN_NAMES.contains(&fn_name.value)
                 {
                     // if it's a builtin, use `std::builtin`
-                    *module = ModulePath::Absolute(UserRepo::new(QUALIFIED_BUILTINS));
+                    *module = ModulePath::Absolute(UserRepo::new(QUALIFIED_BUILTINS).into());
                 } else {
                     self.resolve(module, false)?;
                 }
@@ -43,13 +43,16 @@ impl NameResCtx {
                     self.resolve_expr(arg)?;
                 }
             }
-            ExprKind::Assignment { lhs, rhs } => {
+            ExprKind::Assignment { lhs, rhs } | ExprKind::CompoundAssignment { lhs, rhs, .. } => {
                 self.resolve_expr(lhs)?;
                 self.resolve_expr(rhs)?;
             }
             ExprKind::FieldAccess { lhs, rhs: _ } => {
                 self.resolve_expr(lhs)?;
             }
+            ExprKind::TupleAccess { tuple, idx: _ } => {
+                self.resolve_expr(tuple)?;
+            }
             ExprKind::BinaryOp {
                 op: _,
                 lhs,
@@ -73,7 +76,7 @@ impl NameResCtx {
                 self.resolve_expr(array)?;
                 self.resolve_expr(idx)?;
             }
-            ExprKind::ArrayDeclaration(items) => {
+            ExprKind::ArrayDeclaration(items) | ExprKind::TupleDeclaration(items) => {
                 for expr in items {
                     self.resolve_expr(expr)?;
                 }
@@ -92,6 +95,30 @@ impl NameResCtx {
                     self.resolve_expr(field_value)?;
                 }
             }
+            ExprKind::EnumVariant {
+                enum_name,
+                variant: _,
+                args,
+            } => {
+                self.resolve(&mut enum_name.module, false)?;
+                for arg in args {
+                    self.resolve_expr(arg)?;
+                }
+            }
+            ExprKind::Match { matched, arms } => {
+                self.resolve_expr(matched)?;
+                for arm in arms {
+                    let MatchArm {
+                        pattern,
+                        expr,
+                        span: _,
+                    } = arm;
+                    if let MatchPattern::Variant { enum_name, .. } = pattern {
+                        self.resolve(&mut enum_name.module, false)?;
+                    }
+                    self.resolve_expr(expr)?;
+                }
+            }
             ExprKind::Bool(_) => {}
             ExprKind::IfElse { cond, then_, else_ } => {
                 self.resolve_expr(cond)?;
@@ -1,8 +1,8 @@
 use crate::{
     backends::Backend,
-    cli::packages::UserRepo,
+    cli::packages::QualifiedModule,
     error::{Error, ErrorKind, Result},
-    parser::{ConstDef, FunctionDef, RootKind, StructDef, AST},
+    parser::{ConstDef, EnumDef, FunctionDef, RootKind, StructDef, AST},
 };
 
 use self::context::NameResCtx;
@@ -22,7 +22,10 @@ impl<B: Backend> NAST<B> {
         Self { ast }
     }
 
-    pub fn resolve_modules(this_module: Option<UserRepo>, mut ast: AST<B>) -> Result<NAST<B>> {
+    pub fn resolve_modules(
+        this_module: Option<QualifiedModule>,
+        mut ast: AST<B>,
+    ) -> Result<NAST<B>> {
         let mut ctx = NameResCtx::new(this_module);
 
         // create a map of the imported modules (and how they are aliases)
@@ -54,6 +57,7 @@ impl<B: Backend> NAST<B> {
                 }
                 RootKind::FunctionDef(FunctionDef { span, .. })
                 | RootKind::StructDef(StructDef { span, .. })
+                | RootKind::EnumDef(EnumDef { span, .. })
                 | RootKind::ConstDef(ConstDef { span, .. }) => abort = Some(*span),
                 RootKind::Comment(_) => (),
             }
@@ -64,6 +68,7 @@ impl<B: Backend> NAST<B> {
             match &mut root.kind {
                 RootKind::FunctionDef(f) => ctx.resolve_fn_def(f)?,
                 RootKind::StructDef(s) => ctx.resolve_struct_def(s)?,
+                RootKind::EnumDef(e) => ctx.resolve_enum_def(e)?,
                 RootKind::ConstDef(c) => ctx.resolve_const_def(c)?,
                 RootKind::Use(_) | RootKind::Comment(_) => (),
             }
@@ -77,6 +82,7 @@ impl<B: Backend> NAST<B> {
 mod tests {
     use crate::{
         backends::kimchi::KimchiVesta,
+        cli::packages::UserRepo,
         lexer::Token,
         parser::{
             types::{ModulePath, StmtKind},
@@ -141,7 +147,11 @@ mod tests {
         };
 
         match module {
-            ModulePath::Absolute(u) if u == &UserRepo::new("user/repo") => (),
+            ModulePath::Absolute(QualifiedModule::Package(u))
+                if u == &UserRepo::new("user/repo") =>
+            {
+                ()
+            }
             _ => panic!("expected absolute module path"),
         };
     }
@@ -152,7 +162,7 @@ mod tests {
 
         let tokens = Token::parse(0, CODE).unwrap();
         let (ast, _node_id) = AST::<KimchiVesta>::parse(0, tokens, 0).unwrap();
-        let nast = NAST::resolve_modules(Some(user_repo.clone()), ast).unwrap();
+        let nast = NAST::resolve_modules(Some(user_repo.into()), ast).unwrap();
 
         // find constant declaration
         let mut roots = nast
@@ -166,7 +176,11 @@ mod tests {
         };
 
         match &cst.module {
-            ModulePath::Absolute(u) if u == &UserRepo::new("mimoo/example") => (),
+            ModulePath::Absolute(QualifiedModule::Package(u))
+                if u == &UserRepo::new("mimoo/example") =>
+            {
+                ()
+            }
             _ => panic!("expected absolute module path"),
         };
 
@@ -177,7 +191,11 @@ mod tests {
         };
 
         match &struct_def.module {
-            ModulePath::Absolute(u) if u == &UserRepo::new("mimoo/example") => (),
+            ModulePath::Absolute(QualifiedModule::Package(u))
+                if u == &UserRepo::new("mimoo/example") =>
+            {
+                ()
+            }
             _ => panic!("expected absolute module path"),
         };
 
@@ -196,7 +214,59 @@ mod tests {
         };
 
         match module {
-            ModulePath::Absolute(u) if u == &UserRepo::new("user/repo") => (),
+            ModulePath::Absolute(QualifiedModule::Package(u))
+                if u == &UserRepo::new("user/repo") =>
+            {
+                ()
+            }
+            _ => panic!("expected absolute module path"),
+        };
+    }
+
+    #[test]
+    fn test_name_res_for_submodule() {
+        let code = r#"
+        use self::merkle;
+
+        fn root(xx: Field) -> Field {
+            return merkle::hash(xx);
+        }
+        "#;
+
+        // a module of a library resolves `self` to the library
+        let this_module = QualifiedModule::Submodule {
+            package: Some(UserRepo::new("mimoo/example")),
+            name: "utils".to_string(),
+        };
+
+        let tokens = Token::parse(0, code).unwrap();
+        let (ast, _node_id) = AST::<KimchiVesta>::parse(0, tokens, 0).unwrap();
+        let nast = NAST::resolve_modules(Some(this_module), ast).unwrap();
+
+        let fn_def = nast
+            .ast
+            .0
+            .iter()
+            .find_map(|r| match &r.kind {
+                RootKind::FunctionDef(d) => Some(d),
+                _ => None,
+            })
+            .unwrap();
+        let fn_call = match &fn_def.body[0].kind {
+            StmtKind::Return(e) => e,
+            _ => panic!("expected return"),
+        };
+        let module = match &fn_call.kind {
+            ExprKind::FnCall { module, .. } => module,
+            _ => panic!("expected function call"),
+        };
+
+        let expected = QualifiedModule::Submodule {
+            package: Some(UserRepo::new("mimoo/example")),
+            name: "merkle".to_string(),
+        };
+        match module {
+            ModulePath::Absolute(u) if u == &expected => (),
             _ => panic!("expected absolute module path"),
         };
     }
@@ -1,8 +1,18 @@
+use ark_ff::One;
+
 use crate::{
-    backends::kimchi::KimchiVesta,
+    backends::{
+        kimchi::KimchiVesta,
+        r1cs::{R1csBn254Field, R1CS},
+        Backend,
+    },
+    circuit_writer::CircuitWriter,
     compiler::{typecheck_next_file_inner, Sources},
     error::ErrorKind,
+    inputs::parse_inputs,
+    parser::types::TyKind,
     type_checker::TypeChecker,
+    witness::WitnessEnv,
 };
 
 #[test]
@@ -73,3 +83,1056 @@ fn test_return_mismatch() {
         ErrorKind::ReturnTypeMismatch(..)
     ));
 }
+
+#[test]
+fn test_division_by_zero() {
+    // dividing by the constant zero
+    let code = r#"
+        fn main(pub xx: Field) {
+            let yy = xx / 0;
+            assert_eq(yy, xx);
+        }
+        "#;
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    )
+    .unwrap();
+
+    let res = CircuitWriter::generate_circuit(tast, KimchiVesta::new(false));
+
+    assert!(matches!(res.err().unwrap().kind, ErrorKind::DivisionByZero));
+}
+
+#[test]
+fn test_uint_division() {
+    // unsigned integers can't be divided
+    let code = r#"
+        fn main(pub xx: u8) -> u8 {
+            return xx / 2;
+        }
+        "#;
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    let res = typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    );
+
+    assert!(matches!(
+        res.unwrap_err().kind,
+        ErrorKind::UnsupportedUintOperation(..)
+    ));
+}
+
+#[test]
+fn test_uint_mismatch() {
+    // unsigned integers of different sizes can't be mixed
+    let code = r#"
+        fn main(pub xx: u8, yy: u16) -> u16 {
+            return xx + yy;
+        }
+        "#;
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    let res = typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    );
+
+    assert!(matches!(res.unwrap_err().kind, ErrorKind::MismatchType(..)));
+}
+
+#[test]
+fn test_uint_from_mixed_expression() {
+    // a constant mixed with a field element is a field element, which is not range checked
+    let code = r#"
+        fn double(xx: u8) -> u8 {
+            return xx + xx;
+        }
+
+        fn main(pub xx: Field) -> u8 {
+            let yy = 3 + xx;
+            return double(yy);
+        }
+        "#;
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    let res = typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    );
+
+    assert!(matches!(
+        res.unwrap_err().kind,
+        ErrorKind::ArgumentTypeMismatch(TyKind::U8, TyKind::Field)
+    ));
+}
+
+/// Type checks a function that passes the constant `arg` to a function taking a `u8`, and returns the result.
+fn typecheck_uint_argument(arg: &str) -> Result<(), ErrorKind> {
+    let code = format!(
+        r#"
+        fn double(xx: u8) -> u8 {{
+            return xx + xx;
+        }}
+
+        fn main(pub xx: u8) -> u8 {{
+            let yy = double({arg});
+            return xx + yy;
+        }}
+        "#
+    );
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code,
+        0,
+    )
+    .map(|_| ())
+    .map_err(|err| err.kind)
+}
+
+#[test]
+fn test_uint_constant_argument() {
+    // constants can be used as unsigned integers if they fit
+    assert!(typecheck_uint_argument("255").is_ok());
+    assert!(typecheck_uint_argument("(100 * 2) + 55").is_ok());
+
+    assert!(matches!(
+        typecheck_uint_argument("300"),
+        Err(ErrorKind::UintOverflow(_, TyKind::U8))
+    ));
+    assert!(matches!(
+        typecheck_uint_argument("(100 * 2) + 56"),
+        Err(ErrorKind::UintOverflow(_, TyKind::U8))
+    ));
+}
+
+#[test]
+fn test_unknown_uint_constant() {
+    // the value of a loop index is not known when type checking
+    let code = r#"
+        fn double(xx: u8) -> u8 {
+            return xx + xx;
+        }
+
+        fn main(pub xx: u8) {
+            for ii in 0..300 {
+                assert(double(ii) != xx);
+            }
+        }
+        "#;
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    let res = typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    );
+
+    assert!(matches!(
+        res.unwrap_err().kind,
+        ErrorKind::UnknownUintConstant(TyKind::U8)
+    ));
+}
+
+/// Compiles an addition of two `u8` and runs it with inputs that overflow.
+fn uint_overflow<B: Backend>(backend: B) -> ErrorKind {
+    let code = r#"
+        fn main(pub xx: u8, yy: u8) -> u8 {
+            return xx + yy;
+        }
+        "#;
+
+    let mut tast = TypeChecker::<B>::new();
+    typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    )
+    .unwrap();
+
+    let compiled_circuit = CircuitWriter::generate_circuit(tast, backend).unwrap();
+
+    let public_inputs = parse_inputs(r#"{"xx": "200"}"#).unwrap();
+    let private_inputs = parse_inputs(r#"{"yy": "100"}"#).unwrap();
+    compiled_circuit
+        .generate_witness(public_inputs, private_inputs)
+        .err()
+        .unwrap()
+        .kind
+}
+
+#[test]
+fn test_uint_overflow() {
+    assert!(matches!(
+        uint_overflow(KimchiVesta::new(false)),
+        ErrorKind::InvalidWitness(..)
+    ));
+    assert!(matches!(
+        uint_overflow(R1CS::<R1csBn254Field>::new()),
+        ErrorKind::InvalidWitness(..)
+    ));
+}
+
+/// Compiles an example that uses `std::ec` with kimchi, and runs it.
+fn ec_witness(code: &str, public_inputs: &str) -> Result<(), ErrorKind> {
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    )
+    .unwrap();
+
+    let compiled_circuit = CircuitWriter::generate_circuit(tast, KimchiVesta::new(false)).unwrap();
+
+    let public_inputs = parse_inputs(public_inputs).unwrap();
+    let private_inputs = parse_inputs(r#"{}"#).unwrap();
+    compiled_circuit
+        .generate_witness(public_inputs, private_inputs)
+        .map(|_| ())
+        .map_err(|err| err.kind)
+}
+
+#[test]
+fn test_point_at_infinity() {
+    // the point at infinity can't be represented
+    let code = r#"
+        use std::ec;
+
+        fn main(pub yy: Field) -> Field {
+            let point = ec::Point.new(-1, yy);
+            let res = point.add(point.neg());
+            return res.xx;
+        }
+        "#;
+
+    assert!(matches!(
+        ec_witness(code, r#"{"yy": "2"}"#),
+        Err(ErrorKind::PointAtInfinity)
+    ));
+}
+
+#[test]
+fn test_point_not_on_curve() {
+    // the builtins check their inputs, even if they are not created with `Point.new`
+    let code = r#"
+        use std::ec;
+
+        fn main(pub point: ec::Point) -> Field {
+            let res = point.double();
+            return res.xx;
+        }
+        "#;
+
+    // (-1, 2) is on the curve, but not (-1, 3)
+    let minus_one = "28948022309329048855892746252171976963363056481941560715954676764349967630336";
+    let on_curve = format!(r#"{{"point": {{"xx": "{minus_one}", "yy": "2"}}}}"#);
+    let off_curve = format!(r#"{{"point": {{"xx": "{minus_one}", "yy": "3"}}}}"#);
+    assert!(ec_witness(code, &on_curve).is_ok());
+    assert!(matches!(
+        ec_witness(code, &off_curve),
+        Err(ErrorKind::InvalidWitness(..))
+    ));
+}
+
+#[test]
+fn test_ec_on_r1cs() {
+    // the curve of `std::ec` is not defined over the field of R1CS
+    let code = r#"
+        use std::ec;
+
+        fn main(pub yy: Field) {
+            let point = ec::Point.new(-1, yy);
+        }
+        "#;
+
+    let mut tast = TypeChecker::<R1CS<R1csBn254Field>>::new();
+    let res = typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    );
+
+    assert!(matches!(
+        res.unwrap_err().kind,
+        ErrorKind::UnsupportedEcBackend
+    ));
+}
+
+/// Type checks a function that takes a field element `xx`, and returns the error.
+fn typecheck_field_error(body: &str) -> ErrorKind {
+    let code = format!("fn main(pub xx: Field, yy: Field) -> Field {{ {body} }}");
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    let res = typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code,
+        0,
+    );
+
+    res.unwrap_err().kind
+}
+
+#[test]
+fn test_unbounded_comparison() {
+    // field elements can't be compared, as they are not bounded,
+    // even when compared to constants or mixed with them
+    for body in [
+        "assert(xx < yy); return xx;",
+        "assert(3 < xx); return xx;",
+        "assert(3 >= xx); return xx;",
+        "assert(xx < 3); return xx;",
+        "assert((3 + xx) < 5); return xx;",
+        "let zz = 3 * xx; assert(zz <= 5); return xx;",
+    ] {
+        assert!(
+            matches!(
+                typecheck_field_error(body),
+                ErrorKind::UnboundedComparison(TyKind::Field)
+            ),
+            "{body}"
+        );
+    }
+}
+
+#[test]
+fn test_unbounded_bitwise_operation() {
+    // field elements can't be used in bitwise operations,
+    // even with constants or mixed with them
+    for body in [
+        "return xx & yy;",
+        "return 3 & xx;",
+        "return 3 << xx;",
+        "return xx >> 1;",
+        "return (3 + xx) | 5;",
+        "let zz = 3 - xx; return zz ^ 1;",
+    ] {
+        assert!(
+            matches!(
+                typecheck_field_error(body),
+                ErrorKind::UnboundedBitwiseOperation(TyKind::Field)
+            ),
+            "{body}"
+        );
+    }
+}
+
+#[test]
+fn test_invalid_shift() {
+    // a `u8` can't be shifted by 8 bits or more
+    let code = r#"
+        fn main(pub xx: u8) -> u8 {
+            return xx << 8;
+        }
+        "#;
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    )
+    .unwrap();
+
+    let res = CircuitWriter::generate_circuit(tast, KimchiVesta::new(false));
+
+    assert!(matches!(
+        res.err().unwrap().kind,
+        ErrorKind::InvalidShift(..)
+    ));
+}
+
+/// Compiles a decomposition in 3 bits and runs it with an input that doesn't fit.
+fn to_bits_overflow<B: Backend>(backend: B) -> ErrorKind {
+    let code = r#"
+        fn main(pub xx: Field) -> Field {
+            let bits = to_bits(xx, 3);
+            return from_bits(bits);
+        }
+        "#;
+
+    let mut tast = TypeChecker::<B>::new();
+    typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    )
+    .unwrap();
+
+    let compiled_circuit = CircuitWriter::generate_circuit(tast, backend).unwrap();
+
+    let public_inputs = parse_inputs(r#"{"xx": "8"}"#).unwrap();
+    compiled_circuit
+        .generate_witness(public_inputs, parse_inputs("{}").unwrap())
+        .err()
+        .unwrap()
+        .kind
+}
+
+#[test]
+fn test_to_bits_overflow() {
+    assert!(matches!(
+        to_bits_overflow(KimchiVesta::new(false)),
+        ErrorKind::InvalidWitness(..)
+    ));
+    assert!(matches!(
+        to_bits_overflow(R1CS::<R1csBn254Field>::new()),
+        ErrorKind::InvalidWitness(..)
+    ));
+}
+
+/// Compiles a dynamic access into an array of 2 elements and runs it with an out-of-range index.
+fn dynamic_index_out_of_bounds<B: Backend>(backend: B) -> ErrorKind {
+    let code = r#"
+        fn main(pub arr: [Field; 2], idx: Field) -> Field {
+            return arr[idx];
+        }
+        "#;
+
+    let mut tast = TypeChecker::<B>::new();
+    typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    )
+    .unwrap();
+
+    let compiled_circuit = CircuitWriter::generate_circuit(tast, backend).unwrap();
+
+    let public_inputs = parse_inputs(r#"{"arr": ["3", "7"]}"#).unwrap();
+    let private_inputs = parse_inputs(r#"{"idx": "2"}"#).unwrap();
+    compiled_circuit
+        .generate_witness(public_inputs, private_inputs)
+        .err()
+        .unwrap()
+        .kind
+}
+
+#[test]
+fn test_dynamic_index_out_of_bounds() {
+    assert!(matches!(
+        dynamic_index_out_of_bounds(KimchiVesta::new(false)),
+        ErrorKind::InvalidWitness(..)
+    ));
+    assert!(matches!(
+        dynamic_index_out_of_bounds(R1CS::<R1csBn254Field>::new()),
+        ErrorKind::InvalidWitness(..)
+    ));
+}
+
+#[test]
+fn test_dynamic_index_assignment() {
+    // array elements can't be written at an index only known at proving time
+    let code = r#"
+        fn main(pub idx: Field) {
+            let mut arr = [1, 2];
+            arr[idx] = 3;
+        }
+        "#;
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    let res = typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    );
+
+    assert!(matches!(
+        res.unwrap_err().kind,
+        ErrorKind::DynamicIndexAssignment
+    ));
+}
+
+#[test]
+fn test_undefined_generic_size() {
+    // `LEN` is not a generic size of the function
+    let code = r#"
+        fn sum(arr: [Field; 3]) -> Field {
+            let mut total = 0;
+            for ii in 0..LEN {
+                total = total + arr[ii];
+            }
+            return total;
+        }
+
+        fn main(pub xx: [Field; 3]) -> Field {
+            return sum(xx);
+        }
+        "#;
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    let res = typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    );
+
+    assert!(matches!(
+        res.unwrap_err().kind,
+        ErrorKind::UndefinedGenericSize(..)
+    ));
+}
+
+#[test]
+fn test_const_expr_size_mismatch() {
+    // the array should have `len * 2` elements
+    let code = r#"
+        const size = 2;
+
+        fn first(const len: Field, arr: [Field; len * 2]) -> Field {
+            return arr[0];
+        }
+
+        fn main(pub xx: [Field; size + 1]) -> Field {
+            return first(size, xx);
+        }
+        "#;
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    let res = typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    );
+
+    assert!(matches!(
+        res.unwrap_err().kind,
+        ErrorKind::ArgumentTypeMismatch(..)
+    ));
+}
+
+#[test]
+fn test_iterate_over_field() {
+    // only arrays can be iterated over
+    let code = r#"
+        fn main(pub xx: Field) -> Field {
+            let mut total = 0;
+            for elem in xx {
+                total = total + elem;
+            }
+            return total;
+        }
+        "#;
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    let res = typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    );
+
+    assert!(matches!(
+        res.unwrap_err().kind,
+        ErrorKind::InvalidIterator(..)
+    ));
+}
+
+#[test]
+fn test_compound_assignment_to_immutable() {
+    // `+=` requires a mutable variable, like `=`
+    let code = r#"
+        fn main(pub xx: Field) -> Field {
+            let res = xx;
+            res += 1;
+            return res;
+        }
+        "#;
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    let res = typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    );
+
+    assert!(matches!(
+        res.unwrap_err().kind,
+        ErrorKind::AssignmentToImmutableVariable
+    ));
+}
+
+#[test]
+fn test_tuple_destructuring_mismatch() {
+    // the tuple has two elements, not three
+    let code = r#"
+        fn main(pub pair: (Field, Field)) -> Field {
+            let (aa, bb, cc) = pair;
+            return (aa + bb) + cc;
+        }
+        "#;
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    let res = typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    );
+
+    assert!(matches!(
+        res.unwrap_err().kind,
+        ErrorKind::TupleDestructuringMismatch(..)
+    ));
+}
+
+#[test]
+fn test_non_exhaustive_match() {
+    // the `Rect` variant is not covered
+    let code = r#"
+        enum Shape {
+            Square(Field),
+            Rect(Field, Field),
+        }
+
+        fn main(pub xx: Field) -> Field {
+            let shape = Shape::Square(xx);
+            return match shape {
+                Shape::Square(side) => side,
+            };
+        }
+        "#;
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    let res = typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    );
+
+    assert!(matches!(
+        res.unwrap_err().kind,
+        ErrorKind::NonExhaustiveMatch(..)
+    ));
+}
+
+#[test]
+fn test_unreachable_match_arm() {
+    // the wildcard already covers every variant
+    let code = r#"
+        enum Shape {
+            Square(Field),
+            Rect(Field, Field),
+        }
+
+        fn main(pub xx: Field) -> Field {
+            let shape = Shape::Square(xx);
+            return match shape {
+                _ => 0,
+                Shape::Square(side) => side,
+            };
+        }
+        "#;
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    let res = typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    );
+
+    assert!(matches!(
+        res.unwrap_err().kind,
+        ErrorKind::UnreachableMatchArm
+    ));
+}
+
+#[test]
+fn test_return_in_if_statement() {
+    // early returns can't be made conditional
+    let code = r#"
+        fn main(pub xx: Field, flag: Bool) -> Field {
+            if flag {
+                return xx;
+            }
+            return xx + 1;
+        }
+        "#;
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    let res = typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    );
+
+    assert!(matches!(
+        res.unwrap_err().kind,
+        ErrorKind::ReturnInIfStatement
+    ));
+}
+
+#[test]
+fn test_overflow_check_in_if_statement() {
+    // the overflow check would be enforced even if the block is not taken
+    let code = r#"
+        fn main(pub xx: u8, flag: Bool) -> u8 {
+            let mut yy = xx;
+            if flag {
+                yy = xx + 1;
+            }
+            return yy;
+        }
+        "#;
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    let res = typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    );
+
+    assert!(matches!(
+        res.unwrap_err().kind,
+        ErrorKind::UnconditionalConstraints(..)
+    ));
+}
+
+#[test]
+fn test_division_in_if_statement() {
+    // the divisor would have to be non-zero even if the block is not taken
+    let code = r#"
+        fn main(pub xx: Field, yy: Field, flag: Bool) -> Field {
+            let mut res = xx;
+            if flag {
+                res = xx / yy;
+            }
+            return res;
+        }
+        "#;
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    let res = typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    );
+
+    assert!(matches!(
+        res.unwrap_err().kind,
+        ErrorKind::UnconditionalConstraints(..)
+    ));
+}
+
+#[test]
+fn test_dynamic_index_in_if_statement() {
+    // the index would have to be in bounds even if the block is not taken
+    let code = r#"
+        fn main(pub arr: [Field; 2], idx: Field, flag: Bool) -> Field {
+            let mut res = arr[0];
+            if flag {
+                res = arr[idx];
+            }
+            return res;
+        }
+        "#;
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    let res = typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    );
+
+    assert!(matches!(
+        res.unwrap_err().kind,
+        ErrorKind::UnconditionalConstraints(..)
+    ));
+}
+
+#[test]
+fn test_call_in_if_statement() {
+    // functions are compiled on their own, so their constraints can't be made conditional
+    let code = r#"
+        fn double(xx: Field) -> Field {
+            return xx + xx;
+        }
+
+        fn main(pub xx: Field, flag: Bool) -> Field {
+            let mut res = xx;
+            if flag {
+                res = double(xx);
+            }
+            return res;
+        }
+        "#;
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    let res = typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    );
+
+    assert!(matches!(
+        res.unwrap_err().kind,
+        ErrorKind::CallInIfStatement
+    ));
+}
+
+#[test]
+fn test_unused_hint_output() {
+    // the output of a hint is not constrained, it must be checked
+    let code = r#"
+        hint fn inverse(xx: Field) -> Field {
+            return 1 / xx;
+        }
+
+        fn main(pub xx: Field) {
+            let inv = inverse(xx);
+            assert_eq(xx, 2);
+        }
+        "#;
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    let res = typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    );
+
+    assert!(matches!(
+        res.unwrap_err().kind,
+        ErrorKind::UnusedHintOutput(..)
+    ));
+}
+
+#[test]
+fn test_returned_hint_output() {
+    // returning the output of a hint does not check it
+    let code = r#"
+        hint fn inverse(xx: Field) -> Field {
+            return 1 / xx;
+        }
+
+        fn main(pub xx: Field) -> Field {
+            let inv = inverse(xx);
+            return inv;
+        }
+        "#;
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    let res = typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    );
+
+    assert!(matches!(
+        res.unwrap_err().kind,
+        ErrorKind::UnusedHintOutput(..)
+    ));
+}
+
+#[test]
+fn test_hint_call_outside_let() {
+    // the output of a hint can only be tracked if it is stored in a variable
+    let code = r#"
+        hint fn inverse(xx: Field) -> Field {
+            return 1 / xx;
+        }
+
+        fn main(pub xx: Field) {
+            assert_eq(inverse(xx) * xx, 1);
+        }
+        "#;
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    let res = typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    );
+
+    assert!(matches!(
+        res.unwrap_err().kind,
+        ErrorKind::HintCallOutsideLet
+    ));
+}
+
+/// Compiles a call to a hint returning a value of the given type,
+/// and generates the witness as a dishonest prover would,
+/// replacing the output of the hint with the given value.
+fn dishonest_hint_output<B: Backend>(backend: B, typ: &str, output: u64) -> Result<(), ErrorKind> {
+    // (the assertion holds whatever the output of the hint)
+    let code = format!(
+        r#"
+        hint fn identity(xx: {typ}) -> {typ} {{
+            return xx;
+        }}
+
+        fn main(pub xx: {typ}) {{
+            let res = identity(xx);
+            assert(res == res);
+        }}
+        "#
+    );
+
+    let mut tast = TypeChecker::<B>::new();
+    typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code,
+        0,
+    )
+    .unwrap();
+
+    let compiled_circuit = CircuitWriter::generate_circuit(tast, backend).unwrap();
+
+    let mut env = WitnessEnv::default();
+    env.add_value("xx".to_string(), vec![B::Field::one()]);
+    env.hint_outputs.insert(0, vec![B::Field::from(output)]);
+    compiled_circuit
+        .circuit
+        .generate_witness(&mut env)
+        .map(|_| ())
+        .map_err(|err| err.kind)
+}
+
+#[test]
+fn test_dishonest_bool_hint_output() {
+    assert!(dishonest_hint_output(R1CS::<R1csBn254Field>::new(), "Bool", 1).is_ok());
+    assert!(matches!(
+        dishonest_hint_output(R1CS::<R1csBn254Field>::new(), "Bool", 2),
+        Err(ErrorKind::InvalidWitness(..))
+    ));
+    assert!(matches!(
+        dishonest_hint_output(KimchiVesta::new(false), "Bool", 2),
+        Err(ErrorKind::InvalidWitness(..))
+    ));
+}
+
+#[test]
+fn test_dishonest_uint_hint_output() {
+    assert!(dishonest_hint_output(R1CS::<R1csBn254Field>::new(), "u8", 255).is_ok());
+    assert!(matches!(
+        dishonest_hint_output(R1CS::<R1csBn254Field>::new(), "u8", 256),
+        Err(ErrorKind::InvalidWitness(..))
+    ));
+    assert!(matches!(
+        dishonest_hint_output(KimchiVesta::new(false), "u8", 256),
+        Err(ErrorKind::InvalidWitness(..))
+    ));
+}
+
+#[test]
+fn test_hint_without_return_type() {
+    // a hint is only useful for the value it computes
+    let code = r#"
+        hint fn check(xx: Field) {
+            assert_eq(xx, 2);
+        }
+        "#;
+
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    let res = typecheck_next_file_inner(
+        &mut tast,
+        None,
+        &mut Sources::new(),
+        "example.no".to_string(),
+        code.to_string(),
+        0,
+    );
+
+    assert!(matches!(
+        res.unwrap_err().kind,
+        ErrorKind::InvalidFunctionSignature(..)
+    ));
+}
@@ -0,0 +1,288 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::Span,
+    error::{ErrorKind, Result},
+    lexer::{Token, TokenKind, Tokens},
+    syntax::is_type,
+};
+
+use super::{
+    types::{Ident, ModulePath, Ty},
+    CustomType, Expr, ParserCtx,
+};
+
+//~
+//~ ## Enums
+//~
+//~ Backus–Naur Form (BNF) grammar:
+//~
+//~ enum_def ::= "enum" type_name "{" variant { "," variant } [ "," ] "}"
+//~ variant ::= type_name [ "(" type { "," type } ")" ]
+//~ match ::= "match" expr "{" match_arm { "," match_arm } [ "," ] "}"
+//~ match_arm ::= pattern "=>" expr
+//~ pattern ::= "_" | [ ident "::" ] type_name "::" type_name [ "(" binding { "," binding } ")" ]
+//~ binding ::= ident | "_"
+//~
+
+#[derive(Debug)]
+pub struct EnumDef {
+    pub module: ModulePath, // name resolution
+    pub name: CustomType,
+    /// The variants of the enum, and the types of the values they hold.
+    pub variants: Vec<(Ident, Vec<Ty>)>,
+    pub span: Span,
+}
+
+impl EnumDef {
+    pub fn parse(ctx: &mut ParserCtx, tokens: &mut Tokens) -> Result<Self> {
+        // enum Foo { Bar(Field), Baz }
+        //      ^^^
+        let name = CustomType::parse(ctx, tokens)?;
+        let mut span = name.span;
+
+        // enum Foo { Bar(Field), Baz }
+        //          ^
+        tokens.bump_expected(ctx, TokenKind::LeftCurlyBracket)?;
+
+        let mut variants = vec![];
+        loop {
+            // enum Foo { Bar(Field), Baz }
+            //                            ^
+            if let Some(Token {
+                kind: TokenKind::RightCurlyBracket,
+                span: end,
+            }) = tokens.peek()
+            {
+                tokens.bump(ctx);
+                span = span.merge_with(end);
+                break;
+            }
+
+            // enum Foo { Bar(Field), Baz }
+            //            ^^^
+            let variant = parse_variant_name(ctx, tokens)?;
+
+            // enum Foo { Bar(Field), Baz }
+            //               ^^^^^^^
+            let mut types = vec![];
+            if let Some(Token {
+                kind: TokenKind::LeftParen,
+                ..
+            }) = tokens.peek()
+            {
+                tokens.bump(ctx);
+                loop {
+                    types.push(Ty::parse(ctx, tokens)?);
+
+                    let token = tokens.bump_err(ctx, ErrorKind::InvalidEndOfLine)?;
+                    match token.kind {
+                        TokenKind::Comma => (),
+                        TokenKind::RightParen => break,
+                        _ => return Err(ctx.error(ErrorKind::InvalidEndOfLine, token.span)),
+                    }
+                }
+            }
+
+            variants.push((variant, types));
+
+            // enum Foo { Bar(Field), Baz }
+            //                      ^     ^
+            match tokens.peek() {
+                Some(Token {
+                    kind: TokenKind::Comma,
+                    ..
+                }) => {
+                    tokens.bump(ctx);
+                }
+                Some(Token {
+                    kind: TokenKind::RightCurlyBracket,
+                    ..
+                }) => (),
+                _ => {
+                    return Err(
+                        ctx.error(ErrorKind::ExpectedToken(TokenKind::Comma), ctx.last_span())
+                    )
+                }
+            }
+        }
+
+        if variants.is_empty() {
+            return Err(ctx.error(ErrorKind::EmptyEnum(name.name), span));
+        }
+
+        Ok(EnumDef {
+            module: ModulePath::Local,
+            name,
+            variants,
+            span,
+        })
+    }
+}
+
+/// Parses the name of a variant, which must start with an uppercase letter (like types).
+pub(crate) fn parse_variant_name(ctx: &mut ParserCtx, tokens: &mut Tokens) -> Result<Ident> {
+    let variant = Ident::parse(ctx, tokens)?;
+
+    if !is_type(&variant.value) {
+        return Err(ctx.error(ErrorKind::InvalidEnumVariant(variant.value), variant.span));
+    }
+
+    Ok(variant)
+}
+
+/// An arm of a `match` expression.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchArm {
+    pub pattern: MatchPattern,
+    pub expr: Expr,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum MatchPattern {
+    /// `Enum::Variant(bindings)`, where a binding is `None` if it's ignored with `_`.
+    Variant {
+        enum_name: CustomType,
+        variant: Ident,
+        bindings: Vec<Option<Ident>>,
+    },
+
+    /// `_`
+    Wildcard(Span),
+}
+
+impl MatchArm {
+    /// Parses the arms of a `match` expression, including the closing curly bracket.
+    pub fn parse_arms(ctx: &mut ParserCtx, tokens: &mut Tokens) -> Result<(Vec<Self>, Span)> {
+        let mut arms = vec![];
+
+        loop {
+            // match thing { Foo::Bar(xx) => xx, _ => 0 }
+            //                                           ^
+            if let Some(Token {
+                kind: TokenKind::RightCurlyBracket,
+                span,
+            }) = tokens.peek()
+            {
+                tokens.bump(ctx);
+                return Ok((arms, span));
+            }
+
+            // match thing { Foo::Bar(xx) => xx, _ => 0 }
+            //               ^^^^^^^^^^^^
+            let pattern = MatchPattern::parse(ctx, tokens)?;
+
+            // match thing { Foo::Bar(xx) => xx, _ => 0 }
+            //                            ^^
+            tokens.bump_expected(ctx, TokenKind::FatArrow)?;
+
+            // match thing { Foo::Bar(xx) => xx, _ => 0 }
+            //                               ^^
+            let expr = Expr::parse(ctx, tokens)?;
+            let span = pattern.span().merge_with(expr.span);
+
+            arms.push(MatchArm {
+                pattern,
+                expr,
+                span,
+            });
+
+            // match thing { Foo::Bar(xx) => xx, _ => 0 }
+            //                                 ^        ^
+            match tokens.peek() {
+                Some(Token {
+                    kind: TokenKind::Comma,
+                    ..
+                }) => {
+                    tokens.bump(ctx);
+                }
+                Some(Token {
+                    kind: TokenKind::RightCurlyBracket,
+                    ..
+                }) => (),
+                _ => {
+                    return Err(
+                        ctx.error(ErrorKind::ExpectedToken(TokenKind::Comma), ctx.last_span())
+                    )
+                }
+            }
+        }
+    }
+}
+
+impl MatchPattern {
+    pub fn parse(ctx: &mut ParserCtx, tokens: &mut Tokens) -> Result<Self> {
+        let token = tokens.bump_err(ctx, ErrorKind::InvalidMatchPattern)?;
+
+        // `_` or `Enum::Variant` or `module::Enum::Variant`
+        let first = match token.kind {
+            TokenKind::Underscore => return Ok(Self::Wildcard(token.span)),
+            TokenKind::Identifier(value) => Ident::new(value, token.span),
+            _ => return Err(ctx.error(ErrorKind::InvalidMatchPattern, token.span)),
+        };
+
+        tokens.bump_expected(ctx, TokenKind::DoubleColon)?;
+
+        let enum_name = if is_type(&first.value) {
+            CustomType {
+                module: ModulePath::Local,
+                name: first.value,
+                span: first.span,
+            }
+        } else {
+            let name = Ident::parse(ctx, tokens)?;
+            tokens.bump_expected(ctx, TokenKind::DoubleColon)?;
+            CustomType {
+                module: ModulePath::Alias(first),
+                name: name.value,
+                span: name.span,
+            }
+        };
+
+        let variant = parse_variant_name(ctx, tokens)?;
+
+        // Enum::Variant(aa, _)
+        //              ^^^^^^^
+        let mut bindings = vec![];
+        if let Some(Token {
+            kind: TokenKind::LeftParen,
+            ..
+        }) = tokens.peek()
+        {
+            tokens.bump(ctx);
+            loop {
+                let token = tokens.bump_err(ctx, ErrorKind::InvalidMatchPattern)?;
+                match token.kind {
+                    TokenKind::Identifier(value) => {
+                        bindings.push(Some(Ident::new(value, token.span)))
+                    }
+                    TokenKind::Underscore => bindings.push(None),
+                    _ => return Err(ctx.error(ErrorKind::InvalidMatchPattern, token.span)),
+                }
+
+                let token = tokens.bump_err(ctx, ErrorKind::InvalidMatchPattern)?;
+                match token.kind {
+                    TokenKind::Comma => (),
+                    TokenKind::RightParen => break,
+                    _ => return Err(ctx.error(ErrorKind::InvalidMatchPattern, token.span)),
+                }
+            }
+        }
+
+        Ok(Self::Variant {
+            enum_name,
+            variant,
+            bindings,
+        })
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            Self::Variant {
+                enum_name, variant, ..
+            } => enum_name.span.merge_with(variant.span),
+            Self::Wildcard(span) => *span,
+        }
+    }
+}
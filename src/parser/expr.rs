@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 
 use super::{
     types::{parse_fn_call_args, parse_type_declaration, Ident, ModulePath},
-    CustomType, ParserCtx,
+    CustomType, MatchArm, ParserCtx,
 };
 
 //~
@@ -26,6 +26,10 @@ use super::{
 //~     | ident
 //~     | fn_call
 //~     | array_access
+//~     | "(" expr "," expr { "," expr } ")"
+//~     | expr "." numeric
+//~     | [ ident "::" ] type_name "::" type_name [ "(" expr { "," expr } ")" ]
+//~     | match
 //~ bin_op ::= "+" | "-" | "/" | "*" | "=="
 //~ numeric ::= /[0-9]+/
 //~ ident ::= /[A-Za-z_][A-Za-z_0-9]*/
@@ -70,9 +74,21 @@ pub enum ExprKind {
     /// `let lhs = rhs`
     Assignment { lhs: Box<Expr>, rhs: Box<Expr> },
 
+    /// `lhs += rhs`, `lhs -= rhs` or `lhs *= rhs`
+    /// (the place of `lhs` is only evaluated once)
+    CompoundAssignment {
+        op: Op2,
+        lhs: Box<Expr>,
+        rhs: Box<Expr>,
+    },
+
     /// `lhs.rhs`
     FieldAccess { lhs: Box<Expr>, rhs: Ident },
 
+    /// An access to an element of a tuple, for example:
+    /// `tuple.0`
+    TupleAccess { tuple: Box<Expr>, idx: usize },
+
     /// `lhs <op> rhs`
     BinaryOp {
         op: Op2,
@@ -103,12 +119,28 @@ pub enum ExprKind {
     /// `[ ... ]`
     ArrayDeclaration(Vec<Expr>),
 
+    /// `( ..., ... )`
+    TupleDeclaration(Vec<Expr>),
+
     /// `name { fields }`
     CustomTypeDeclaration {
         custom: CustomType,
         fields: Vec<(Ident, Expr)>,
     },
 
+    /// `Enum::Variant` or `Enum::Variant(args)`
+    EnumVariant {
+        enum_name: CustomType,
+        variant: Ident,
+        args: Vec<Expr>,
+    },
+
+    /// `match matched { arms }`
+    Match {
+        matched: Box<Expr>,
+        arms: Vec<MatchArm>,
+    },
+
     /// `true` or `false`
     Bool(bool),
 
@@ -120,7 +152,7 @@ pub enum ExprKind {
     },
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum Op2 {
     Addition,
     Subtraction,
@@ -128,8 +160,17 @@ pub enum Op2 {
     Division,
     Equality,
     Inequality,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
     BoolAnd,
     BoolOr,
+    BitwiseAnd,
+    BitwiseOr,
+    BitwiseXor,
+    ShiftLeft,
+    ShiftRight,
 }
 
 impl Expr {
@@ -166,14 +207,46 @@ impl Expr {
                             _ => panic!("cannot qualify a non-identifier"),
                         };
 
-                        Expr::new(
-                            ctx,
-                            ExprKind::Variable {
+                        // Enum::Variant
+                        // ^^^^
+                        if is_type(&maybe_module.value) {
+                            let enum_name = CustomType {
+                                module: ModulePath::Local,
+                                name: maybe_module.value,
+                                span: maybe_module.span,
+                            };
+                            parse_enum_variant(ctx, tokens, enum_name, name)?
+                        }
+                        // mod::Enum::Variant
+                        //      ^^^^
+                        else if is_type(&name.value)
+                            && matches!(
+                                tokens.peek(),
+                                Some(Token {
+                                    kind: TokenKind::DoubleColon,
+                                    ..
+                                })
+                            )
+                        {
+                            tokens.bump(ctx); // ::
+
+                            let enum_name = CustomType {
                                 module: ModulePath::Alias(maybe_module),
-                                name,
-                            },
-                            span,
-                        )
+                                name: name.value,
+                                span: name.span,
+                            };
+                            let variant = Ident::parse(ctx, tokens)?;
+                            parse_enum_variant(ctx, tokens, enum_name, variant)?
+                        } else {
+                            Expr::new(
+                                ctx,
+                                ExprKind::Variable {
+                                    module: ModulePath::Alias(maybe_module),
+                                    name,
+                                },
+                                span,
+                            )
+                        }
                     }
 
                     // just an identifier
@@ -195,16 +268,47 @@ impl Expr {
                 Expr::new(ctx, ExprKind::Negated(Box::new(expr)), span)
             }
 
-            // parenthesis
+            // parenthesis or tuple
             TokenKind::LeftParen => {
                 let mut expr = Expr::parse(ctx, tokens)?;
-                tokens.bump_expected(ctx, TokenKind::RightParen)?;
 
-                if let ExprKind::BinaryOp { protected, .. } = &mut expr.kind {
-                    *protected = true;
-                }
+                // (expr, expr)
+                //      ^
+                if matches!(
+                    tokens.peek(),
+                    Some(Token {
+                        kind: TokenKind::Comma,
+                        ..
+                    })
+                ) {
+                    let mut items = vec![expr];
+
+                    let last_span = loop {
+                        // (expr, expr)
+                        //      ^     ^
+                        let token = tokens.bump_err(ctx, ErrorKind::InvalidEndOfLine)?;
+                        match token.kind {
+                            TokenKind::RightParen => break token.span,
+                            TokenKind::Comma => (),
+                            _ => return Err(ctx.error(ErrorKind::InvalidEndOfLine, token.span)),
+                        }
+
+                        // (expr, expr)
+                        //        ^^^^
+                        items.push(Expr::parse(ctx, tokens)?);
+                    };
 
-                expr
+                    let span = span.merge_with(last_span);
+                    Expr::new(ctx, ExprKind::TupleDeclaration(items), span)
+                } else {
+                    tokens.bump_expected(ctx, TokenKind::RightParen)?;
+
+                    if let ExprKind::BinaryOp { protected, .. } = &mut expr.kind {
+                        *protected = true;
+                    }
+
+                    expr
+                }
             }
 
             // true or false
@@ -275,6 +379,25 @@ impl Expr {
                 Expr::new(ctx, ExprKind::IfElse { cond, then_, else_ }, span)
             }
 
+            // `match expr { arms }`
+            TokenKind::Keyword(Keyword::Match) => {
+                // match thing { arms }
+                //       ^^^^^
+                let matched = Box::new(Expr::parse(ctx, tokens)?);
+
+                // match thing { arms }
+                //             ^
+                tokens.bump_expected(ctx, TokenKind::LeftCurlyBracket)?;
+
+                // match thing { arms }
+                //               ^^^^^^
+                let (arms, end) = MatchArm::parse_arms(ctx, tokens)?;
+
+                let span = span.merge_with(end);
+
+                Expr::new(ctx, ExprKind::Match { matched, arms }, span)
+            }
+
             // negation (logical NOT)
             TokenKind::Exclamation => {
                 let expr = Expr::parse(ctx, tokens)?;
@@ -385,6 +508,49 @@ impl Expr {
                 )
             }
 
+            // compound assignment
+            Some(Token {
+                kind: TokenKind::PlusEqual | TokenKind::MinusEqual | TokenKind::StarEqual,
+                span,
+            }) => {
+                // lhs += rhs
+                //     ^^
+                let op = match tokens.bump(ctx).unwrap().kind {
+                    TokenKind::PlusEqual => Op2::Addition,
+                    TokenKind::MinusEqual => Op2::Subtraction,
+                    TokenKind::StarEqual => Op2::Multiplication,
+                    _ => unreachable!(),
+                };
+
+                // sanitize
+                if !matches!(
+                    &self.kind,
+                    ExprKind::Variable { .. }
+                        | ExprKind::ArrayAccess { .. }
+                        | ExprKind::FieldAccess { .. },
+                ) {
+                    return Err(ctx.error(
+                        ErrorKind::InvalidAssignmentExpression,
+                        self.span.merge_with(span),
+                    ));
+                }
+
+                // lhs += rhs
+                //        ^^^
+                let rhs = Expr::parse(ctx, tokens)?;
+                let span = self.span.merge_with(rhs.span);
+
+                Expr::new(
+                    ctx,
+                    ExprKind::CompoundAssignment {
+                        op,
+                        lhs: Box::new(self),
+                        rhs: Box::new(rhs),
+                    },
+                    span,
+                )
+            }
+
             // binary operation
             Some(Token {
                 kind:
@@ -394,8 +560,17 @@ impl Expr {
                     | TokenKind::Slash
                     | TokenKind::DoubleEqual
                     | TokenKind::NotEqual
+                    | TokenKind::Less
+                    | TokenKind::LessEqual
+                    | TokenKind::Greater
+                    | TokenKind::GreaterEqual
                     | TokenKind::DoubleAmpersand
                     | TokenKind::DoublePipe
+                    | TokenKind::Ampersand
+                    | TokenKind::Pipe
+                    | TokenKind::Caret
+                    | TokenKind::DoubleLess
+                    | TokenKind::DoubleGreater
                     | TokenKind::Exclamation,
                 ..
             }) => {
@@ -408,8 +583,17 @@ impl Expr {
                     TokenKind::Slash => Op2::Division,
                     TokenKind::DoubleEqual => Op2::Equality,
                     TokenKind::NotEqual => Op2::Inequality,
+                    TokenKind::Less => Op2::LessThan,
+                    TokenKind::LessEqual => Op2::LessThanOrEqual,
+                    TokenKind::Greater => Op2::GreaterThan,
+                    TokenKind::GreaterEqual => Op2::GreaterThanOrEqual,
                     TokenKind::DoubleAmpersand => Op2::BoolAnd,
                     TokenKind::DoublePipe => Op2::BoolOr,
+                    TokenKind::Ampersand => Op2::BitwiseAnd,
+                    TokenKind::Pipe => Op2::BitwiseOr,
+                    TokenKind::Caret => Op2::BitwiseXor,
+                    TokenKind::DoubleLess => Op2::ShiftLeft,
+                    TokenKind::DoubleGreater => Op2::ShiftRight,
                     _ => unreachable!(),
                 };
 
@@ -480,7 +664,9 @@ impl Expr {
                 // sanity check
                 if !matches!(
                     self.kind,
-                    ExprKind::Variable { .. } | ExprKind::FieldAccess { .. }
+                    ExprKind::Variable { .. }
+                        | ExprKind::FieldAccess { .. }
+                        | ExprKind::TupleAccess { .. }
                 ) {
                     panic!("an array access can only follow a variable");
                 }
@@ -547,11 +733,38 @@ impl Expr {
                     ExprKind::FieldAccess { .. }
                         | ExprKind::Variable { .. }
                         | ExprKind::ArrayAccess { .. }
+                        | ExprKind::TupleAccess { .. }
                 ) {
                     let span = self.span.merge_with(period.span);
                     return Err(ctx.error(ErrorKind::InvalidFieldAccessExpression, span));
                 }
 
+                // tuple.0
+                //       ^
+                if let Some(Token {
+                    kind: TokenKind::BigUInt(idx),
+                    span,
+                }) = tokens.peek()
+                {
+                    tokens.bump(ctx);
+
+                    let idx: usize = idx
+                        .try_into()
+                        .map_err(|_| ctx.error(ErrorKind::InvalidFieldAccessExpression, span))?;
+                    let span = self.span.merge_with(span);
+
+                    let expr = Expr::new(
+                        ctx,
+                        ExprKind::TupleAccess {
+                            tuple: Box::new(self),
+                            idx,
+                        },
+                        span,
+                    );
+
+                    return expr.parse_rhs(ctx, tokens);
+                }
+
                 // lhs.field
                 //     ^^^^^
                 let rhs = Ident::parse(ctx, tokens)?;
@@ -605,3 +818,41 @@ impl Expr {
         lhs.parse_rhs(ctx, tokens)
     }
 }
+
+/// Parses the arguments of an enum variant, if there are any.
+/// `Enum::Variant(args)`
+///               ^^^^^^
+fn parse_enum_variant(
+    ctx: &mut ParserCtx,
+    tokens: &mut Tokens,
+    enum_name: CustomType,
+    variant: Ident,
+) -> Result<Expr> {
+    if !is_type(&variant.value) {
+        return Err(ctx.error(ErrorKind::InvalidEnumVariant(variant.value), variant.span));
+    }
+
+    let mut span = enum_name.span.merge_with(variant.span);
+
+    let args = if let Some(Token {
+        kind: TokenKind::LeftParen,
+        ..
+    }) = tokens.peek()
+    {
+        let (args, end) = parse_fn_call_args(ctx, tokens)?;
+        span = span.merge_with(end);
+        args
+    } else {
+        vec![]
+    };
+
+    Ok(Expr::new(
+        ctx,
+        ExprKind::EnumVariant {
+            enum_name,
+            variant,
+            args,
+        },
+        span,
+    ))
+}
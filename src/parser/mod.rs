@@ -5,12 +5,15 @@ use crate::{
     lexer::{Keyword, Token, TokenKind, Tokens},
 };
 
+use self::types::FuncOrMethod;
 pub use self::types::{ConstDef, FunctionDef, Root, RootKind, UsePath};
 
+pub mod enums;
 pub mod expr;
 pub mod structs;
 pub mod types;
 
+pub use enums::{EnumDef, MatchArm, MatchPattern};
 pub use expr::{Expr, ExprKind, Op2};
 pub use structs::{CustomType, StructDef};
 
@@ -158,6 +161,45 @@ impl<B: Backend> AST<B> {
                     });
                 }
 
+                // `hint fn sqrt(xx: Field) -> Field { }`
+                TokenKind::Keyword(Keyword::Hint) => {
+                    function_observed = true;
+
+                    tokens.bump_expected(ctx, TokenKind::Keyword(Keyword::Fn))?;
+                    let mut func = FunctionDef::parse(ctx, &mut tokens)?;
+
+                    if func.is_main() {
+                        return Err(ctx.error(
+                            ErrorKind::InvalidFunctionSignature(
+                                "the main function can't be a hint",
+                            ),
+                            func.sig.name.span,
+                        ));
+                    }
+
+                    if matches!(func.sig.kind, FuncOrMethod::Method(_)) {
+                        return Err(ctx.error(
+                            ErrorKind::InvalidFunctionSignature("methods can't be hints"),
+                            func.sig.name.span,
+                        ));
+                    }
+
+                    if func.sig.return_type.is_none() {
+                        return Err(ctx.error(
+                            ErrorKind::InvalidFunctionSignature(
+                                "a hint function must return a value",
+                            ),
+                            func.sig.name.span,
+                        ));
+                    }
+
+                    func.is_hint = true;
+                    ast.push(Root {
+                        kind: RootKind::FunctionDef(func),
+                        span: token.span,
+                    });
+                }
+
                 // `struct Foo { a: Field, b: Field }`
                 TokenKind::Keyword(Keyword::Struct) => {
                     let s = StructDef::parse(ctx, &mut tokens)?;
@@ -167,6 +209,15 @@ impl<B: Backend> AST<B> {
                     });
                 }
 
+                // `enum Foo { Bar(Field), Baz }`
+                TokenKind::Keyword(Keyword::Enum) => {
+                    let e = EnumDef::parse(ctx, &mut tokens)?;
+                    ast.push(Root {
+                        kind: RootKind::EnumDef(e),
+                        span: token.span,
+                    });
+                }
+
                 // `// some comment`
                 TokenKind::Comment(comment) => {
                     ast.push(Root {
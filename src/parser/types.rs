@@ -1,5 +1,6 @@
 use educe::Educe;
 use std::{
+    collections::HashMap,
     fmt::Display,
     hash::{Hash, Hasher},
     str::FromStr,
@@ -9,7 +10,7 @@ use ark_ff::{Field, Zero};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    cli::packages::UserRepo,
+    cli::packages::{QualifiedModule, UserRepo, SELF_MODULE},
     constants::Span,
     error::{ErrorKind, Result},
     lexer::{Keyword, Token, TokenKind, Tokens},
@@ -17,7 +18,7 @@ use crate::{
     syntax::is_type,
 };
 
-use super::{CustomType, Expr, ExprKind, ParserCtx, StructDef};
+use super::{CustomType, EnumDef, Expr, ExprKind, Op2, ParserCtx, StructDef};
 
 pub fn parse_type_declaration(
     ctx: &mut ParserCtx,
@@ -145,10 +146,19 @@ pub fn parse_fn_call_args(ctx: &mut ParserCtx, tokens: &mut Tokens) -> Result<(V
 //~
 //~ type ::=
 //~     | /[A-Z] (A-Za-z0-9)*/
-//~     | "[" type ";" numeric "]"
+//~     | "u8" | "u16" | "u32" | "u64"
+//~     | "[" type ";" const_expr "]"
+//~     | "(" type "," type { "," type } ")"
 //~
+//~ const_expr ::= const_product { ("+" | "-") const_product }
+//~ const_product ::= const_atom { "*" const_atom }
+//~ const_atom ::= numeric | ident | "(" const_expr ")"
 //~ numeric ::= /[0-9]+/
 //~
+//~ where an `ident` in an array size is a constant of the module,
+//~ or a generic size of a function inferred at each call site (or given by a `const` argument of the function).
+//~ The same constant expressions are used for the bounds of `for` loops.
+//~
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Ty {
@@ -167,8 +177,9 @@ pub enum ModulePath {
     Alias(Ident),
 
     /// This is a type imported from another module,
-    /// fully-qualified (as `user::repo`) thanks to the name resolution pass of the compiler.
-    Absolute(UserRepo),
+    /// fully-qualified (as `user::repo`, or `self::module` for a module of the package)
+    /// thanks to the name resolution pass of the compiler.
+    Absolute(QualifiedModule),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -187,23 +198,139 @@ pub enum TyKind {
     /// An array of a fixed size.
     Array(Box<TyKind>, u32),
 
+    /// An array whose size depends on a generic size or a `const` argument of the function (e.g. `[Bool; LEN]`).
+    /// It is resolved to an [TyKind::Array] at each call site.
+    GenericSizedArray(Box<TyKind>, ConstExpr),
+
     /// A boolean (`true` or `false`).
     Bool,
-    // Tuple(Vec<TyKind>),
-    // Bool,
-    // U8,
-    // U16,
-    // U32,
-    // U64,
+
+    /// Unsigned integers, range-checked to fit in the given number of bits.
+    U8,
+    U16,
+    U32,
+    U64,
+
+    /// A tuple of values of (possibly) different types (e.g. `(Field, Bool)`).
+    Tuple(Vec<TyKind>),
 }
 
 impl TyKind {
+    /// Returns the number of bits of an unsigned integer type,
+    /// or `None` if the type is not an unsigned integer.
+    pub fn uint_bits(&self) -> Option<usize> {
+        match self {
+            TyKind::U8 => Some(8),
+            TyKind::U16 => Some(16),
+            TyKind::U32 => Some(32),
+            TyKind::U64 => Some(64),
+            _ => None,
+        }
+    }
+
+    /// Returns the unsigned integer type with the given name (`u8`, `u16`, `u32` or `u64`).
+    pub fn uint_from_name(name: &str) -> Option<TyKind> {
+        match name {
+            "u8" => Some(TyKind::U8),
+            "u16" => Some(TyKind::U16),
+            "u32" => Some(TyKind::U32),
+            "u64" => Some(TyKind::U64),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the type contains an array with a generic size.
+    pub fn is_generic(&self) -> bool {
+        match self {
+            TyKind::GenericSizedArray(..) => true,
+            TyKind::Array(ty, _) => ty.is_generic(),
+            TyKind::Tuple(types) => types.iter().any(TyKind::is_generic),
+            _ => false,
+        }
+    }
+
+    /// Returns the names of the generic sizes found in the type.
+    pub fn generic_names(&self) -> Vec<String> {
+        match self {
+            TyKind::GenericSizedArray(ty, size) => {
+                let mut names = ty.generic_names();
+                names.extend(size.names());
+                names
+            }
+            TyKind::Array(ty, _) => ty.generic_names(),
+            TyKind::Tuple(types) => types.iter().flat_map(TyKind::generic_names).collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Binds the generic sizes found in `self` to the sizes of the `observed` type.
+    /// The observed sizes might themselves be generic (in the body of a generic function).
+    /// Sizes that are already bound are left untouched.
+    pub fn bind_generics(&self, observed: &TyKind, generics: &mut HashMap<String, ConstExpr>) {
+        let (ty, size, observed_ty, observed_size) = match (self, observed) {
+            (TyKind::GenericSizedArray(ty, size), TyKind::Array(observed_ty, observed_size)) => {
+                (ty, size, observed_ty, ConstExpr::Literal(*observed_size))
+            }
+            (
+                TyKind::GenericSizedArray(ty, size),
+                TyKind::GenericSizedArray(observed_ty, observed_size),
+            ) => (ty, size, observed_ty, observed_size.clone()),
+            (
+                TyKind::Array(ty, _),
+                TyKind::Array(observed_ty, _) | TyKind::GenericSizedArray(observed_ty, _),
+            ) => return ty.bind_generics(observed_ty, generics),
+            (TyKind::Tuple(types), TyKind::Tuple(observed_types)) => {
+                for (ty, observed_ty) in types.iter().zip(observed_types) {
+                    ty.bind_generics(observed_ty, generics);
+                }
+                return;
+            }
+            _ => return,
+        };
+
+        // only a size given by a single name can be inferred
+        if let ConstExpr::Name(name) = size {
+            generics.entry(name.clone()).or_insert(observed_size);
+        }
+        ty.bind_generics(observed_ty, generics);
+    }
+
+    /// Replaces the generic sizes of arrays with what they are bound to.
+    /// Arrays whose size can then be evaluated become [TyKind::Array]s.
+    pub fn resolve_generics(&self, generics: &HashMap<String, ConstExpr>) -> TyKind {
+        self.resolve_sizes(&|name| generics.get(name).cloned())
+    }
+
+    /// Same as [Self::resolve_generics], but with the values of the names given by `lookup`.
+    pub fn resolve_sizes(&self, lookup: &impl Fn(&str) -> Option<ConstExpr>) -> TyKind {
+        match self {
+            TyKind::GenericSizedArray(ty, size) => {
+                let ty = Box::new(ty.resolve_sizes(lookup));
+                match size.substitute(lookup) {
+                    ConstExpr::Literal(size) => TyKind::Array(ty, size),
+                    size => TyKind::GenericSizedArray(ty, size),
+                }
+            }
+            TyKind::Array(ty, size) => TyKind::Array(Box::new(ty.resolve_sizes(lookup)), *size),
+            TyKind::Tuple(types) => {
+                TyKind::Tuple(types.iter().map(|ty| ty.resolve_sizes(lookup)).collect())
+            }
+            ty => ty.clone(),
+        }
+    }
+
     pub fn match_expected(&self, expected: &TyKind) -> bool {
         match (self, expected) {
             (TyKind::BigInt, TyKind::Field) => true,
+            (TyKind::BigInt, expected) if expected.uint_bits().is_some() => true,
+            // unsigned integers can be used as field elements
+            (uint, TyKind::Field) if uint.uint_bits().is_some() => true,
             (TyKind::Array(lhs, lhs_size), TyKind::Array(rhs, rhs_size)) => {
                 lhs_size == rhs_size && lhs.match_expected(rhs)
             }
+            (TyKind::Tuple(lhs), TyKind::Tuple(rhs)) => {
+                lhs.len() == rhs.len() && lhs.iter().zip(rhs).all(|(l, r)| l.match_expected(r))
+            }
             (
                 TyKind::Custom { module, name },
                 TyKind::Custom {
@@ -219,9 +346,13 @@ impl TyKind {
     pub fn same_as(&self, other: &TyKind) -> bool {
         match (self, other) {
             (TyKind::BigInt, TyKind::Field) | (TyKind::Field, TyKind::BigInt) => true,
+            (TyKind::BigInt, uint) | (uint, TyKind::BigInt) if uint.uint_bits().is_some() => true,
             (TyKind::Array(lhs, lhs_size), TyKind::Array(rhs, rhs_size)) => {
                 lhs_size == rhs_size && lhs.match_expected(rhs)
             }
+            (TyKind::Tuple(lhs), TyKind::Tuple(rhs)) => {
+                lhs.len() == rhs.len() && lhs.iter().zip(rhs).all(|(l, r)| l.same_as(r))
+            }
             (
                 TyKind::Custom { module, name },
                 TyKind::Custom {
@@ -239,13 +370,26 @@ impl Display for TyKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             TyKind::Custom { module, name } => match module {
-                ModulePath::Absolute(user_repo) => write!(
+                ModulePath::Absolute(QualifiedModule::Package(user_repo)) => write!(
                     f,
                     "a `{module}::{submodule}::{name}` struct",
                     name = name,
                     module = user_repo.user,
                     submodule = user_repo.repo
                 ),
+                ModulePath::Absolute(QualifiedModule::Submodule {
+                    package: Some(user_repo),
+                    name: module,
+                }) => write!(
+                    f,
+                    "a `{user}::{repo}::{module}::{name}` struct",
+                    user = user_repo.user,
+                    repo = user_repo.repo,
+                ),
+                ModulePath::Absolute(QualifiedModule::Submodule {
+                    package: None,
+                    name: module,
+                }) => write!(f, "a `{SELF_MODULE}::{module}::{name}` struct"),
                 ModulePath::Alias(module) => write!(
                     f,
                     "a `{module}::{name}` struct",
@@ -257,7 +401,16 @@ impl Display for TyKind {
             TyKind::Field => write!(f, "Field"),
             TyKind::BigInt => write!(f, "BigInt"),
             TyKind::Array(ty, size) => write!(f, "[{}; {}]", ty, size),
+            TyKind::GenericSizedArray(ty, size) => write!(f, "[{}; {}]", ty, size),
             TyKind::Bool => write!(f, "Bool"),
+            TyKind::U8 => write!(f, "u8"),
+            TyKind::U16 => write!(f, "u16"),
+            TyKind::U32 => write!(f, "u32"),
+            TyKind::U64 => write!(f, "u64"),
+            TyKind::Tuple(types) => {
+                let types: Vec<_> = types.iter().map(|ty| ty.to_string()).collect();
+                write!(f, "({})", types.join(", "))
+            }
         }
     }
 }
@@ -281,6 +434,15 @@ impl Ty {
         let token = tokens.bump_err(ctx, ErrorKind::MissingType)?;
 
         match token.kind {
+            // u8, u16, u32, u64
+            // ^^
+            TokenKind::Identifier(ty_name) if TyKind::uint_from_name(&ty_name).is_some() => {
+                Ok(Self {
+                    kind: TyKind::uint_from_name(&ty_name).unwrap(),
+                    span: token.span,
+                })
+            }
+
             // module::Type or Type
             // ^^^^^^^^^^^^    ^^^^
             TokenKind::Identifier(ty_name) => {
@@ -318,6 +480,39 @@ impl Ty {
                 })
             }
 
+            // tuple
+            // (type, type)
+            // ^
+            TokenKind::LeftParen => {
+                let mut types = vec![];
+
+                let right_paren = loop {
+                    // (type, type)
+                    //  ^^^^  ^^^^
+                    types.push(Ty::parse(ctx, tokens)?.kind);
+
+                    // (type, type)
+                    //      ^     ^
+                    let token = tokens.bump_err(ctx, ErrorKind::InvalidTupleType)?;
+                    match token.kind {
+                        TokenKind::Comma => (),
+                        TokenKind::RightParen => break token,
+                        _ => return Err(ctx.error(ErrorKind::InvalidTupleType, token.span)),
+                    }
+                };
+
+                // a tuple has at least two elements
+                let span = token.span.merge_with(right_paren.span);
+                if types.len() < 2 {
+                    return Err(ctx.error(ErrorKind::InvalidTupleType, span));
+                }
+
+                Ok(Self {
+                    kind: TyKind::Tuple(types),
+                    span,
+                })
+            }
+
             // array
             // [type; size]
             // ^
@@ -334,20 +529,7 @@ impl Ty {
 
                 // [type; size]
                 //         ^
-                let siz = tokens.bump_err(ctx, ErrorKind::InvalidToken)?;
-                let siz: u32 = match siz.kind {
-                    TokenKind::BigUInt(b) => b
-                        .try_into()
-                        .map_err(|_e| ctx.error(ErrorKind::InvalidArraySize, siz.span))?,
-                    _ => {
-                        return Err(ctx.error(
-                            ErrorKind::ExpectedToken(TokenKind::BigUInt(
-                                num_bigint::BigUint::zero(),
-                            )),
-                            siz.span,
-                        ));
-                    }
-                };
+                let (siz, _) = ConstExpr::parse(ctx, tokens)?;
 
                 // [type; size]
                 //            ^
@@ -355,6 +537,17 @@ impl Ty {
 
                 let span = span.merge_with(right_paren.span);
 
+                // sizes that are not numbers are evaluated later
+                let siz = match siz {
+                    ConstExpr::Literal(siz) => siz,
+                    siz => {
+                        return Ok(Ty {
+                            kind: TyKind::GenericSizedArray(Box::new(ty.kind), siz),
+                            span,
+                        })
+                    }
+                };
+
                 Ok(Ty {
                     kind: TyKind::Array(Box::new(ty.kind), siz),
                     span,
@@ -375,6 +568,9 @@ impl Ty {
 //~ fn_sig ::= ident "(" param { "," param } ")" [ return_val ]
 //~ return_val ::= "->" type
 //~ param ::= { "pub" } ident ":" type
+//~ hint_fn ::= "hint" "fn" fn_sig block
+//~
+//~ where a hint function can't be a method or the main function, and must return a value.
 //~
 
 impl FnSig {
@@ -462,6 +658,10 @@ pub struct FunctionDef {
     pub sig: FnSig,
     pub body: Vec<Stmt>,
     pub span: Span,
+
+    /// Whether it is a hint function (`hint fn`),
+    /// which is computed outside of the circuit when generating the witness.
+    pub is_hint: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -804,7 +1004,12 @@ impl FunctionDef {
             ));
         }
 
-        let func = Self { sig, body, span };
+        let func = Self {
+            sig,
+            body,
+            span,
+            is_hint: false,
+        };
 
         Ok(func)
     }
@@ -858,14 +1063,205 @@ pub fn is_valid_fn_type(name: &str) -> bool {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Range {
-    pub start: u32,
-    pub end: u32,
+    pub start: ConstExpr,
+    pub end: ConstExpr,
     pub span: Span,
 }
 
-impl Range {
-    pub fn range(&self) -> std::ops::Range<u32> {
-        self.start..self.end
+/// An expression evaluated at compile time, used for range bounds and array sizes.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum ConstExpr {
+    /// A numeric literal (e.g. `3`).
+    Literal(u32),
+
+    /// A constant of the module, or a generic array size or a `const` argument of the function (e.g. `LEN`).
+    Name(String),
+
+    /// An addition, subtraction or multiplication (e.g. `LEN * 2`).
+    BinaryOp {
+        op: Op2,
+        lhs: Box<ConstExpr>,
+        rhs: Box<ConstExpr>,
+    },
+}
+
+impl ConstExpr {
+    /// Parses a sum of products of numbers, names, and parenthesized constant expressions.
+    pub fn parse(ctx: &mut ParserCtx, tokens: &mut Tokens) -> Result<(Self, Span)> {
+        let (mut res, mut span) = Self::parse_product(ctx, tokens)?;
+
+        loop {
+            let op = match tokens.peek() {
+                Some(Token {
+                    kind: TokenKind::Plus,
+                    ..
+                }) => Op2::Addition,
+                Some(Token {
+                    kind: TokenKind::Minus,
+                    ..
+                }) => Op2::Subtraction,
+                _ => return Ok((res, span)),
+            };
+            tokens.bump(ctx);
+
+            let (rhs, rhs_span) = Self::parse_product(ctx, tokens)?;
+            res = ConstExpr::BinaryOp {
+                op,
+                lhs: Box::new(res),
+                rhs: Box::new(rhs),
+            };
+            span = span.merge_with(rhs_span);
+        }
+    }
+
+    fn parse_product(ctx: &mut ParserCtx, tokens: &mut Tokens) -> Result<(Self, Span)> {
+        let (mut res, mut span) = Self::parse_atom(ctx, tokens)?;
+
+        while let Some(Token {
+            kind: TokenKind::Star,
+            ..
+        }) = tokens.peek()
+        {
+            tokens.bump(ctx);
+
+            let (rhs, rhs_span) = Self::parse_atom(ctx, tokens)?;
+            res = ConstExpr::BinaryOp {
+                op: Op2::Multiplication,
+                lhs: Box::new(res),
+                rhs: Box::new(rhs),
+            };
+            span = span.merge_with(rhs_span);
+        }
+
+        Ok((res, span))
+    }
+
+    fn parse_atom(ctx: &mut ParserCtx, tokens: &mut Tokens) -> Result<(Self, Span)> {
+        match tokens.bump(ctx) {
+            Some(Token {
+                kind: TokenKind::BigUInt(n),
+                span,
+            }) => {
+                let n: u32 = n
+                    .try_into()
+                    .map_err(|_e| ctx.error(ErrorKind::InvalidConstExpr, span))?;
+                Ok((ConstExpr::Literal(n), span))
+            }
+            Some(Token {
+                kind: TokenKind::Identifier(name),
+                span,
+            }) => Ok((ConstExpr::Name(name), span)),
+            Some(Token {
+                kind: TokenKind::LeftParen,
+                span,
+            }) => {
+                let (res, _) = Self::parse(ctx, tokens)?;
+                let right_paren = tokens.bump_expected(ctx, TokenKind::RightParen)?;
+                Ok((res, span.merge_with(right_paren.span)))
+            }
+            _ => Err(ctx.error(
+                ErrorKind::ExpectedToken(TokenKind::BigUInt(num_bigint::BigUint::zero())),
+                ctx.last_span(),
+            )),
+        }
+    }
+
+    /// Converts an expression parsed with [Expr::parse] (e.g. the start of a range).
+    pub fn from_expr(ctx: &mut ParserCtx, expr: &Expr) -> Result<Self> {
+        match &expr.kind {
+            ExprKind::BigUInt(n) => {
+                let n: u32 = n
+                    .try_into()
+                    .map_err(|_e| ctx.error(ErrorKind::InvalidConstExpr, expr.span))?;
+                Ok(ConstExpr::Literal(n))
+            }
+            ExprKind::Variable {
+                module: ModulePath::Local,
+                name,
+            } => Ok(ConstExpr::Name(name.value.clone())),
+            ExprKind::BinaryOp { op, lhs, rhs, .. }
+                if matches!(op, Op2::Addition | Op2::Subtraction | Op2::Multiplication) =>
+            {
+                Ok(ConstExpr::BinaryOp {
+                    op: op.clone(),
+                    lhs: Box::new(Self::from_expr(ctx, lhs)?),
+                    rhs: Box::new(Self::from_expr(ctx, rhs)?),
+                })
+            }
+            _ => Err(ctx.error(ErrorKind::InvalidConstExpr, expr.span)),
+        }
+    }
+
+    /// Returns the value of the expression, if it has been evaluated.
+    pub fn value(&self) -> Option<u32> {
+        match self {
+            ConstExpr::Literal(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the names found in the expression.
+    pub fn names(&self) -> Vec<String> {
+        match self {
+            ConstExpr::Literal(_) => vec![],
+            ConstExpr::Name(name) => vec![name.clone()],
+            ConstExpr::BinaryOp { lhs, rhs, .. } => {
+                let mut names = lhs.names();
+                names.extend(rhs.names());
+                names
+            }
+        }
+    }
+
+    /// Replaces the names with what `lookup` returns for them (if anything),
+    /// and evaluates the operations on numbers.
+    /// Operations that would overflow or underflow a `u32` are left unevaluated.
+    pub fn substitute(&self, lookup: &impl Fn(&str) -> Option<ConstExpr>) -> ConstExpr {
+        match self {
+            ConstExpr::Literal(_) => self.clone(),
+            ConstExpr::Name(name) => lookup(name).unwrap_or_else(|| self.clone()),
+            ConstExpr::BinaryOp { op, lhs, rhs } => {
+                let lhs = lhs.substitute(lookup);
+                let rhs = rhs.substitute(lookup);
+
+                let res = match (lhs.value(), rhs.value()) {
+                    (Some(lhs), Some(rhs)) => match op {
+                        Op2::Addition => lhs.checked_add(rhs),
+                        Op2::Subtraction => lhs.checked_sub(rhs),
+                        Op2::Multiplication => lhs.checked_mul(rhs),
+                        _ => unreachable!("unexpected operation in a constant expression"),
+                    },
+                    _ => None,
+                };
+
+                match res {
+                    Some(res) => ConstExpr::Literal(res),
+                    None => ConstExpr::BinaryOp {
+                        op: op.clone(),
+                        lhs: Box::new(lhs),
+                        rhs: Box::new(rhs),
+                    },
+                }
+            }
+        }
+    }
+}
+
+impl Display for ConstExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConstExpr::Literal(value) => write!(f, "{value}"),
+            ConstExpr::Name(name) => write!(f, "{name}"),
+            ConstExpr::BinaryOp { op, lhs, rhs } => {
+                let op = match op {
+                    Op2::Addition => "+",
+                    Op2::Subtraction => "-",
+                    Op2::Multiplication => "*",
+                    _ => unreachable!("unexpected operation in a constant expression"),
+                };
+                write!(f, "({lhs} {op} {rhs})")
+            }
+        }
     }
 }
 
@@ -882,16 +1278,41 @@ pub enum StmtKind {
         lhs: Ident,
         rhs: Box<Expr>,
     },
+
+    // `let (aa, mut bb) = <rhs>;`
+    // (each name comes with whether it is mutable)
+    TupleAssign {
+        lhs: Vec<(bool, Ident)>,
+        rhs: Box<Expr>,
+    },
     Expr(Box<Expr>),
     Return(Box<Expr>),
     Comment(String),
 
-    // `for var in 0..10 { <body> }`
+    // `for var in 0..10 { <body> }` or `for var in array { <body> }`
     ForLoop {
         var: Ident,
-        range: Range,
+        argument: ForLoopArgument,
         body: Vec<Stmt>,
     },
+
+    // `if cond { <then_> } else { <else_> }`
+    // (`else_` is empty if there's no `else` block, and `else if` is an `if` nested in `else_`)
+    If {
+        cond: Box<Expr>,
+        then_: Vec<Stmt>,
+        else_: Vec<Stmt>,
+    },
+}
+
+/// What a `for` loop iterates over.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ForLoopArgument {
+    /// A range of integers (e.g. `0..LEN`).
+    Range(Range),
+
+    /// The elements of an array (e.g. `house.rooms`).
+    Iterator(Box<Expr>),
 }
 
 impl Stmt {
@@ -907,6 +1328,71 @@ impl Stmt {
                 let mut span = span;
                 tokens.bump(ctx);
 
+                // let (aa, mut bb) = f();
+                //     ^
+                if matches!(
+                    tokens.peek(),
+                    Some(Token {
+                        kind: TokenKind::LeftParen,
+                        ..
+                    })
+                ) {
+                    tokens.bump(ctx);
+
+                    let mut lhs = vec![];
+                    loop {
+                        // let (aa, mut bb) = f();
+                        //          ^^^
+                        let mutable = if matches!(
+                            tokens.peek(),
+                            Some(Token {
+                                kind: TokenKind::Keyword(Keyword::Mut),
+                                ..
+                            })
+                        ) {
+                            tokens.bump(ctx);
+                            true
+                        } else {
+                            false
+                        };
+
+                        // let (aa, mut bb) = f();
+                        //      ^^      ^^
+                        lhs.push((mutable, Ident::parse(ctx, tokens)?));
+
+                        // let (aa, mut bb) = f();
+                        //        ^       ^
+                        let token = tokens.bump_err(ctx, ErrorKind::InvalidTupleDestructuring)?;
+                        match token.kind {
+                            TokenKind::Comma => (),
+                            TokenKind::RightParen => break,
+                            _ => {
+                                return Err(
+                                    ctx.error(ErrorKind::InvalidTupleDestructuring, token.span)
+                                )
+                            }
+                        }
+                    }
+
+                    // let (aa, mut bb) = f();
+                    //                  ^
+                    tokens.bump_expected(ctx, TokenKind::Equal)?;
+
+                    // let (aa, mut bb) = f();
+                    //                    ^^^
+                    let rhs = Box::new(Expr::parse(ctx, tokens)?);
+                    span = span.merge_with(rhs.span);
+
+                    // let (aa, mut bb) = f();
+                    //                       ^
+                    tokens.bump_expected(ctx, TokenKind::SemiColon)?;
+
+                    return Ok(Stmt {
+                        kind: StmtKind::TupleAssign { lhs, rhs },
+                        span,
+                    });
+                }
+
                 // let mut x = 5;
                 //     ^^^
 
@@ -964,56 +1450,34 @@ impl Stmt {
 
                 // for i in 0..5 { ... }
                 //          ^
-                let (start, start_span) = match tokens.bump(ctx) {
+                // (or `for elem in array { ... }`)
+                //                  ^^^^^
+                let expr = Expr::parse(ctx, tokens)?;
+
+                let argument = if matches!(
+                    tokens.peek(),
                     Some(Token {
-                        kind: TokenKind::BigUInt(n),
-                        span,
-                    }) => {
-                        let start: u32 = n
-                            .try_into()
-                            .map_err(|_e| ctx.error(ErrorKind::InvalidRangeSize, span))?;
-                        (start, span)
-                    }
-                    _ => {
-                        return Err(ctx.error(
-                            ErrorKind::ExpectedToken(TokenKind::BigUInt(
-                                num_bigint::BigUint::zero(),
-                            )),
-                            ctx.last_span(),
-                        ))
-                    }
-                };
+                        kind: TokenKind::DoubleDot,
+                        ..
+                    })
+                ) {
+                    let start = ConstExpr::from_expr(ctx, &expr)?;
 
-                // for i in 0..5 { ... }
-                //           ^^
-                tokens.bump_expected(ctx, TokenKind::DoubleDot)?;
+                    // for i in 0..5 { ... }
+                    //           ^^
+                    tokens.bump(ctx);
 
-                // for i in 0..5 { ... }
-                //             ^
-                let (end, end_span) = match tokens.bump(ctx) {
-                    Some(Token {
-                        kind: TokenKind::BigUInt(n),
-                        span,
-                    }) => {
-                        let end: u32 = n
-                            .try_into()
-                            .map_err(|_e| ctx.error(ErrorKind::InvalidRangeSize, span))?;
-                        (end, span)
-                    }
-                    _ => {
-                        return Err(ctx.error(
-                            ErrorKind::ExpectedToken(TokenKind::BigUInt(
-                                num_bigint::BigUint::zero(),
-                            )),
-                            ctx.last_span(),
-                        ))
-                    }
-                };
+                    // for i in 0..5 { ... }
+                    //             ^
+                    let (end, end_span) = ConstExpr::parse(ctx, tokens)?;
 
-                let range = Range {
-                    start,
-                    end,
-                    span: start_span.merge_with(end_span),
+                    ForLoopArgument::Range(Range {
+                        start,
+                        end,
+                        span: expr.span.merge_with(end_span),
+                    })
+                } else {
+                    ForLoopArgument::Iterator(Box::new(expr))
                 };
 
                 // for i in 0..5 { ... }
@@ -1048,7 +1512,11 @@ impl Stmt {
 
                 //
                 Ok(Stmt {
-                    kind: StmtKind::ForLoop { var, range, body },
+                    kind: StmtKind::ForLoop {
+                        var,
+                        argument,
+                        body,
+                    },
                     span,
                 })
             }
@@ -1056,10 +1524,51 @@ impl Stmt {
             // if/else
             Some(Token {
                 kind: TokenKind::Keyword(Keyword::If),
-                span: _,
+                span,
             }) => {
-                // TODO: wait, this should be implemented as an expression! not a statement
-                panic!("if statements are not implemented yet. Use if expressions instead (e.g. `x = if cond {{ 1 }} else {{ 2 }};`)");
+                tokens.bump(ctx);
+
+                // if cond { ... } else { ... }
+                //    ^^^^
+                let cond = Box::new(Expr::parse(ctx, tokens)?);
+
+                // if cond { ... } else { ... }
+                //         ^^^^^^^
+                let (then_, mut end) = Self::parse_block(ctx, tokens)?;
+
+                // if cond { ... } else { ... }
+                //                 ^^^^
+                let mut else_ = vec![];
+                if let Some(Token {
+                    kind: TokenKind::Keyword(Keyword::Else),
+                    ..
+                }) = tokens.peek()
+                {
+                    tokens.bump(ctx);
+
+                    // if cond { ... } else if cond2 { ... }
+                    //                      ^^^^^^^^^^^^^^^^
+                    if let Some(Token {
+                        kind: TokenKind::Keyword(Keyword::If),
+                        ..
+                    }) = tokens.peek()
+                    {
+                        let else_if = Stmt::parse(ctx, tokens)?;
+                        end = else_if.span;
+                        else_.push(else_if);
+                    } else {
+                        // if cond { ... } else { ... }
+                        //                      ^^^^^^^
+                        let (stmts, else_end) = Self::parse_block(ctx, tokens)?;
+                        end = else_end;
+                        else_ = stmts;
+                    }
+                }
+
+                Ok(Stmt {
+                    kind: StmtKind::If { cond, then_, else_ },
+                    span: span.merge_with(end),
+                })
             }
 
             // return
@@ -1109,6 +1618,26 @@ impl Stmt {
             }
         }
     }
+
+    /// Parses a block of statements between curly brackets,
+    /// and returns them along with the span of the closing bracket.
+    fn parse_block(ctx: &mut ParserCtx, tokens: &mut Tokens) -> Result<(Vec<Self>, Span)> {
+        tokens.bump_expected(ctx, TokenKind::LeftCurlyBracket)?;
+
+        let mut stmts = vec![];
+        loop {
+            if let Some(Token {
+                kind: TokenKind::RightCurlyBracket,
+                span,
+            }) = tokens.peek()
+            {
+                tokens.bump(ctx);
+                return Ok((stmts, span));
+            }
+
+            stmts.push(Stmt::parse(ctx, tokens)?);
+        }
+    }
 }
 
 //
@@ -1181,6 +1710,7 @@ pub enum RootKind<F: Field> {
     FunctionDef(FunctionDef),
     Comment(String),
     StructDef(StructDef),
+    EnumDef(EnumDef),
     ConstDef(ConstDef<F>),
 }
 
@@ -1,14 +1,38 @@
+use ark_ff::Zero;
+use num_bigint::BigUint;
+
 use crate::backends::Backend;
+use crate::circuit_writer::{CircuitWriter, VarInfo};
 use crate::constants::Span;
+use crate::constraints::{field, keccak, sha256};
+use crate::error::{Error, ErrorKind, Result};
 use crate::imports::FnKind;
 use crate::lexer::Token;
 use crate::parser::types::FnSig;
 use crate::parser::ParserCtx;
 use crate::type_checker::FnInfo;
+use crate::var::{ConstOrCell, Var};
 
 const POSEIDON_FN: &str = "poseidon(input: [Field; 2]) -> [Field; 3]";
+const HASH_FN: &str = "hash(input: [Field; LEN]) -> Field";
+const SPONGE_ABSORB_FN: &str =
+    "sponge_absorb(state: [Field; 3], mode: Field, input: Field) -> ([Field; 3], Field)";
+const SPONGE_SQUEEZE_FN: &str =
+    "sponge_squeeze(state: [Field; 3], mode: Field) -> ([Field; 3], Field, Field)";
+const SHA256_FN: &str = "sha256(input: [u8; LEN]) -> [u8; 32]";
+const KECCAK256_FN: &str = "keccak256(input: [u8; LEN]) -> [u8; 32]";
+
+pub const CRYPTO_SIGS: &[&str] = &[
+    POSEIDON_FN,
+    HASH_FN,
+    SPONGE_ABSORB_FN,
+    SPONGE_SQUEEZE_FN,
+    SHA256_FN,
+    KECCAK256_FN,
+];
 
-pub const CRYPTO_SIGS: &[&str] = &[POSEIDON_FN];
+/// The part of `std::crypto` that is written in noname (the `Sponge` struct).
+pub const CRYPTO_MODULE: &str = include_str!("native/crypto.no");
 
 pub fn get_crypto_fn<B: Backend>(name: &str) -> Option<FnInfo<B>> {
     let ctx = &mut ParserCtx::default();
@@ -17,6 +41,11 @@ pub fn get_crypto_fn<B: Backend>(name: &str) -> Option<FnInfo<B>> {
 
     let fn_handle = match name {
         POSEIDON_FN => B::poseidon(),
+        HASH_FN => hash,
+        SPONGE_ABSORB_FN => sponge_absorb,
+        SPONGE_SQUEEZE_FN => sponge_squeeze,
+        SHA256_FN => B::sha256(),
+        KECCAK256_FN => B::keccak256(),
         _ => return None,
     };
 
@@ -33,3 +62,265 @@ pub fn crypto_fns<B: Backend>() -> Vec<FnInfo<B>> {
         .map(|sig| get_crypto_fn(sig).unwrap())
         .collect()
 }
+
+//
+// Poseidon sponge
+//
+
+/// The number of field elements absorbed or squeezed between two permutations.
+/// The last element of the state is the capacity.
+const RATE: usize = 2;
+
+/// The mode of a sponge, which mirrors the sponge of `mina_poseidon`.
+/// It is known at compile time, and is encoded in the circuit as a constant:
+/// `Absorbed(n)` as `n`, and `Squeezed(n)` as `RATE + n`.
+#[derive(Debug, Clone, Copy)]
+enum SpongeMode {
+    /// `n` elements have been absorbed since the last permutation.
+    Absorbed(usize),
+
+    /// `n` elements have been squeezed since the last permutation.
+    Squeezed(usize),
+}
+
+impl SpongeMode {
+    fn decode<B: Backend>(mode: &Var<B::Field, B::Var>, span: Span) -> Result<Self> {
+        let err = || Error::new("constraint-generation", ErrorKind::InvalidSpongeMode, span);
+
+        let mode: BigUint = mode.constant().ok_or_else(err)?.into();
+        let mode: usize = mode.try_into().map_err(|_| err())?;
+
+        match mode {
+            0..=RATE => Ok(Self::Absorbed(mode)),
+            _ if mode <= 2 * RATE => Ok(Self::Squeezed(mode - RATE)),
+            _ => Err(err()),
+        }
+    }
+
+    fn encode<B: Backend>(self, span: Span) -> Var<B::Field, B::Var> {
+        let mode = match self {
+            Self::Absorbed(n) => n,
+            Self::Squeezed(n) => RATE + n,
+        };
+
+        Var::new_constant(B::Field::from(mode as u64), span)
+    }
+}
+
+/// A poseidon sponge, built on the poseidon permutation of the backend.
+struct Sponge<B: Backend> {
+    state: Vec<ConstOrCell<B::Field, B::Var>>,
+    mode: SpongeMode,
+}
+
+impl<B: Backend> Sponge<B> {
+    fn new() -> Self {
+        Self {
+            state: vec![ConstOrCell::Const(B::Field::zero()); RATE + 1],
+            mode: SpongeMode::Absorbed(0),
+        }
+    }
+
+    fn permute(&mut self, compiler: &mut CircuitWriter<B>, span: Span) -> Result<()> {
+        let state = B::poseidon_permutation()(compiler, &self.state, span)?;
+        self.state = state.into_iter().map(ConstOrCell::Cell).collect();
+        Ok(())
+    }
+
+    fn add_to_state(
+        &mut self,
+        compiler: &mut CircuitWriter<B>,
+        idx: usize,
+        input: &ConstOrCell<B::Field, B::Var>,
+        span: Span,
+    ) {
+        let res = field::add(compiler, &self.state[idx], input, span);
+        self.state[idx] = res.cvars[0].clone();
+    }
+
+    fn absorb(
+        &mut self,
+        compiler: &mut CircuitWriter<B>,
+        input: &ConstOrCell<B::Field, B::Var>,
+        span: Span,
+    ) -> Result<()> {
+        match self.mode {
+            SpongeMode::Absorbed(n) if n < RATE => {
+                self.add_to_state(compiler, n, input, span);
+                self.mode = SpongeMode::Absorbed(n + 1);
+            }
+            SpongeMode::Absorbed(_) => {
+                self.permute(compiler, span)?;
+                self.add_to_state(compiler, 0, input, span);
+                self.mode = SpongeMode::Absorbed(1);
+            }
+            SpongeMode::Squeezed(_) => {
+                self.add_to_state(compiler, 0, input, span);
+                self.mode = SpongeMode::Absorbed(1);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn squeeze(
+        &mut self,
+        compiler: &mut CircuitWriter<B>,
+        span: Span,
+    ) -> Result<ConstOrCell<B::Field, B::Var>> {
+        match self.mode {
+            SpongeMode::Squeezed(n) if n < RATE => {
+                self.mode = SpongeMode::Squeezed(n + 1);
+                Ok(self.state[n].clone())
+            }
+            SpongeMode::Absorbed(_) | SpongeMode::Squeezed(_) => {
+                self.permute(compiler, span)?;
+                self.mode = SpongeMode::Squeezed(1);
+                Ok(self.state[0].clone())
+            }
+        }
+    }
+
+    /// Returns the state of the sponge, followed by its mode.
+    fn into_vars(self, span: Span) -> Vec<ConstOrCell<B::Field, B::Var>> {
+        let mut cvars = self.state;
+        cvars.extend(self.mode.encode::<B>(span).cvars);
+        cvars
+    }
+}
+
+/// Hashes an array of field elements.
+/// The length of the array is absorbed first, so that arrays of different lengths
+/// (for example `[xx]` and `[xx, 0]`) don't have the same digest.
+fn hash<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    vars: &[VarInfo<B::Field, B::Var>],
+    span: Span,
+) -> Result<Option<Var<B::Field, B::Var>>> {
+    // we get an array of field elements
+    assert_eq!(vars.len(), 1);
+    let input = &vars[0].var;
+
+    let len = ConstOrCell::Const(B::Field::from(input.len() as u64));
+
+    let mut sponge = Sponge::<B>::new();
+    sponge.absorb(compiler, &len, span)?;
+    for cvar in &input.cvars {
+        sponge.absorb(compiler, cvar, span)?;
+    }
+    let digest = sponge.squeeze(compiler, span)?;
+
+    Ok(Some(Var::new_cvar(digest, span)))
+}
+
+/// Absorbs a field element in a sponge, and returns the new state and mode of the sponge.
+fn sponge_absorb<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    vars: &[VarInfo<B::Field, B::Var>],
+    span: Span,
+) -> Result<Option<Var<B::Field, B::Var>>> {
+    // we get a state, a mode and a field element
+    assert_eq!(vars.len(), 3);
+
+    let mut sponge = Sponge::<B> {
+        state: vars[0].var.cvars.clone(),
+        mode: SpongeMode::decode::<B>(&vars[1].var, span)?,
+    };
+
+    let input = &vars[2].var;
+    assert_eq!(input.len(), 1);
+    sponge.absorb(compiler, &input[0], span)?;
+
+    Ok(Some(Var::new(sponge.into_vars(span), span)))
+}
+
+/// Squeezes a field element out of a sponge,
+/// and returns the new state and mode of the sponge followed by the field element.
+fn sponge_squeeze<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    vars: &[VarInfo<B::Field, B::Var>],
+    span: Span,
+) -> Result<Option<Var<B::Field, B::Var>>> {
+    // we get a state and a mode
+    assert_eq!(vars.len(), 2);
+
+    let mut sponge = Sponge::<B> {
+        state: vars[0].var.cvars.clone(),
+        mode: SpongeMode::decode::<B>(&vars[1].var, span)?,
+    };
+
+    let output = sponge.squeeze(compiler, span)?;
+
+    let mut cvars = sponge.into_vars(span);
+    cvars.push(output);
+
+    Ok(Some(Var::new(cvars, span)))
+}
+
+//
+// SHA-256
+//
+
+/// Hashes an array of bytes with SHA-256.
+pub fn sha256<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    vars: &[VarInfo<B::Field, B::Var>],
+    span: Span,
+) -> Result<Option<Var<B::Field, B::Var>>> {
+    // we get an array of bytes
+    assert_eq!(vars.len(), 1);
+    let input = &vars[0].var;
+
+    let bytes = bytes_to_bits(compiler, input, span)?;
+    let digest = sha256::hash(compiler, &bytes, span)?;
+
+    Ok(Some(bits_to_bytes(compiler, &digest, span)))
+}
+
+//
+// Keccak-256
+//
+
+/// Hashes an array of bytes with Keccak-256 (as done by Ethereum).
+pub fn keccak256<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    vars: &[VarInfo<B::Field, B::Var>],
+    span: Span,
+) -> Result<Option<Var<B::Field, B::Var>>> {
+    // we get an array of bytes
+    assert_eq!(vars.len(), 1);
+    let input = &vars[0].var;
+
+    let bytes = bytes_to_bits(compiler, input, span)?;
+    let digest = keccak::hash(compiler, &bytes, span);
+
+    Ok(Some(bits_to_bytes(compiler, &digest, span)))
+}
+
+/// Decomposes each byte of an array in bits, from the least significant bit.
+/// The decomposition also constrains the inputs to be bytes.
+fn bytes_to_bits<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    input: &Var<B::Field, B::Var>,
+    span: Span,
+) -> Result<Vec<Vec<ConstOrCell<B::Field, B::Var>>>> {
+    input
+        .cvars
+        .iter()
+        .map(|byte| field::to_bits(compiler, byte, 8, span))
+        .collect()
+}
+
+/// Recomposes bytes from their bits.
+fn bits_to_bytes<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    bytes: &[Vec<ConstOrCell<B::Field, B::Var>>],
+    span: Span,
+) -> Var<B::Field, B::Var> {
+    let cvars = bytes
+        .iter()
+        .map(|bits| field::from_bits(compiler, bits, span)[0].clone())
+        .collect();
+
+    Var::new(cvars, span)
+}
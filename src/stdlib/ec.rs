@@ -0,0 +1,67 @@
+//! The `std::ec` module: points of the Pallas curve `y^2 = x^3 + 5`.
+//! The `Point` struct is written in noname, and its operations call the builtins of this module,
+//! which are only provided by backends whose circuit field is the base field of Pallas (see [Backend::ec_add]).
+
+use crate::{
+    backends::Backend,
+    circuit_writer::VarInfo,
+    constants::Span,
+    constraints::ec::Point,
+    error::{Error, ErrorKind, Result},
+    imports::FnKind,
+    lexer::Token,
+    parser::{types::FnSig, ParserCtx},
+    type_checker::FnInfo,
+};
+
+const ADD_POINTS_FN: &str = "add_points(lhs: [Field; 2], rhs: [Field; 2]) -> [Field; 2]";
+const DOUBLE_POINT_FN: &str = "double_point(point: [Field; 2]) -> [Field; 2]";
+const SCALE_POINT_FN: &str = "scale_point(point: [Field; 2], scalar: [Bool; LEN]) -> [Field; 2]";
+
+pub const EC_SIGS: &[&str] = &[ADD_POINTS_FN, DOUBLE_POINT_FN, SCALE_POINT_FN];
+
+/// The part of `std::ec` that is written in noname (the `Point` struct).
+pub const EC_MODULE: &str = include_str!("native/ec.no");
+
+pub fn get_ec_fn<B: Backend>(name: &str) -> Option<FnInfo<B>> {
+    let ctx = &mut ParserCtx::default();
+    let mut tokens = Token::parse(0, name).unwrap();
+    let sig = FnSig::parse(ctx, &mut tokens).unwrap();
+
+    let fn_handle = match name {
+        ADD_POINTS_FN => B::ec_add(),
+        DOUBLE_POINT_FN => B::ec_double(),
+        SCALE_POINT_FN => B::ec_scale(),
+        _ => None,
+    }?;
+
+    Some(FnInfo {
+        kind: FnKind::BuiltIn(sig, fn_handle),
+        span: Span::default(),
+    })
+}
+
+/// a function returns elliptic curve functions (none if the backend doesn't support them)
+pub fn ec_fns<B: Backend>() -> Vec<FnInfo<B>> {
+    EC_SIGS.iter().filter_map(|sig| get_ec_fn(sig)).collect()
+}
+
+/// Returns an error if `std::ec` is imported with a backend that doesn't provide its builtins.
+pub fn check_backend<B: Backend>(span: Span) -> Result<()> {
+    if ec_fns::<B>().len() != EC_SIGS.len() {
+        return Err(Error::new(
+            "type-checker",
+            ErrorKind::UnsupportedEcBackend,
+            span,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Returns the point passed as argument to a builtin.
+pub(crate) fn point_arg<B: Backend>(var_info: &VarInfo<B::Field, B::Var>) -> Point<B> {
+    let var = &var_info.var;
+    assert_eq!(var.len(), 2);
+    [var[0].clone(), var[1].clone()]
+}
@@ -0,0 +1,208 @@
+//! The `std::merkle` module is written in noname.
+//! This module also contains a native Merkle tree, hashed like `std::merkle`,
+//! to produce the inputs of circuits that use `std::merkle`.
+
+use serde_json::{json, Value};
+
+use crate::{
+    backends::{
+        kimchi::VestaField,
+        r1cs::{poseidon, R1csBls12381Field, R1csBn254Field},
+        BackendField,
+    },
+    helpers::poseidon_hash,
+    inputs::ExtField,
+};
+
+/// The source code of the `std::merkle` module.
+pub const MERKLE_MODULE: &str = include_str!("native/merkle.no");
+
+/// A field of a backend, on which the nodes of a Merkle tree can be hashed natively.
+pub trait MerkleField: BackendField {
+    /// Hashes two children into their parent node, like `hash_nodes` in `std::merkle`
+    /// (which uses `crypto::hash`, whose poseidon parameters depend on the backend).
+    fn hash_nodes(left: Self, right: Self) -> Self;
+}
+
+impl MerkleField for VestaField {
+    fn hash_nodes(left: Self, right: Self) -> Self {
+        poseidon_hash(&[left, right])
+    }
+}
+
+impl MerkleField for R1csBn254Field {
+    fn hash_nodes(left: Self, right: Self) -> Self {
+        poseidon::hash(&[left, right])
+    }
+}
+
+impl MerkleField for R1csBls12381Field {
+    fn hash_nodes(left: Self, right: Self) -> Self {
+        poseidon::hash(&[left, right])
+    }
+}
+
+/// A Merkle tree of `2^depth` leaves, hashed with poseidon.
+#[derive(Debug, Clone)]
+pub struct MerkleTree<F: MerkleField = VestaField> {
+    /// The nodes of the tree, level by level:
+    /// the first level contains the leaves, and the last one contains the root.
+    levels: Vec<Vec<F>>,
+}
+
+/// The path from a leaf to the root of a [MerkleTree].
+#[derive(Debug, Clone)]
+pub struct MerklePath<F: MerkleField = VestaField> {
+    /// The siblings of the nodes on the path, from the leaf.
+    pub siblings: Vec<F>,
+
+    /// The bits of the index of the leaf, from the least significant bit
+    /// (`true` if the node at that height is a right child).
+    pub index_bits: Vec<bool>,
+}
+
+impl<F: MerkleField> MerkleTree<F> {
+    /// Creates a tree of the given depth, with all leaves set to zero.
+    pub fn new(depth: usize) -> Self {
+        Self::from_leaves(depth, &[])
+    }
+
+    /// Creates a tree of the given depth, with the given leaves followed by zeros.
+    pub fn from_leaves(depth: usize, leaves: &[F]) -> Self {
+        assert!(
+            leaves.len() <= 1 << depth,
+            "too many leaves for a tree of depth {depth}"
+        );
+
+        let mut level = leaves.to_vec();
+        level.resize(1 << depth, F::zero());
+
+        let mut levels = vec![level];
+        for _ in 0..depth {
+            let children = &levels[levels.len() - 1];
+            let parents = children
+                .chunks(2)
+                .map(|pair| F::hash_nodes(pair[0], pair[1]))
+                .collect();
+            levels.push(parents);
+        }
+
+        Self { levels }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.levels.len() - 1
+    }
+
+    pub fn root(&self) -> F {
+        self.levels[self.depth()][0]
+    }
+
+    pub fn leaf(&self, index: usize) -> F {
+        self.levels[0][index]
+    }
+
+    /// Returns the path from the leaf at `index` to the root.
+    pub fn path(&self, index: usize) -> MerklePath<F> {
+        let mut siblings = vec![];
+        let mut index_bits = vec![];
+
+        let mut idx = index;
+        for level in &self.levels[..self.depth()] {
+            siblings.push(level[idx ^ 1]);
+            index_bits.push(idx & 1 == 1);
+            idx >>= 1;
+        }
+
+        MerklePath {
+            siblings,
+            index_bits,
+        }
+    }
+
+    /// Sets the leaf at `index`, and updates the nodes on its path.
+    pub fn set(&mut self, index: usize, leaf: F) {
+        self.levels[0][index] = leaf;
+
+        let mut idx = index;
+        for height in 0..self.depth() {
+            let left = self.levels[height][idx & !1];
+            let right = self.levels[height][idx | 1];
+            idx >>= 1;
+            self.levels[height + 1][idx] = F::hash_nodes(left, right);
+        }
+    }
+
+    /// Returns the private inputs of `merkle::verify_path(leaf, root, path, index_bits)`
+    /// for the leaf at `index`, as JSON (the root is usually public).
+    pub fn witness(&self, index: usize) -> Value {
+        let path = self.path(index);
+        json!({
+            "leaf": self.leaf(index).to_dec_string(),
+            "path": path.siblings_json(),
+            "index_bits": path.index_bits,
+        })
+    }
+
+    /// Replaces the leaf at `index` with `new_leaf`, and returns the private inputs of
+    /// `merkle::verify_update(leaf, new_leaf, root, new_root, path, index_bits)` as JSON
+    /// (the roots before and after the update are usually public).
+    pub fn update(&mut self, index: usize, new_leaf: F) -> Value {
+        let mut witness = self.witness(index);
+        witness["new_leaf"] = json!(new_leaf.to_dec_string());
+
+        self.set(index, new_leaf);
+
+        witness
+    }
+}
+
+impl<F: MerkleField> MerklePath<F> {
+    /// Computes the root of the tree from a leaf.
+    pub fn compute_root(&self, leaf: F) -> F {
+        self.siblings
+            .iter()
+            .zip(&self.index_bits)
+            .fold(leaf, |node, (sibling, is_right)| {
+                if *is_right {
+                    F::hash_nodes(*sibling, node)
+                } else {
+                    F::hash_nodes(node, *sibling)
+                }
+            })
+    }
+
+    fn siblings_json(&self) -> Vec<String> {
+        self.siblings.iter().map(ExtField::to_dec_string).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_ff::Zero;
+
+    use super::*;
+
+    #[test]
+    fn test_merkle_tree() {
+        let leaves: Vec<VestaField> = (1..=5u64).map(VestaField::from).collect();
+        let mut tree = MerkleTree::from_leaves(3, &leaves);
+        assert_eq!(tree.depth(), 3);
+
+        // the path of each leaf leads to the root
+        for index in 0..8 {
+            let path = tree.path(index);
+            assert_eq!(path.compute_root(tree.leaf(index)), tree.root());
+        }
+
+        // updating a leaf updates the root, and the path of the leaf stays the same
+        let path = tree.path(6);
+        tree.set(6, VestaField::from(42u64));
+        assert_eq!(path.compute_root(VestaField::from(42u64)), tree.root());
+
+        let mut leaves = leaves;
+        leaves.resize(6, VestaField::zero());
+        leaves.push(VestaField::from(42u64));
+        assert_eq!(MerkleTree::from_leaves(3, &leaves).root(), tree.root());
+    }
+}
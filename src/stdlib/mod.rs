@@ -1,12 +1,14 @@
 use std::collections::HashSet;
 
-use ark_ff::One;
+use ark_ff::{One, Zero};
+use num_bigint::BigUint;
 use once_cell::sync::Lazy;
 
 use crate::{
     backends::Backend,
     circuit_writer::{CircuitWriter, VarInfo},
     constants::Span,
+    constraints::field,
     error::{Error, ErrorKind, Result},
     imports::FnKind,
     lexer::Token,
@@ -19,6 +21,35 @@ use crate::{
 };
 
 pub mod crypto;
+pub mod ec;
+pub mod merkle;
+
+/// The name used to import the standard library (e.g. `use std::crypto`).
+pub const STD_MODULE: &str = "std";
+
+/// The modules of the standard library that are (partly) written in noname,
+/// and their source code.
+pub const STD_MODULES: &[(&str, &str)] = &[
+    ("crypto", crypto::CRYPTO_MODULE),
+    ("ec", ec::EC_MODULE),
+    ("merkle", merkle::MERKLE_MODULE),
+];
+
+/// Returns the noname source code of a module of the standard library, if it has one.
+pub fn get_std_module(name: &str) -> Option<&'static str> {
+    STD_MODULES
+        .iter()
+        .find(|(module, _)| *module == name)
+        .map(|(_, code)| *code)
+}
+
+/// Returns an error if a module of the standard library can't be used with the backend.
+pub fn check_std_module<B: Backend>(name: &str, span: Span) -> Result<()> {
+    match name {
+        "ec" => ec::check_backend::<B>(span),
+        _ => Ok(()),
+    }
+}
 
 //
 // Builtins or utils (imported by default)
@@ -29,9 +60,11 @@ pub const QUALIFIED_BUILTINS: &str = "std/builtins";
 
 const ASSERT_FN: &str = "assert(condition: Bool)";
 const ASSERT_EQ_FN: &str = "assert_eq(lhs: Field, rhs: Field)";
+const TO_BITS_FN: &str = "to_bits(val: Field, const LEN: Field) -> [Bool; LEN]";
+const FROM_BITS_FN: &str = "from_bits(bits: [Bool; LEN]) -> Field";
 
 /// List of builtin function signatures.
-pub const BUILTIN_SIGS: &[&str] = &[ASSERT_FN, ASSERT_EQ_FN];
+pub const BUILTIN_SIGS: &[&str] = &[ASSERT_FN, ASSERT_EQ_FN, TO_BITS_FN, FROM_BITS_FN];
 
 // Unique set of builtin function names, derived from function signatures.
 pub static BUILTIN_FN_NAMES: Lazy<HashSet<String>> = Lazy::new(|| {
@@ -54,6 +87,8 @@ pub fn get_builtin_fn<B: Backend>(name: &str) -> Option<FnInfo<B>> {
     let fn_handle = match name {
         ASSERT_FN => assert,
         ASSERT_EQ_FN => assert_eq,
+        TO_BITS_FN => to_bits,
+        FROM_BITS_FN => from_bits,
         _ => return None,
     };
 
@@ -82,15 +117,20 @@ fn assert_eq<B: Backend>(
     let lhs_info = &vars[0];
     let rhs_info = &vars[1];
 
-    // they are both of type field
-    if !matches!(lhs_info.typ, Some(TyKind::Field | TyKind::BigInt)) {
+    // they are both of type field (unsigned integers are field elements as well)
+    let is_field = |typ: &Option<TyKind>| {
+        matches!(typ, Some(TyKind::Field | TyKind::BigInt))
+            || typ.as_ref().and_then(TyKind::uint_bits).is_some()
+    };
+
+    if !is_field(&lhs_info.typ) {
         panic!(
             "the lhs of assert_eq must be of type Field or BigInt. It was of type {:?}",
             lhs_info.typ
         );
     }
 
-    if !matches!(rhs_info.typ, Some(TyKind::Field | TyKind::BigInt)) {
+    if !is_field(&rhs_info.typ) {
         panic!(
             "the rhs of assert_eq must be of type Field or BigInt. It was of type {:?}",
             rhs_info.typ
@@ -106,7 +146,37 @@ fn assert_eq<B: Backend>(
     assert_eq!(rhs_var.len(), 1);
     let rhs_cvar = &rhs_var[0];
 
-    match (lhs_cvar, rhs_cvar) {
+    assert_eq_cells(compiler, lhs_cvar, rhs_cvar, span)?;
+
+    Ok(None)
+}
+
+/// Constrains two cells to be equal.
+/// In the block of an `if` statement, this is only enforced if the block is taken:
+/// `cond * (lhs - rhs) = 0` is constrained instead.
+fn assert_eq_cells<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    lhs: &ConstOrCell<B::Field, B::Var>,
+    rhs: &ConstOrCell<B::Field, B::Var>,
+    span: Span,
+) -> Result<()> {
+    let (lhs, rhs) = match compiler.condition.clone() {
+        // not in an `if` statement, or in a block that is always taken
+        None => (lhs.clone(), rhs.clone()),
+        Some(ConstOrCell::Const(cond)) if cond.is_one() => (lhs.clone(), rhs.clone()),
+
+        // in a block that is never taken
+        Some(ConstOrCell::Const(_)) => return Ok(()),
+
+        // cond * (lhs - rhs) = 0
+        Some(cond) => {
+            let diff = field::sub(compiler, lhs, rhs, span);
+            let res = field::mul(compiler, &cond, &diff[0], span);
+            (res[0].clone(), ConstOrCell::Const(B::Field::zero()))
+        }
+    };
+
+    match (&lhs, &rhs) {
         // two constants
         (ConstOrCell::Const(a), ConstOrCell::Const(b)) => {
             if a != b {
@@ -128,7 +198,7 @@ fn assert_eq<B: Backend>(
         }
     }
 
-    Ok(None)
+    Ok(())
 }
 
 /// Asserts that a condition is true.
@@ -149,15 +219,47 @@ fn assert<B: Backend>(
     assert_eq!(var.len(), 1);
     let cond = &var[0];
 
-    match cond {
-        ConstOrCell::Const(cst) => {
-            assert!(cst.is_one());
-        }
-        ConstOrCell::Cell(cvar) => {
-            let one = B::Field::one();
-            compiler.backend.assert_eq_const(cvar, one, span);
-        }
-    }
+    let one = ConstOrCell::Const(B::Field::one());
+    assert_eq_cells(compiler, cond, &one, span)?;
 
     Ok(None)
 }
+
+/// Decomposes a field element into `LEN` booleans, from the least significant bit.
+fn to_bits<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    vars: &[VarInfo<B::Field, B::Var>],
+    span: Span,
+) -> Result<Option<Var<B::Field, B::Var>>> {
+    // we get a field element and the number of bits
+    assert_eq!(vars.len(), 2);
+
+    let val_var = &vars[0].var;
+    assert_eq!(val_var.len(), 1);
+
+    let len_var = &vars[1].var;
+    let len: BigUint = len_var
+        .constant()
+        .ok_or_else(|| Error::new("constraint-generation", ErrorKind::ExpectedConstant, span))?
+        .into();
+    let len: usize = len
+        .try_into()
+        .expect("the number of bits was checked by the type checker");
+
+    let bits = field::to_bits(compiler, &val_var[0], len, span)?;
+
+    Ok(Some(Var::new(bits, span)))
+}
+
+/// Recomposes a field element from booleans, given from the least significant bit.
+fn from_bits<B: Backend>(
+    compiler: &mut CircuitWriter<B>,
+    vars: &[VarInfo<B::Field, B::Var>],
+    span: Span,
+) -> Result<Option<Var<B::Field, B::Var>>> {
+    // we get an array of booleans
+    assert_eq!(vars.len(), 1);
+    let bits = &vars[0].var;
+
+    Ok(Some(field::from_bits(compiler, &bits.cvars, span)))
+}
@@ -48,9 +48,23 @@ pub fn is_type(s: &str) -> bool {
     // first char is an uppercase letter
     // rest are lowercase alphanumeric
     first_char.is_alphabetic() && first_char.is_uppercase() && chars.all(|c| (c.is_alphanumeric()))
+    // all-uppercase names are generic sizes, not types
+        && !is_generic_parameter(s)
     // TODO: check camel case?
 }
 
+/// Returns true if the given string is the name of a generic size
+/// (uppercase letters, digits and underscores, e.g. `LEN`)
+pub fn is_generic_parameter(s: &str) -> bool {
+    let mut chars = s.chars();
+    let first_letter = chars.next().unwrap();
+    // first char is an uppercase letter
+    first_letter.is_ascii_uppercase()
+    // rest are uppercase alphanumeric or underscore
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        && s.len() > 1
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,5 +78,9 @@ mod tests {
         assert!(is_identifier_or_type("c_ond2"));
         assert!(is_identifier("cond2"));
         assert!(is_type("Cond2"));
+        assert!(is_generic_parameter("LEN"));
+        assert!(is_generic_parameter("MAX_LEN2"));
+        assert!(!is_generic_parameter("Len"));
+        assert!(!is_type("LEN"));
     }
 }
@@ -1,15 +1,17 @@
 use std::{path::Path, str::FromStr};
 
+use kimchi::mina_poseidon::poseidon::Sponge as _;
 use rstest::rstest;
 
 use crate::{
     backends::{
         kimchi::{KimchiVesta, VestaField},
-        r1cs::R1CS,
-        BackendKind,
+        r1cs::{R1csBls12381Field, R1csBn254Field, R1CS},
+        BackendField, BackendKind,
     },
-    compiler::{compile, typecheck_next_file, Sources},
-    inputs::{parse_inputs, ExtField},
+    compiler::{compile, generate_witness, typecheck_next_file, Sources},
+    inputs::{parse_inputs, ExtField, JsonInputs},
+    stdlib::merkle::{MerkleField, MerkleTree},
     type_checker::TypeChecker,
 };
 
@@ -94,64 +96,124 @@ fn test_file(
             // verify proof
             verifier_index.verify(full_public_inputs, proof).unwrap();
         }
-        BackendKind::R1csBls12_381(r1cs) => {
-            // compile
-            let mut sources = Sources::new();
-            let mut tast = TypeChecker::new();
-            let this_module = None;
-            let _node_id = typecheck_next_file(
-                &mut tast,
-                this_module,
-                &mut sources,
-                file_name.to_string(),
-                code.clone(),
-                0,
-            )
-            .unwrap();
-
-            let compiled_circuit = compile(&sources, tast, r1cs)?;
+        BackendKind::R1csBls12_381(r1cs) => test_r1cs_file(
+            r1cs,
+            file_name,
+            code,
+            &prefix_examples,
+            public_inputs,
+            private_inputs,
+            expected_public_output,
+        )?,
+        BackendKind::R1csBn254(r1cs) => test_r1cs_file(
+            r1cs,
+            file_name,
+            code,
+            &prefix_examples,
+            public_inputs,
+            private_inputs,
+            expected_public_output,
+        )?,
+    }
 
-            // this should check the constraints
-            let generated_witness = compiled_circuit
-                .generate_witness(public_inputs.clone(), private_inputs.clone())
-                .unwrap();
+    Ok(())
+}
 
-            // check the ASM
-            if compiled_circuit.circuit.backend.num_constraints() < 100 {
-                let prefix_asm = prefix_examples.join("fixture/asm/r1cs");
-                let expected_asm =
-                    std::fs::read_to_string(prefix_asm.clone().join(format!("{file_name}.asm")))
-                        .unwrap();
-                let obtained_asm = compiled_circuit.asm(&Sources::new(), false);
+/// Generates the witness of an example with the kimchi backend, without creating a proof.
+/// This checks the gates that don't need the prover (see [KimchiVesta::generate_witness]),
+/// for circuits that are too large to be proven in a test.
+fn test_kimchi_witness(
+    file_name: &str,
+    public_inputs: &str,
+    private_inputs: &str,
+) -> miette::Result<()> {
+    let prefix_examples = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples");
+    let code = std::fs::read_to_string(prefix_examples.join(format!("{file_name}.no"))).unwrap();
+
+    let mut sources = Sources::new();
+    let mut tast = TypeChecker::new();
+    typecheck_next_file(
+        &mut tast,
+        None,
+        &mut sources,
+        file_name.to_string(),
+        code,
+        0,
+    )?;
+    let compiled_circuit = compile(&sources, tast, KimchiVesta::new(false))?;
 
-                if obtained_asm != expected_asm {
-                    eprintln!("obtained:");
-                    eprintln!("{obtained_asm}");
-                    eprintln!("expected:");
-                    eprintln!("{expected_asm}");
-                    panic!("Obtained ASM does not match expected ASM");
-                }
-            }
+    generate_witness(
+        &compiled_circuit,
+        &sources,
+        parse_inputs(public_inputs).unwrap(),
+        parse_inputs(private_inputs).unwrap(),
+    )?;
 
-            let expected_public_output = expected_public_output
-                .iter()
-                .map(|x| crate::backends::r1cs::R1csBls12381Field::from_str(x).unwrap())
-                .collect::<Vec<_>>();
+    Ok(())
+}
 
-            if generated_witness.outputs != expected_public_output {
-                eprintln!("obtained by executing the circuit:");
-                generated_witness
-                    .outputs
-                    .iter()
-                    .for_each(|x| eprintln!("- {x}"));
-                eprintln!("passed as output by the verifier:");
-                expected_public_output
-                    .iter()
-                    .for_each(|x| eprintln!("- {x}"));
-                panic!("Obtained output does not match expected output");
-            }
+fn test_r1cs_file<F: BackendField>(
+    r1cs: R1CS<F>,
+    file_name: &str,
+    code: String,
+    prefix_examples: &Path,
+    public_inputs: JsonInputs,
+    private_inputs: JsonInputs,
+    expected_public_output: Vec<&str>,
+) -> miette::Result<()> {
+    // compile
+    let mut sources = Sources::new();
+    let mut tast = TypeChecker::new();
+    let this_module = None;
+    let _node_id = typecheck_next_file(
+        &mut tast,
+        this_module,
+        &mut sources,
+        file_name.to_string(),
+        code,
+        0,
+    )
+    .unwrap();
+
+    let compiled_circuit = compile(&sources, tast, r1cs)?;
+
+    // this should check the constraints
+    let generated_witness = compiled_circuit
+        .generate_witness(public_inputs, private_inputs)
+        .unwrap();
+
+    // check the ASM
+    if compiled_circuit.circuit.backend.num_constraints() < 100 {
+        let prefix_asm = prefix_examples.join("fixture/asm/r1cs");
+        let expected_asm =
+            std::fs::read_to_string(prefix_asm.clone().join(format!("{file_name}.asm"))).unwrap();
+        let obtained_asm = compiled_circuit.asm(&Sources::new(), false);
+
+        if obtained_asm != expected_asm {
+            eprintln!("obtained:");
+            eprintln!("{obtained_asm}");
+            eprintln!("expected:");
+            eprintln!("{expected_asm}");
+            panic!("Obtained ASM does not match expected ASM");
         }
-        BackendKind::R1csBn254(_) => todo!(),
+    }
+
+    let expected_public_output = expected_public_output
+        .iter()
+        .map(|x| F::from_str(x).unwrap_or_else(|_| panic!("invalid field element `{x}`")))
+        .collect::<Vec<_>>();
+
+    if generated_witness.outputs != expected_public_output {
+        eprintln!("obtained by executing the circuit:");
+        generated_witness
+            .outputs
+            .iter()
+            .for_each(|x| eprintln!("- {x}"));
+        eprintln!("passed as output by the verifier:");
+        expected_public_output
+            .iter()
+            .for_each(|x| eprintln!("- {x}"));
+        panic!("Obtained output does not match expected output");
     }
 
     Ok(())
@@ -207,7 +269,6 @@ fn test_lc_return(#[case] backend: BackendKind) -> miette::Result<()> {
 
 #[rstest]
 #[case::kimchi_vesta(BackendKind::KimchiVesta(KimchiVesta::new(false)))]
-//todo: #[case::r1cs(BackendKind::R1CS(R1CS::new()))]
 fn test_poseidon(#[case] backend: BackendKind) -> miette::Result<()> {
     let private_inputs = r#"{"private_input": ["1", "1"]}"#;
     let private_input = [1.into(), 1.into()];
@@ -225,6 +286,208 @@ fn test_poseidon(#[case] backend: BackendKind) -> miette::Result<()> {
     Ok(())
 }
 
+// the digests of `[1, 2]` are the first elements of the test vectors
+// `poseidonperm_x5_255_3` and `poseidonperm_x5_254_3` of the Poseidon reference implementation
+// (see the tests of `backends::r1cs::poseidon`), the latter also being circomlib's `poseidon([1, 2])`
+#[rstest]
+#[case::r1cs(
+    BackendKind::R1csBls12_381(R1CS::new()),
+    "18456658763349757341014058622209659766100673761449600566550821987295786346378"
+)]
+#[case::r1cs_bn254(
+    BackendKind::R1csBn254(R1CS::new()),
+    "7853200120776062878684798364095072458815029376092732009249414926327459813530"
+)]
+fn test_poseidon_r1cs(#[case] backend: BackendKind, #[case] digest: &str) -> miette::Result<()> {
+    let private_inputs = r#"{"private_input": ["1", "2"]}"#;
+    let public_inputs = &format!(r#"{{"public_input": "{digest}"}}"#);
+
+    test_file("poseidon", public_inputs, private_inputs, vec![], backend)?;
+
+    Ok(())
+}
+
+#[rstest]
+#[case::kimchi_vesta(BackendKind::KimchiVesta(KimchiVesta::new(false)))]
+fn test_hash(#[case] backend: BackendKind) -> miette::Result<()> {
+    let preimage: Vec<VestaField> = (1..=10u64).map(VestaField::from).collect();
+    let digest = crate::helpers::poseidon_hash(&preimage);
+
+    // the outputs of the sponge after absorbing the first 4 elements
+    let mut sponge = crate::helpers::poseidon_sponge();
+    sponge.absorb(&preimage[..4]);
+    let outputs: Vec<_> = (0..3)
+        .map(|_| format!(r#""{}""#, sponge.squeeze().to_dec_string()))
+        .collect();
+
+    let preimage: Vec<_> = preimage
+        .iter()
+        .map(|x| format!(r#""{}""#, x.to_dec_string()))
+        .collect();
+
+    let public_inputs = &format!(
+        r#"{{"digest": "{}", "outputs": [{}]}}"#,
+        digest.to_dec_string(),
+        outputs.join(", ")
+    );
+    let private_inputs = &format!(r#"{{"preimage": [{}]}}"#, preimage.join(", "));
+
+    test_file("hash", public_inputs, private_inputs, vec![], backend)?;
+
+    Ok(())
+}
+
+/// Returns the public and private inputs of the merkle example,
+/// which updates a leaf of a tree hashed with the poseidon parameters of the backend.
+fn merkle_inputs<F: MerkleField>() -> (String, String) {
+    let leaves: Vec<F> = (1..=6u64).map(F::from).collect();
+    let mut tree = MerkleTree::from_leaves(3, &leaves);
+
+    let root = tree.root();
+    let private_inputs = tree.update(5, F::from(42u64)).to_string();
+    let new_root = tree.root();
+
+    let public_inputs = format!(
+        r#"{{"root": "{}", "new_root": "{}"}}"#,
+        root.to_dec_string(),
+        new_root.to_dec_string()
+    );
+
+    (public_inputs, private_inputs)
+}
+
+#[rstest]
+#[case::kimchi_vesta(
+    BackendKind::KimchiVesta(KimchiVesta::new(false)),
+    merkle_inputs::<VestaField>()
+)]
+#[case::r1cs(
+    BackendKind::R1csBls12_381(R1CS::new()),
+    merkle_inputs::<R1csBls12381Field>()
+)]
+#[case::r1cs_bn254(
+    BackendKind::R1csBn254(R1CS::new()),
+    merkle_inputs::<R1csBn254Field>()
+)]
+fn test_merkle(
+    #[case] backend: BackendKind,
+    #[case] inputs: (String, String),
+) -> miette::Result<()> {
+    let (public_inputs, private_inputs) = inputs;
+
+    test_file("merkle", &public_inputs, &private_inputs, vec![], backend)?;
+
+    Ok(())
+}
+
+// `std::ec` is only available with kimchi
+#[rstest]
+#[case::kimchi_vesta(BackendKind::KimchiVesta(KimchiVesta::new(false)))]
+fn test_ec(#[case] backend: BackendKind) -> miette::Result<()> {
+    let public_inputs = r#"{"yy": "2"}"#;
+    let private_inputs = r#"{"scalar": "3"}"#;
+
+    test_file("ec", public_inputs, private_inputs, vec![], backend)?;
+
+    Ok(())
+}
+
+/// A message of 56 bytes, which needs two blocks once padded for SHA-256.
+const SHA256_PREIMAGE: &[u8; 56] = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+
+/// Returns the public and private inputs of the sha256 example.
+fn sha256_inputs(preimage: &[u8; 56]) -> (String, String) {
+    use sha2::{Digest, Sha256};
+
+    let digest = Sha256::digest(preimage);
+
+    let to_json = |bytes: &[u8]| {
+        let bytes: Vec<_> = bytes.iter().map(|byte| byte.to_string()).collect();
+        format!("[{}]", bytes.join(", "))
+    };
+    let public_inputs = format!(r#"{{"digest": {}}}"#, to_json(&digest));
+    let private_inputs = format!(r#"{{"preimage": {}}}"#, to_json(preimage.as_slice()));
+
+    (public_inputs, private_inputs)
+}
+
+#[rstest]
+#[case::r1cs(BackendKind::R1csBls12_381(R1CS::new()))]
+fn test_sha256(#[case] backend: BackendKind) -> miette::Result<()> {
+    let (public_inputs, private_inputs) = sha256_inputs(SHA256_PREIMAGE);
+
+    test_file("sha256", &public_inputs, &private_inputs, vec![], backend)?;
+
+    Ok(())
+}
+
+// the circuit is too large to create a kimchi proof in a test
+#[test]
+fn test_sha256_kimchi_witness() -> miette::Result<()> {
+    let (public_inputs, private_inputs) = sha256_inputs(SHA256_PREIMAGE);
+
+    test_kimchi_witness("sha256", &public_inputs, &private_inputs)?;
+
+    // the digest of another message is rejected
+    let mut other_preimage = *SHA256_PREIMAGE;
+    other_preimage[0] ^= 1;
+    let (_, other_private_inputs) = sha256_inputs(&other_preimage);
+    assert!(test_kimchi_witness("sha256", &public_inputs, &other_private_inputs).is_err());
+
+    Ok(())
+}
+
+/// A message of 32 bytes.
+const KECCAK256_PREIMAGE: [u8; 32] = *b"the quick brown fox jumps over a";
+
+/// Returns the public and private inputs of the keccak256 example.
+fn keccak256_inputs(preimage: &[u8; 32]) -> (String, String) {
+    use sha3::{Digest, Keccak256};
+
+    let digest = Keccak256::digest(preimage);
+
+    let to_json = |bytes: &[u8]| {
+        let bytes: Vec<_> = bytes.iter().map(|byte| byte.to_string()).collect();
+        format!("[{}]", bytes.join(", "))
+    };
+    let public_inputs = format!(r#"{{"digest": {}}}"#, to_json(&digest));
+    let private_inputs = format!(r#"{{"preimage": {}}}"#, to_json(preimage.as_slice()));
+
+    (public_inputs, private_inputs)
+}
+
+#[rstest]
+#[case::r1cs(BackendKind::R1csBls12_381(R1CS::new()))]
+fn test_keccak256(#[case] backend: BackendKind) -> miette::Result<()> {
+    let (public_inputs, private_inputs) = keccak256_inputs(&KECCAK256_PREIMAGE);
+
+    test_file(
+        "keccak256",
+        &public_inputs,
+        &private_inputs,
+        vec![],
+        backend,
+    )?;
+
+    Ok(())
+}
+
+// the circuit is too large to create a kimchi proof in a test
+#[test]
+fn test_keccak256_kimchi_witness() -> miette::Result<()> {
+    let (public_inputs, private_inputs) = keccak256_inputs(&KECCAK256_PREIMAGE);
+
+    test_kimchi_witness("keccak256", &public_inputs, &private_inputs)?;
+
+    // the digest of another message is rejected
+    let mut other_preimage = KECCAK256_PREIMAGE;
+    other_preimage[0] ^= 1;
+    let (_, other_private_inputs) = keccak256_inputs(&other_preimage);
+    assert!(test_kimchi_witness("keccak256", &public_inputs, &other_private_inputs).is_err());
+
+    Ok(())
+}
+
 #[rstest]
 #[case::kimchi_vesta(BackendKind::KimchiVesta(KimchiVesta::new(false)))]
 #[case::r1cs(BackendKind::R1csBls12_381(R1CS::new()))]
@@ -389,6 +652,25 @@ fn test_iterate(#[case] backend: BackendKind) -> miette::Result<()> {
     Ok(())
 }
 
+#[rstest]
+#[case::kimchi_vesta(BackendKind::KimchiVesta(KimchiVesta::new(false)))]
+#[case::r1cs(BackendKind::R1csBls12_381(R1CS::new()))]
+fn test_iterate_array(#[case] backend: BackendKind) -> miette::Result<()> {
+    let public_inputs =
+        r#"{"rooms": [{"beds": "1", "windows": "2"}, {"beds": "3", "windows": "4"}]}"#;
+    let private_inputs = r#"{"floors": [["5", "6"], ["7", "8"]]}"#;
+
+    test_file(
+        "iterate_array",
+        public_inputs,
+        private_inputs,
+        vec!["36"],
+        backend,
+    )?;
+
+    Ok(())
+}
+
 #[rstest]
 #[case::kimchi_vesta(BackendKind::KimchiVesta(KimchiVesta::new(false)))]
 #[case::r1cs(BackendKind::R1csBls12_381(R1CS::new()))]
@@ -520,3 +802,289 @@ fn test_dup_var(#[case] backend: BackendKind) -> miette::Result<()> {
 
     Ok(())
 }
+
+#[rstest]
+#[case::kimchi_vesta(BackendKind::KimchiVesta(KimchiVesta::new(false)))]
+#[case::r1cs(BackendKind::R1csBls12_381(R1CS::new()))]
+#[case::r1cs_bn254(BackendKind::R1csBn254(R1CS::new()))]
+fn test_division(#[case] backend: BackendKind) -> miette::Result<()> {
+    let public_inputs = r#"{"public_input": "2"}"#;
+    let private_inputs = r#"{"private_input": "6"}"#;
+
+    test_file("division", public_inputs, private_inputs, vec![], backend)?;
+
+    Ok(())
+}
+
+#[rstest]
+#[case::kimchi_vesta(BackendKind::KimchiVesta(KimchiVesta::new(false)))]
+#[case::r1cs(BackendKind::R1csBls12_381(R1CS::new()))]
+#[case::r1cs_bn254(BackendKind::R1csBn254(R1CS::new()))]
+fn test_negation(#[case] backend: BackendKind) -> miette::Result<()> {
+    let public_inputs = r#"{"public_input": "2"}"#;
+    let private_inputs = r#"{"private_input": "2"}"#;
+
+    test_file("negation", public_inputs, private_inputs, vec![], backend)?;
+
+    Ok(())
+}
+
+#[rstest]
+#[case::kimchi_vesta(BackendKind::KimchiVesta(KimchiVesta::new(false)))]
+#[case::r1cs(BackendKind::R1csBls12_381(R1CS::new()))]
+fn test_uint(#[case] backend: BackendKind) -> miette::Result<()> {
+    let public_inputs = r#"{"xx": "3"}"#;
+    let private_inputs = r#"{"yy": 4}"#;
+
+    test_file("uint", public_inputs, private_inputs, vec!["10"], backend)?;
+
+    Ok(())
+}
+
+#[rstest]
+#[case::kimchi_vesta(BackendKind::KimchiVesta(KimchiVesta::new(false)))]
+#[case::r1cs(BackendKind::R1csBls12_381(R1CS::new()))]
+fn test_comparison(#[case] backend: BackendKind) -> miette::Result<()> {
+    let public_inputs = r#"{"xx": "2"}"#;
+    let private_inputs = r#"{"yy": "5"}"#;
+
+    test_file("comparison", public_inputs, private_inputs, vec![], backend)?;
+
+    Ok(())
+}
+
+#[rstest]
+#[case::kimchi_vesta(BackendKind::KimchiVesta(KimchiVesta::new(false)))]
+#[case::r1cs(BackendKind::R1csBls12_381(R1CS::new()))]
+fn test_bits(#[case] backend: BackendKind) -> miette::Result<()> {
+    let public_inputs = r#"{"xx": "5"}"#;
+    let private_inputs = r#"{}"#;
+
+    test_file("bits", public_inputs, private_inputs, vec!["5"], backend)?;
+
+    Ok(())
+}
+
+#[rstest]
+#[case::kimchi_vesta(BackendKind::KimchiVesta(KimchiVesta::new(false)))]
+#[case::r1cs(BackendKind::R1csBls12_381(R1CS::new()))]
+fn test_bitwise(#[case] backend: BackendKind) -> miette::Result<()> {
+    let public_inputs = r#"{"xx": "3000000000"}"#;
+    let private_inputs = r#"{"yy": "123456789"}"#;
+
+    test_file(
+        "bitwise",
+        public_inputs,
+        private_inputs,
+        vec!["1005997736"],
+        backend,
+    )?;
+
+    Ok(())
+}
+
+#[rstest]
+#[case::kimchi_vesta(BackendKind::KimchiVesta(KimchiVesta::new(false)))]
+#[case::r1cs(BackendKind::R1csBls12_381(R1CS::new()))]
+fn test_dynamic_index(#[case] backend: BackendKind) -> miette::Result<()> {
+    let public_inputs = r#"{"arr": ["3", "7"]}"#;
+    let private_inputs = r#"{"idx": "1"}"#;
+
+    test_file(
+        "dynamic_index",
+        public_inputs,
+        private_inputs,
+        vec!["7"],
+        backend,
+    )?;
+
+    Ok(())
+}
+
+#[rstest]
+#[case::kimchi_vesta(BackendKind::KimchiVesta(KimchiVesta::new(false)))]
+#[case::r1cs(BackendKind::R1csBls12_381(R1CS::new()))]
+fn test_dynamic_index_struct(#[case] backend: BackendKind) -> miette::Result<()> {
+    let public_inputs =
+        r#"{"things": [{"xx": "1", "yy": "2"}, {"xx": "3", "yy": "4"}, {"xx": "5", "yy": "6"}]}"#;
+    let private_inputs = r#"{"idx": "2"}"#;
+
+    test_file(
+        "dynamic_index_struct",
+        public_inputs,
+        private_inputs,
+        vec!["11"],
+        backend,
+    )?;
+
+    Ok(())
+}
+
+#[rstest]
+#[case::kimchi_vesta(BackendKind::KimchiVesta(KimchiVesta::new(false)))]
+#[case::r1cs(BackendKind::R1csBls12_381(R1CS::new()))]
+fn test_generic_array(#[case] backend: BackendKind) -> miette::Result<()> {
+    let public_inputs = r#"{"xx": ["1", "2", "3"]}"#;
+    let private_inputs = r#"{"yy": ["4", "5"]}"#;
+
+    test_file(
+        "generic_array",
+        public_inputs,
+        private_inputs,
+        vec!["16"],
+        backend,
+    )?;
+
+    Ok(())
+}
+
+#[rstest]
+#[case::kimchi_vesta(BackendKind::KimchiVesta(KimchiVesta::new(false)))]
+#[case::r1cs(BackendKind::R1csBls12_381(R1CS::new()))]
+fn test_const_expr(#[case] backend: BackendKind) -> miette::Result<()> {
+    let public_inputs = r#"{"xx": ["1", "2", "3", "4"]}"#;
+    let private_inputs = r#"{"yy": ["5", "6", "7"]}"#;
+
+    test_file(
+        "const_expr",
+        public_inputs,
+        private_inputs,
+        vec!["28"],
+        backend,
+    )?;
+
+    Ok(())
+}
+
+#[rstest]
+#[case::kimchi_vesta(BackendKind::KimchiVesta(KimchiVesta::new(false)))]
+#[case::r1cs(BackendKind::R1csBls12_381(R1CS::new()))]
+fn test_compound_assignment(#[case] backend: BackendKind) -> miette::Result<()> {
+    let public_inputs = r#"{"xx": "3"}"#;
+    let private_inputs = r#"{"yy": "4"}"#;
+
+    test_file(
+        "compound_assignment",
+        public_inputs,
+        private_inputs,
+        vec!["56"],
+        backend,
+    )?;
+
+    Ok(())
+}
+
+#[rstest]
+#[case::kimchi_vesta(BackendKind::KimchiVesta(KimchiVesta::new(false)))]
+#[case::r1cs(BackendKind::R1csBls12_381(R1CS::new()))]
+fn test_tuple(#[case] backend: BackendKind) -> miette::Result<()> {
+    let public_inputs = r#"{"pair": ["2", "3"]}"#;
+    let private_inputs = r#"{"yy": "4"}"#;
+
+    test_file(
+        "tuple",
+        public_inputs,
+        private_inputs,
+        vec!["14", "3"],
+        backend,
+    )?;
+
+    Ok(())
+}
+
+#[rstest]
+#[case::kimchi_vesta(BackendKind::KimchiVesta(KimchiVesta::new(false)))]
+#[case::r1cs(BackendKind::R1csBls12_381(R1CS::new()))]
+fn test_enums(#[case] backend: BackendKind) -> miette::Result<()> {
+    let public_inputs = r#"{"xx": "2"}"#;
+    let private_inputs = r#"{"yy": "3"}"#;
+
+    test_file("enums", public_inputs, private_inputs, vec!["10"], backend)?;
+
+    Ok(())
+}
+
+#[rstest]
+#[case::kimchi_vesta(BackendKind::KimchiVesta(KimchiVesta::new(false)))]
+#[case::r1cs(BackendKind::R1csBls12_381(R1CS::new()))]
+fn test_if_statement(#[case] backend: BackendKind) -> miette::Result<()> {
+    let public_inputs = r#"{"xx": "2"}"#;
+    let private_inputs = r#"{"flag": true}"#;
+
+    test_file(
+        "if_statement",
+        public_inputs,
+        private_inputs,
+        vec!["3"],
+        backend,
+    )?;
+
+    Ok(())
+}
+
+#[rstest]
+#[case::kimchi_vesta(BackendKind::KimchiVesta(KimchiVesta::new(false)))]
+#[case::r1cs(BackendKind::R1csBls12_381(R1CS::new()))]
+fn test_if_statement_else(#[case] backend: BackendKind) -> miette::Result<()> {
+    // the assertion of the block that is not taken is not enforced
+    let public_inputs = r#"{"xx": "5"}"#;
+    let private_inputs = r#"{"flag": false}"#;
+
+    test_file(
+        "if_statement",
+        public_inputs,
+        private_inputs,
+        vec!["15"],
+        backend,
+    )?;
+
+    Ok(())
+}
+
+#[rstest]
+#[case::kimchi_vesta(BackendKind::KimchiVesta(KimchiVesta::new(false)))]
+#[case::r1cs(BackendKind::R1csBls12_381(R1CS::new()))]
+fn test_if_statement_select(#[case] backend: BackendKind) -> miette::Result<()> {
+    let public_inputs = r#"{"xx": "2"}"#;
+    let private_inputs = r#"{"yy": "3", "flag": true}"#;
+
+    test_file(
+        "if_statement_select",
+        public_inputs,
+        private_inputs,
+        vec!["3", "2"],
+        backend,
+    )?;
+
+    Ok(())
+}
+
+#[rstest]
+#[case::kimchi_vesta(BackendKind::KimchiVesta(KimchiVesta::new(false)))]
+#[case::r1cs(BackendKind::R1csBls12_381(R1CS::new()))]
+fn test_if_statement_select_else(#[case] backend: BackendKind) -> miette::Result<()> {
+    let public_inputs = r#"{"xx": "2"}"#;
+    let private_inputs = r#"{"yy": "3", "flag": false}"#;
+
+    test_file(
+        "if_statement_select",
+        public_inputs,
+        private_inputs,
+        vec!["2", "5"],
+        backend,
+    )?;
+
+    Ok(())
+}
+
+#[rstest]
+#[case::kimchi_vesta(BackendKind::KimchiVesta(KimchiVesta::new(false)))]
+#[case::r1cs(BackendKind::R1csBls12_381(R1CS::new()))]
+fn test_hint(#[case] backend: BackendKind) -> miette::Result<()> {
+    let public_inputs = r#"{"xx": "2"}"#;
+    let private_inputs = r#"{"yy": "42"}"#;
+
+    test_file("hint", public_inputs, private_inputs, vec!["2"], backend)?;
+
+    Ok(())
+}
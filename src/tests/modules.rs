@@ -1,6 +1,6 @@
 use crate::{
     backends::kimchi::KimchiVesta,
-    cli::packages::UserRepo,
+    cli::packages::{QualifiedModule, UserRepo},
     compiler::{compile, typecheck_next_file, Sources},
     type_checker::TypeChecker,
 };
@@ -15,7 +15,7 @@ struct Lol {
     aa: Field,
 }
 
-fn Lol.match(self, bb: Field) {
+fn Lol.check(self, bb: Field) {
     assert_eq(self.aa, bb);
 }
 
@@ -58,7 +58,7 @@ fn new_liblib() -> liblib::Lol {
 }
 
 fn test_liblib(ff: Field, lol: liblib::Lol) {
-    lol.match(ff);
+    lol.check(ff);
 }
 "#;
 
@@ -90,7 +90,7 @@ fn test_simple_module() -> miette::Result<()> {
     let mut node_id = 0;
     node_id = typecheck_next_file(
         &mut tast,
-        Some(UserRepo::new("mimoo/liblib")),
+        Some(UserRepo::new("mimoo/liblib").into()),
         &mut sources,
         "liblib.no".to_string(),
         LIBLIB.to_string(),
@@ -100,7 +100,7 @@ fn test_simple_module() -> miette::Result<()> {
     // parse the lib
     node_id = typecheck_next_file(
         &mut tast,
-        Some(UserRepo::new("mimoo/lib")),
+        Some(UserRepo::new("mimoo/lib").into()),
         &mut sources,
         "lib.no".to_string(),
         LIB.to_string(),
@@ -125,3 +125,92 @@ fn test_simple_module() -> miette::Result<()> {
 
     Ok(())
 }
+
+//
+// MAIN -> SELF::UTILS -> SELF::CONSTANTS
+//
+
+const CONSTANTS: &str = "
+const two = 2;
+
+struct Pair {
+    aa: Field,
+    bb: Field,
+}
+
+fn new_pair(aa: Field, bb: Field) -> Pair {
+    return Pair { aa: aa, bb: bb };
+}
+";
+
+const UTILS: &str = "
+use self::constants;
+
+fn double(xx: Field) -> Field {
+    return xx * constants::two;
+}
+
+fn pair(xx: Field) -> constants::Pair {
+    return constants::new_pair(xx, double(xx));
+}
+";
+
+const MAIN_WITH_SUBMODULES: &str = r#"
+use self::constants;
+use self::utils;
+
+fn main(pub xx: Field) {
+    let pair = utils::pair(xx);
+    assert_eq(pair.bb, utils::double(pair.aa));
+    assert_eq(pair.aa * constants::two, 2);
+}
+"#;
+
+#[test]
+fn test_submodules() -> miette::Result<()> {
+    let mut sources = Sources::new();
+
+    // the modules of a package are type checked before the files that import them
+    let mut tast = TypeChecker::<KimchiVesta>::new();
+    let mut node_id = 0;
+    node_id = typecheck_next_file(
+        &mut tast,
+        Some(QualifiedModule::Submodule {
+            package: None,
+            name: "constants".to_string(),
+        }),
+        &mut sources,
+        "constants.no".to_string(),
+        CONSTANTS.to_string(),
+        node_id,
+    )?;
+
+    node_id = typecheck_next_file(
+        &mut tast,
+        Some(QualifiedModule::Submodule {
+            package: None,
+            name: "utils".to_string(),
+        }),
+        &mut sources,
+        "utils.no".to_string(),
+        UTILS.to_string(),
+        node_id,
+    )?;
+
+    typecheck_next_file(
+        &mut tast,
+        None,
+        &mut sources,
+        "main.no".to_string(),
+        MAIN_WITH_SUBMODULES.to_string(),
+        node_id,
+    )?;
+
+    // backend
+    let kimchi_vesta = KimchiVesta::new(false);
+
+    // compile
+    compile(&sources, tast, kimchi_vesta)?;
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -8,9 +9,13 @@ use crate::{
     error::{ErrorKind, Result},
     imports::FnKind,
     parser::{
-        types::{FnSig, FunctionDef, Stmt, StmtKind, Ty, TyKind},
-        CustomType, Expr, ExprKind, Op2,
+        types::{
+            ConstExpr, FnSig, ForLoopArgument, FuncOrMethod, FunctionDef, ModulePath, Stmt,
+            StmtKind, Ty, TyKind,
+        },
+        CustomType, Expr, ExprKind, MatchPattern, Op2,
     },
+    stdlib::BUILTIN_FN_NAMES,
     syntax::is_type,
 };
 
@@ -43,6 +48,25 @@ pub struct StructInfo {
     pub methods: HashMap<String, FunctionDef>,
 }
 
+/// Keeps track of the variants of a user-defined enum.
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct EnumInfo {
+    pub name: String,
+    /// The variants, and the types of the values they hold.
+    /// The tag of a variant is its position in this list.
+    pub variants: Vec<(String, Vec<TyKind>)>,
+}
+
+impl EnumInfo {
+    /// Returns the tag and the types of the values of a variant.
+    pub fn variant(&self, name: &str) -> Option<(usize, &[TyKind])> {
+        self.variants
+            .iter()
+            .position(|(variant, _)| variant == name)
+            .map(|tag| (tag, self.variants[tag].1.as_slice()))
+    }
+}
+
 /// Information that we need to pass around between expression nodes when type checking.
 #[derive(Debug)]
 struct ExprTyInfo {
@@ -116,6 +140,29 @@ impl<B: Backend> TypeChecker<B> {
                 }
             }
 
+            ExprKind::TupleAccess { tuple, idx } => {
+                // compute type of the tuple
+                let tuple_node = self
+                    .compute_type(tuple, typed_fn_env)?
+                    .expect("type-checker bug: tuple access on an empty var");
+
+                // obtain the type of the element
+                let elem_typ = match &tuple_node.typ {
+                    TyKind::Tuple(types) => types.get(*idx).cloned(),
+                    _ => None,
+                };
+
+                match elem_typ {
+                    Some(typ) => Some(ExprTyInfo::new(tuple_node.var_name, typ)),
+                    None => {
+                        return Err(self.error(
+                            ErrorKind::InvalidTupleIndex(*idx, tuple_node.typ),
+                            expr.span,
+                        ))
+                    }
+                }
+            }
+
             // `module::fn_name(args)`
             ExprKind::FnCall {
                 module,
@@ -132,9 +179,59 @@ impl<B: Backend> TypeChecker<B> {
                 })?;
                 let fn_sig = fn_info.sig().clone();
 
+                // the output of a hint is not constrained, so it must be stored in a variable
+                // to make sure that it is checked
+                let hint_call_allowed = typed_fn_env.take_hint_call_allowed();
+                let is_hint_call = matches!(&fn_info.kind, FnKind::Native(func) if func.is_hint);
+                if is_hint_call && !typed_fn_env.is_hint() && !hint_call_allowed {
+                    return Err(self.error(ErrorKind::HintCallOutsideLet, expr.span));
+                }
+
+                // hints are interpreted when generating the witness,
+                // so they can only call functions written in noname, and the builtins
+                if typed_fn_env.is_hint() {
+                    if let FnKind::BuiltIn(sig, _) = &fn_info.kind {
+                        if !BUILTIN_FN_NAMES.contains(&sig.name.value) {
+                            return Err(self.error(
+                                ErrorKind::InvalidHintCall(fn_name.value.clone()),
+                                expr.span,
+                            ));
+                        }
+                    }
+                }
+
+                // in an `if` statement, only the builtins that don't check anything on their own can be called
+                // (the assertions of `assert` and `assert_eq` are made conditional when compiling the circuit)
+                if typed_fn_env.is_conditional() {
+                    match &fn_info.kind {
+                        FnKind::Native(_) => {
+                            return Err(self.error(ErrorKind::CallInIfStatement, expr.span))
+                        }
+                        FnKind::BuiltIn(sig, _) if sig.name.value == "to_bits" => {
+                            return Err(self.error(
+                                ErrorKind::UnconditionalConstraints("`to_bits`"),
+                                expr.span,
+                            ))
+                        }
+                        FnKind::BuiltIn(..) => (),
+                    }
+                }
+
+                // the values read in the arguments of an assertion are checked
+                let is_assertion = matches!(
+                    &fn_info.kind,
+                    FnKind::BuiltIn(sig, _) if ["assert", "assert_eq"].contains(&sig.name.value.as_str())
+                );
+
                 // type check the function call
                 let method_call = false;
+                if is_assertion {
+                    typed_fn_env.enter_assertion();
+                }
                 let res = self.check_fn_call(typed_fn_env, method_call, fn_sig, args, expr.span)?;
+                if is_assertion {
+                    typed_fn_env.exit_assertion();
+                }
 
                 res.map(ExprTyInfo::new_anon)
             }
@@ -145,6 +242,10 @@ impl<B: Backend> TypeChecker<B> {
                 method_name,
                 args,
             } => {
+                if typed_fn_env.is_conditional() {
+                    return Err(self.error(ErrorKind::CallInIfStatement, expr.span));
+                }
+
                 // retrieve struct name on the lhs
                 let lhs_type = self.compute_type(lhs, typed_fn_env)?;
                 let (module, struct_name) = match lhs_type.map(|t| t.typ) {
@@ -184,66 +285,7 @@ impl<B: Backend> TypeChecker<B> {
             }
 
             ExprKind::Assignment { lhs, rhs } => {
-                // compute type of lhs
-                let lhs_node = self
-                    .compute_type(lhs, typed_fn_env)?
-                    .expect("type-checker bug: lhs access on an empty var");
-
-                // lhs can be a local variable or a path to an array
-                let lhs_name = match &lhs.kind {
-                    // `name = <rhs>`
-                    ExprKind::Variable { module, name } => {
-                        // we first check if it's a constant
-                        // note: the only way to check that atm is to check in the constants hashmap
-                        // this is because we don't differentiate const vars from normal variables
-                        // (perhaps we should)
-                        let qualified = FullyQualified::new(&module, &name.value);
-                        if let Some(_cst_info) = self.const_info(&qualified) {
-                            return Err(self.error(
-                                ErrorKind::UnexpectedError("cannot assign to an external variable"),
-                                lhs.span,
-                            ));
-                        }
-
-                        name.value.clone()
-                    }
-
-                    // `array[idx] = <rhs>`
-                    ExprKind::ArrayAccess { array, idx } => {
-                        // get variable behind array
-                        let array_node = self
-                            .compute_type(array, typed_fn_env)?
-                            .expect("type-checker bug: array access on an empty var");
-
-                        array_node
-                            .var_name
-                            .expect("anonymous array access cannot be mutated")
-                    }
-
-                    // `struct.field = <rhs>`
-                    ExprKind::FieldAccess { lhs, rhs } => {
-                        // get variable behind lhs
-                        let lhs_node = self
-                            .compute_type(lhs, typed_fn_env)?
-                            .expect("type-checker bug: lhs access on an empty var");
-
-                        lhs_node
-                            .var_name
-                            .expect("anonymous lhs access cannot be mutated")
-                    }
-                    _ => panic!("bad expression assignment (TODO: replace with error)"),
-                };
-
-                // check that the var exists locally
-                let lhs_info = typed_fn_env
-                    .get_type_info(&lhs_name)
-                    .expect("variable not found (TODO: replace with error")
-                    .clone();
-
-                // and is mutable
-                if !lhs_info.mutable {
-                    return Err(self.error(ErrorKind::AssignmentToImmutableVariable, expr.span));
-                }
+                let lhs_node = self.check_assignment_lhs(lhs, typed_fn_env, expr.span)?;
 
                 // and is of the same type as the rhs
                 let rhs_typ = self.compute_type(rhs, typed_fn_env)?.unwrap();
@@ -254,6 +296,31 @@ impl<B: Backend> TypeChecker<B> {
                         expr.span,
                     ));
                 }
+                self.check_uint_constants(Some(rhs), &rhs_typ.typ, &lhs_node.typ, rhs.span)?;
+
+                None
+            }
+
+            ExprKind::CompoundAssignment { op, lhs, rhs } => {
+                let lhs_node = self.check_assignment_lhs(lhs, typed_fn_env, expr.span)?;
+                let rhs_node = self
+                    .compute_type(rhs, typed_fn_env)?
+                    .expect("type-checker bug");
+
+                // `lhs += rhs` has the type of `lhs + rhs`, which must be the type of `lhs`
+                let typ = self.binary_op_type(
+                    op,
+                    lhs_node.typ.clone(),
+                    rhs_node.typ,
+                    typed_fn_env,
+                    expr.span,
+                )?;
+                if !typ.match_expected(&lhs_node.typ) {
+                    return Err(self.error(
+                        ErrorKind::MismatchType(lhs_node.typ.clone(), typ),
+                        expr.span,
+                    ));
+                }
 
                 None
             }
@@ -266,29 +333,8 @@ impl<B: Backend> TypeChecker<B> {
                     .compute_type(rhs, typed_fn_env)?
                     .expect("type-checker bug");
 
-                if lhs_node.typ != rhs_node.typ {
-                    // only allow bigint mixed with field
-                    match (&lhs_node.typ, &rhs_node.typ) {
-                        (TyKind::BigInt, TyKind::Field) | (TyKind::Field, TyKind::BigInt) => (),
-                        _ => {
-                            return Err(self.error(
-                                ErrorKind::MismatchType(lhs_node.typ.clone(), rhs_node.typ.clone()),
-                                expr.span,
-                            ))
-                        }
-                    }
-                }
-
-                let typ = match op {
-                    Op2::Equality => TyKind::Bool,
-                    Op2::Inequality => TyKind::Bool,
-                    Op2::Addition
-                    | Op2::Subtraction
-                    | Op2::Multiplication
-                    | Op2::Division
-                    | Op2::BoolAnd
-                    | Op2::BoolOr => lhs_node.typ,
-                };
+                let typ =
+                    self.binary_op_type(op, lhs_node.typ, rhs_node.typ, typed_fn_env, expr.span)?;
 
                 Some(ExprTyInfo::new_anon(typ))
             }
@@ -353,12 +399,8 @@ impl<B: Backend> TypeChecker<B> {
                             .get_type(&name.value)
                             .ok_or_else(|| self.error(ErrorKind::UndefinedVariable, name.span))?
                             .clone();
-                        // if it's a field, we need to convert it to a bigint
-                        if matches!(typ, TyKind::Field) {
-                            TyKind::BigInt
-                        } else {
-                            typ
-                        }
+                        typed_fn_env.use_var(&name.value);
+                        typ
                     };
 
                     let res = ExprTyInfo::new_var(name.value.clone(), typ);
@@ -371,24 +413,43 @@ impl<B: Backend> TypeChecker<B> {
                 let typ = self.compute_type(array, typed_fn_env)?.unwrap();
 
                 // check that it is an array
-                if !matches!(typ.typ, TyKind::Array(..)) {
+                if !matches!(typ.typ, TyKind::Array(..) | TyKind::GenericSizedArray(..)) {
                     return Err(self.error(ErrorKind::ArrayAccessOnNonArray, expr.span));
                 }
 
-                // check that expression is a bigint
+                // check that the index is a constant, or a value computed in the circuit
                 let idx_typ = self.compute_type(idx, typed_fn_env)?;
-                match idx_typ.map(|t| t.typ) {
-                    Some(TyKind::BigInt) => (),
-                    _ => return Err(self.error(ErrorKind::ExpectedConstant, expr.span)),
+                let idx_typ = idx_typ.expect("expected a value").typ;
+                let constant_idx = match idx_typ {
+                    TyKind::BigInt => true,
+                    TyKind::Field => false,
+                    ref typ if typ.uint_bits().is_some() => false,
+                    typ => return Err(self.error(ErrorKind::InvalidArrayIndex(typ), idx.span)),
                 };
 
+                // (the element selected at runtime must exist, which can't be made conditional)
+                if !constant_idx && typed_fn_env.is_conditional() {
+                    return Err(self.error(
+                        ErrorKind::UnconditionalConstraints(
+                            "an array access at an index computed in the circuit",
+                        ),
+                        expr.span,
+                    ));
+                }
+
                 // get type of element
                 let el_typ = match typ.typ {
-                    TyKind::Array(typkind, _) => *typkind,
+                    TyKind::Array(typkind, _) | TyKind::GenericSizedArray(typkind, _) => *typkind,
                     _ => panic!("not an array"),
                 };
 
-                let res = ExprTyInfo::new(typ.var_name, el_typ);
+                // an element selected at runtime is not a pointer into the array
+                // (except in hints, where it can be assigned)
+                let res = if constant_idx || typed_fn_env.is_hint() {
+                    ExprTyInfo::new(typ.var_name, el_typ)
+                } else {
+                    ExprTyInfo::new_anon(el_typ)
+                };
                 Some(res)
             }
 
@@ -420,6 +481,19 @@ impl<B: Backend> TypeChecker<B> {
                 Some(res)
             }
 
+            ExprKind::TupleDeclaration(items) => {
+                let mut types = vec![];
+
+                for item in items {
+                    let item_typ = self
+                        .compute_type(item, typed_fn_env)?
+                        .ok_or_else(|| self.error(ErrorKind::CannotComputeExpression, item.span))?;
+                    types.push(item_typ.typ);
+                }
+
+                Some(ExprTyInfo::new_anon(TyKind::Tuple(types)))
+            }
+
             ExprKind::IfElse { cond, then_, else_ } => {
                 // cond can only be a boolean
                 let cond_node = self
@@ -467,6 +541,183 @@ impl<B: Backend> TypeChecker<B> {
                 Some(ExprTyInfo::new_anon(then_node.typ))
             }
 
+            ExprKind::EnumVariant {
+                enum_name,
+                variant,
+                args,
+            } => {
+                let CustomType {
+                    module,
+                    name,
+                    span: _,
+                } = enum_name;
+                let qualified = FullyQualified::new(module, name);
+                let enum_info = self
+                    .enum_info(&qualified)
+                    .ok_or_else(|| self.error(ErrorKind::UndefinedEnum(name.clone()), expr.span))?;
+
+                let (_tag, types) = enum_info.variant(&variant.value).ok_or_else(|| {
+                    self.error(
+                        ErrorKind::UndefinedVariant(name.clone(), variant.value.clone()),
+                        variant.span,
+                    )
+                })?;
+                let types = types.to_vec();
+
+                if types.len() != args.len() {
+                    return Err(self.error(
+                        ErrorKind::MismatchVariantValues(
+                            variant.value.clone(),
+                            types.len(),
+                            args.len(),
+                        ),
+                        expr.span,
+                    ));
+                }
+
+                for (expected, arg) in types.iter().zip(args) {
+                    let arg_typ = self
+                        .compute_type(arg, typed_fn_env)?
+                        .ok_or_else(|| self.error(ErrorKind::CannotComputeExpression, arg.span))?;
+
+                    if !arg_typ.typ.match_expected(expected) {
+                        return Err(self.error(
+                            ErrorKind::ArgumentTypeMismatch(expected.clone(), arg_typ.typ),
+                            arg.span,
+                        ));
+                    }
+                    self.check_uint_constants(Some(arg), &arg_typ.typ, expected, arg.span)?;
+                }
+
+                let res = ExprTyInfo::new_anon(TyKind::Custom {
+                    module: module.clone(),
+                    name: name.clone(),
+                });
+                Some(res)
+            }
+
+            ExprKind::Match { matched, arms } => {
+                // we can only match on enums
+                let matched_typ = self
+                    .compute_type(matched, typed_fn_env)?
+                    .ok_or_else(|| self.error(ErrorKind::CannotComputeExpression, matched.span))?
+                    .typ;
+                let enum_info = match &matched_typ {
+                    TyKind::Custom { module, name } => {
+                        self.enum_info(&FullyQualified::new(module, name)).cloned()
+                    }
+                    _ => None,
+                }
+                .ok_or_else(|| {
+                    self.error(ErrorKind::MatchOnNonEnum(matched_typ.clone()), matched.span)
+                })?;
+                let matched_enum = match &matched_typ {
+                    TyKind::Custom { module, name } => FullyQualified::new(module, name),
+                    _ => unreachable!(),
+                };
+
+                let mut covered = vec![false; enum_info.variants.len()];
+                let mut arms_typ: Option<TyKind> = None;
+
+                for arm in arms {
+                    // every arm must cover at least one variant that wasn't covered before
+                    if covered.iter().all(|covered| *covered) {
+                        return Err(self.error(ErrorKind::UnreachableMatchArm, arm.pattern.span()));
+                    }
+
+                    typed_fn_env.nest();
+
+                    match &arm.pattern {
+                        MatchPattern::Variant {
+                            enum_name,
+                            variant,
+                            bindings,
+                        } => {
+                            // the variant must belong to the enum we're matching on
+                            let qualified = FullyQualified::new(&enum_name.module, &enum_name.name);
+                            if qualified != matched_enum {
+                                return Err(self.error(
+                                    ErrorKind::MismatchMatchPattern(
+                                        matched_typ.clone(),
+                                        enum_name.name.clone(),
+                                    ),
+                                    arm.pattern.span(),
+                                ));
+                            }
+
+                            let (tag, types) =
+                                enum_info.variant(&variant.value).ok_or_else(|| {
+                                    self.error(
+                                        ErrorKind::UndefinedVariant(
+                                            enum_info.name.clone(),
+                                            variant.value.clone(),
+                                        ),
+                                        variant.span,
+                                    )
+                                })?;
+
+                            if covered[tag] {
+                                return Err(
+                                    self.error(ErrorKind::UnreachableMatchArm, arm.pattern.span())
+                                );
+                            }
+                            covered[tag] = true;
+
+                            // the values held by the variant are bound to new variables
+                            if types.len() != bindings.len() {
+                                return Err(self.error(
+                                    ErrorKind::MismatchVariantValues(
+                                        variant.value.clone(),
+                                        types.len(),
+                                        bindings.len(),
+                                    ),
+                                    arm.pattern.span(),
+                                ));
+                            }
+
+                            for (binding, typ) in bindings.iter().zip(types) {
+                                if let Some(binding) = binding {
+                                    typed_fn_env.store_type(
+                                        binding.value.clone(),
+                                        TypeInfo::new(typ.clone(), binding.span),
+                                    )?;
+                                }
+                            }
+                        }
+                        MatchPattern::Wildcard(_) => covered.fill(true),
+                    }
+
+                    let arm_typ = self
+                        .compute_type(&arm.expr, typed_fn_env)?
+                        .ok_or_else(|| self.error(ErrorKind::CannotComputeExpression, arm.span))?
+                        .typ;
+
+                    typed_fn_env.pop();
+
+                    // all arms must have the same type
+                    // (the type of non-constant values wins over the type of constants)
+                    arms_typ = match arms_typ {
+                        None => Some(arm_typ),
+                        Some(typ) if !typ.same_as(&arm_typ) => {
+                            return Err(self
+                                .error(ErrorKind::MatchArmsTypeMismatch(typ, arm_typ), arm.span))
+                        }
+                        Some(TyKind::BigInt) => Some(arm_typ),
+                        Some(typ) => Some(typ),
+                    };
+                }
+
+                // all variants must be covered
+                if let Some(tag) = covered.iter().position(|covered| !covered) {
+                    return Err(self.error(
+                        ErrorKind::NonExhaustiveMatch(enum_info.variants[tag].0.clone()),
+                        expr.span,
+                    ));
+                }
+
+                arms_typ.map(ExprTyInfo::new_anon)
+            }
+
             ExprKind::CustomTypeDeclaration { custom, fields } => {
                 let CustomType {
                     module,
@@ -507,6 +758,12 @@ impl<B: Backend> TypeChecker<B> {
                             expr.span,
                         ));
                     }
+                    self.check_uint_constants(
+                        Some(&observed.1),
+                        &observed_typ.typ,
+                        &defined.1,
+                        observed.1.span,
+                    )?;
                 }
 
                 let res = ExprTyInfo::new_anon(TyKind::Custom {
@@ -526,6 +783,204 @@ impl<B: Backend> TypeChecker<B> {
         Ok(typ)
     }
 
+    /// Checks the left-hand side of an assignment:
+    /// it must be a place (a variable, or a field or element of one) of a mutable variable.
+    /// Returns the type of the place.
+    fn check_assignment_lhs(
+        &mut self,
+        lhs: &Expr,
+        typed_fn_env: &mut TypedFnEnv,
+        span: Span,
+    ) -> Result<ExprTyInfo> {
+        // compute type of lhs
+        let lhs_node = self
+            .compute_type(lhs, typed_fn_env)?
+            .expect("type-checker bug: lhs access on an empty var");
+
+        // lhs can be a local variable or a path to an array
+        let lhs_name = match &lhs.kind {
+            // `name = <rhs>`
+            ExprKind::Variable { module, name } => {
+                // we first check if it's a constant
+                // note: the only way to check that atm is to check in the constants hashmap
+                // this is because we don't differentiate const vars from normal variables
+                // (perhaps we should)
+                let qualified = FullyQualified::new(&module, &name.value);
+                if let Some(_cst_info) = self.const_info(&qualified) {
+                    return Err(self.error(
+                        ErrorKind::UnexpectedError("cannot assign to an external variable"),
+                        lhs.span,
+                    ));
+                }
+
+                name.value.clone()
+            }
+
+            // `array[idx] = <rhs>`
+            ExprKind::ArrayAccess { array, idx } => {
+                // only elements at constant indexes can be mutated
+                // (except in hints, which are not compiled in the circuit)
+                let idx_typ = self.compute_type(idx, typed_fn_env)?;
+                if !typed_fn_env.is_hint()
+                    && !matches!(idx_typ.map(|t| t.typ), Some(TyKind::BigInt))
+                {
+                    return Err(self.error(ErrorKind::DynamicIndexAssignment, idx.span));
+                }
+
+                // get variable behind array
+                let array_node = self
+                    .compute_type(array, typed_fn_env)?
+                    .expect("type-checker bug: array access on an empty var");
+
+                array_node
+                    .var_name
+                    .expect("anonymous array access cannot be mutated")
+            }
+
+            // `struct.field = <rhs>`
+            ExprKind::FieldAccess { lhs, rhs } => {
+                // get variable behind lhs
+                let lhs_node = self
+                    .compute_type(lhs, typed_fn_env)?
+                    .expect("type-checker bug: lhs access on an empty var");
+
+                lhs_node
+                    .var_name
+                    .expect("anonymous lhs access cannot be mutated")
+            }
+            _ => panic!("bad expression assignment (TODO: replace with error)"),
+        };
+
+        // check that the var exists locally
+        let lhs_info = typed_fn_env
+            .get_type_info(&lhs_name)
+            .expect("variable not found (TODO: replace with error")
+            .clone();
+
+        // and is mutable
+        if !lhs_info.mutable {
+            return Err(self.error(ErrorKind::AssignmentToImmutableVariable, span));
+        }
+
+        Ok(lhs_node)
+    }
+
+    /// Returns the type of a binary operation on operands of the given types.
+    fn binary_op_type(
+        &self,
+        op: &Op2,
+        lhs_typ: TyKind,
+        rhs_typ: TyKind,
+        typed_fn_env: &TypedFnEnv,
+        span: Span,
+    ) -> Result<TyKind> {
+        if lhs_typ != rhs_typ {
+            // only allow bigint mixed with field or unsigned integers
+            match (&lhs_typ, &rhs_typ) {
+                (TyKind::BigInt, TyKind::Field) | (TyKind::Field, TyKind::BigInt) => (),
+                (TyKind::BigInt, uint) | (uint, TyKind::BigInt) if uint.uint_bits().is_some() => {}
+                _ => {
+                    return Err(self.error(
+                        ErrorKind::MismatchType(lhs_typ.clone(), rhs_typ.clone()),
+                        span,
+                    ))
+                }
+            }
+        }
+
+        // the check that a divisor is not zero can't be made conditional
+        if matches!(op, Op2::Division)
+            && !matches!(rhs_typ, TyKind::BigInt)
+            && typed_fn_env.is_conditional()
+        {
+            return Err(self.error(
+                ErrorKind::UnconditionalConstraints(
+                    "a division by a value computed in the circuit",
+                ),
+                span,
+            ));
+        }
+
+        // the type of the other operand wins over the type of constants
+        let operand_typ = if matches!(lhs_typ, TyKind::BigInt) {
+            rhs_typ
+        } else {
+            lhs_typ
+        };
+
+        // unsigned integers only support overflow-checked arithmetic
+        if operand_typ.uint_bits().is_some()
+            && matches!(op, Op2::Division | Op2::BoolAnd | Op2::BoolOr)
+        {
+            return Err(self.error(ErrorKind::UnsupportedUintOperation(operand_typ), span));
+        }
+
+        // and overflow checks can't be made conditional
+        if operand_typ.uint_bits().is_some()
+            && matches!(op, Op2::Addition | Op2::Subtraction | Op2::Multiplication)
+            && typed_fn_env.is_conditional()
+        {
+            return Err(self.error(
+                ErrorKind::UnconditionalConstraints(
+                    "overflow-checked arithmetic on unsigned integers",
+                ),
+                span,
+            ));
+        }
+
+        // comparisons are only sound on bounded values
+        // (hints are computed outside of the circuit, on the values of field elements)
+        let is_comparison = matches!(
+            op,
+            Op2::LessThan | Op2::LessThanOrEqual | Op2::GreaterThan | Op2::GreaterThanOrEqual
+        );
+        if is_comparison
+            && !typed_fn_env.is_hint()
+            && operand_typ.uint_bits().is_none()
+            && !matches!(operand_typ, TyKind::BigInt)
+        {
+            return Err(self.error(ErrorKind::UnboundedComparison(operand_typ), span));
+        }
+
+        // so are bitwise operations (`&`, `|` and `^` are also logical operations on booleans)
+        let is_logical = matches!(op, Op2::BitwiseAnd | Op2::BitwiseOr | Op2::BitwiseXor)
+            && matches!(operand_typ, TyKind::Bool);
+        let is_bitwise = matches!(
+            op,
+            Op2::BitwiseAnd | Op2::BitwiseOr | Op2::BitwiseXor | Op2::ShiftLeft | Op2::ShiftRight
+        );
+        if is_bitwise
+            && !is_logical
+            && !typed_fn_env.is_hint()
+            && operand_typ.uint_bits().is_none()
+            && !matches!(operand_typ, TyKind::BigInt)
+        {
+            return Err(self.error(ErrorKind::UnboundedBitwiseOperation(operand_typ), span));
+        }
+
+        let typ = match op {
+            Op2::Equality
+            | Op2::Inequality
+            | Op2::LessThan
+            | Op2::LessThanOrEqual
+            | Op2::GreaterThan
+            | Op2::GreaterThanOrEqual => TyKind::Bool,
+            Op2::Addition
+            | Op2::Subtraction
+            | Op2::Multiplication
+            | Op2::Division
+            | Op2::BoolAnd
+            | Op2::BoolOr
+            | Op2::BitwiseAnd
+            | Op2::BitwiseOr
+            | Op2::BitwiseXor
+            | Op2::ShiftLeft
+            | Op2::ShiftRight => operand_typ,
+        };
+
+        Ok(typ)
+    }
+
     pub fn check_block(
         &mut self,
         typed_fn_env: &mut TypedFnEnv,
@@ -561,6 +1016,14 @@ impl<B: Backend> TypeChecker<B> {
                         expected.span,
                     ));
                 }
+
+                // the returned value is the last statement
+                let last = stmts.last().unwrap();
+                let returned = match &last.kind {
+                    StmtKind::Return(res) => Some(res.as_ref()),
+                    _ => None,
+                };
+                self.check_uint_constants(returned, &observed, &expected.kind, last.span)?;
             }
         };
 
@@ -580,29 +1043,120 @@ impl<B: Backend> TypeChecker<B> {
                 // inferance can be easy: we can do it the Golang way and just use the type that rhs has (in `let` assignments)
 
                 // but first we need to compute the type of the rhs expression
+                // (which is the only place where a hint can be called)
+                let is_hint_call = !typed_fn_env.is_hint() && self.is_hint_call(rhs);
+                if is_hint_call {
+                    typed_fn_env.allow_hint_call();
+                }
                 let node = self.compute_type(rhs, typed_fn_env)?.unwrap();
 
                 let type_info = if *mutable {
-                    TypeInfo::new_mut(node.typ, lhs.span)
+                    // a mutable variable initialized with a constant can later hold field elements
+                    let typ = match node.typ {
+                        TyKind::BigInt => TyKind::Field,
+                        typ => typ,
+                    };
+                    TypeInfo::new_mut(typ, lhs.span)
                 } else {
                     TypeInfo::new(node.typ, lhs.span)
                 };
 
                 // store the type of lhs in the env
                 typed_fn_env.store_type(lhs.value.clone(), type_info)?;
+
+                // the output of a hint must be checked, as it is not constrained
+                if is_hint_call {
+                    typed_fn_env.store_hint_output(lhs.value.clone(), lhs.span);
+                }
             }
-            StmtKind::ForLoop { var, range, body } => {
-                // enter a new scope
-                typed_fn_env.nest();
+            StmtKind::TupleAssign { lhs, rhs } => {
+                let is_hint_call = !typed_fn_env.is_hint() && self.is_hint_call(rhs);
+                if is_hint_call {
+                    typed_fn_env.allow_hint_call();
+                }
+                let node = self
+                    .compute_type(rhs, typed_fn_env)?
+                    .ok_or_else(|| self.error(ErrorKind::CannotComputeExpression, rhs.span))?;
+
+                // the rhs must be a tuple with as many elements as there are names
+                let types = match node.typ {
+                    TyKind::Tuple(types) if types.len() == lhs.len() => types,
+                    typ => {
+                        return Err(self.error(
+                            ErrorKind::TupleDestructuringMismatch(typ, lhs.len()),
+                            stmt.span,
+                        ))
+                    }
+                };
 
-                // create var (for now it's always a bigint)
-                typed_fn_env
-                    .store_type(var.value.clone(), TypeInfo::new(TyKind::BigInt, var.span))?;
+                // store the type of each name in the env
+                for ((mutable, name), typ) in lhs.iter().zip(types) {
+                    let type_info = if *mutable {
+                        TypeInfo::new_mut(typ, name.span)
+                    } else {
+                        TypeInfo::new(typ, name.span)
+                    };
+                    typed_fn_env.store_type(name.value.clone(), type_info)?;
 
-                // ensure start..end makes sense
-                if range.end < range.start {
-                    return Err(self.error(ErrorKind::InvalidRange, range.span));
+                    if is_hint_call {
+                        typed_fn_env.store_hint_output(name.value.clone(), name.span);
+                    }
                 }
+            }
+            StmtKind::ForLoop {
+                var,
+                argument,
+                body,
+            } => {
+                // the loop variable is either an integer of the range, or an element of the array
+                let var_typ = match argument {
+                    ForLoopArgument::Range(range) => {
+                        // ensure start..end makes sense
+                        // (the constants of the module have already been evaluated,
+                        // and generic sizes can only be evaluated at each call site)
+                        for bound in [&range.start, &range.end] {
+                            if let Some(name) = bound
+                                .names()
+                                .into_iter()
+                                .find(|name| !typed_fn_env.is_generic(name))
+                            {
+                                return Err(
+                                    self.error(ErrorKind::UndefinedGenericSize(name), range.span)
+                                );
+                            }
+                        }
+                        if let (Some(start), Some(end)) = (range.start.value(), range.end.value()) {
+                            if end < start {
+                                return Err(self.error(ErrorKind::InvalidRange, range.span));
+                            }
+                        }
+
+                        TyKind::BigInt
+                    }
+                    ForLoopArgument::Iterator(iterator) => {
+                        let typ = self
+                            .compute_type(iterator, typed_fn_env)?
+                            .ok_or_else(|| {
+                                self.error(ErrorKind::CannotComputeExpression, iterator.span)
+                            })?
+                            .typ;
+
+                        match typ {
+                            TyKind::Array(elem, _) | TyKind::GenericSizedArray(elem, _) => *elem,
+                            typ => {
+                                return Err(
+                                    self.error(ErrorKind::InvalidIterator(typ), iterator.span)
+                                )
+                            }
+                        }
+                    }
+                };
+
+                // enter a new scope
+                typed_fn_env.nest();
+
+                // create var
+                typed_fn_env.store_type(var.value.clone(), TypeInfo::new(var_typ, var.span))?;
 
                 // check block
                 self.check_block(typed_fn_env, body, None)?;
@@ -619,7 +1173,35 @@ impl<B: Backend> TypeChecker<B> {
                     return Err(self.error(ErrorKind::UnusedReturnValue, expr.span));
                 }
             }
+            StmtKind::If { cond, then_, else_ } => {
+                // the condition must be a boolean
+                let cond_typ = self
+                    .compute_type(cond, typed_fn_env)?
+                    .ok_or_else(|| self.error(ErrorKind::CannotComputeExpression, cond.span))?
+                    .typ;
+                if !matches!(cond_typ, TyKind::Bool) {
+                    return Err(
+                        self.error(ErrorKind::IfElseInvalidConditionType(cond_typ), cond.span)
+                    );
+                }
+
+                // the constraints created in the blocks must be conditional
+                // (in hints, only the block that is taken is computed)
+                let conditional = !typed_fn_env.is_hint();
+                if conditional {
+                    typed_fn_env.enter_conditional();
+                }
+                self.check_block(typed_fn_env, then_, None)?;
+                self.check_block(typed_fn_env, else_, None)?;
+                if conditional {
+                    typed_fn_env.exit_conditional();
+                }
+            }
             StmtKind::Return(res) => {
+                if typed_fn_env.is_conditional() {
+                    return Err(self.error(ErrorKind::ReturnInIfStatement, stmt.span));
+                }
+
                 let node = self.compute_type(res, typed_fn_env)?.unwrap();
 
                 return Ok(Some(node.typ));
@@ -680,17 +1262,229 @@ impl<B: Backend> TypeChecker<B> {
             ));
         }
 
+        // bind the generic array sizes of the signature,
+        // either to the value of the `const` argument of the same name,
+        // or to the size of the array passed as argument.
+        // If we are in a generic function, they might be bound to its own generic sizes
+        let mut generics = HashMap::new();
+        for ((sig_arg, arg), (typ, _)) in expected.iter().zip(args).zip(&observed) {
+            if sig_arg.is_constant() {
+                if let Some(value) = self.const_u32(arg) {
+                    generics.insert(sig_arg.name.value.clone(), ConstExpr::Literal(value));
+                } else if let ExprKind::Variable { module: _, name } = &arg.kind {
+                    if typed_fn_env.is_generic(&name.value) {
+                        generics.insert(
+                            sig_arg.name.value.clone(),
+                            ConstExpr::Name(name.value.clone()),
+                        );
+                    }
+                }
+            }
+            sig_arg.typ.kind.bind_generics(typ, &mut generics);
+        }
+
         // compare argument types with the function signature
-        for (sig_arg, (typ, span)) in expected.iter().zip(observed) {
-            if !typ.match_expected(&sig_arg.typ.kind) {
-                return Err(self.error(
-                    ErrorKind::ArgumentTypeMismatch(sig_arg.typ.kind.clone(), typ),
-                    span,
-                ));
+        for ((sig_arg, arg), (typ, span)) in expected.iter().zip(args).zip(observed) {
+            let expected_typ = self.resolve_generics(typed_fn_env, &sig_arg.typ, &generics)?;
+            if !typ.match_expected(&expected_typ) {
+                return Err(self.error(ErrorKind::ArgumentTypeMismatch(expected_typ, typ), span));
             }
+            self.check_uint_constants(Some(arg), &typ, &expected_typ, span)?;
         }
 
         // return the return type of the function
-        Ok(fn_sig.return_type.as_ref().map(|ty| ty.kind.clone()))
+        fn_sig
+            .return_type
+            .as_ref()
+            .map(|ty| self.resolve_generics(typed_fn_env, ty, &generics))
+            .transpose()
+    }
+
+    /// Replaces the generic array sizes of a type with the sizes they were bound to.
+    /// The sizes that can't be evaluated must depend on the generic sizes of the caller.
+    fn resolve_generics(
+        &self,
+        typed_fn_env: &TypedFnEnv,
+        ty: &Ty,
+        generics: &HashMap<String, ConstExpr>,
+    ) -> Result<TyKind> {
+        let resolved = ty.kind.resolve_generics(generics);
+
+        if resolved.is_generic() {
+            let names = resolved.generic_names();
+            if names.is_empty() || names.iter().any(|name| !typed_fn_env.is_generic(name)) {
+                return Err(self.error(ErrorKind::UnresolvedGenericSize(ty.kind.clone()), ty.span));
+            }
+        }
+
+        Ok(resolved)
+    }
+
+    /// Returns a copy of the function where the constants of the module,
+    /// used in array sizes or in the ranges of `for` loops, have been evaluated.
+    /// (`const` arguments take precedence over constants of the same name.)
+    pub(crate) fn resolve_module_consts(&self, function: &FunctionDef) -> FunctionDef {
+        let module = match &function.sig.kind {
+            FuncOrMethod::Function(module) => module,
+            FuncOrMethod::Method(custom) => &custom.module,
+        };
+
+        let const_args: Vec<_> = function
+            .sig
+            .arguments
+            .iter()
+            .filter(|arg| arg.is_constant())
+            .map(|arg| arg.name.value.as_str())
+            .collect();
+        let lookup = |name: &str| {
+            if const_args.contains(&name) {
+                None
+            } else {
+                self.module_const(module, name)
+            }
+        };
+
+        let mut function = function.clone();
+        for arg in &mut function.sig.arguments {
+            arg.typ.kind = arg.typ.kind.resolve_sizes(&lookup);
+        }
+        if let Some(typ) = &mut function.sig.return_type {
+            typ.kind = typ.kind.resolve_sizes(&lookup);
+        }
+        resolve_ranges(&mut function.body, &lookup);
+
+        function
+    }
+
+    /// Returns the value of a constant of the module, if it fits in a `u32`.
+    pub(crate) fn module_const(&self, module: &ModulePath, name: &str) -> Option<ConstExpr> {
+        let qualified = FullyQualified::new(module, &name.to_string());
+        let cst_info = self.const_info(&qualified)?;
+        let value: BigUint = cst_info.value[0].into();
+        u32::try_from(value).ok().map(ConstExpr::Literal)
+    }
+
+    /// Returns true if the expression is a call to a hint function.
+    fn is_hint_call(&self, expr: &Expr) -> bool {
+        match &expr.kind {
+            ExprKind::FnCall {
+                module, fn_name, ..
+            } => {
+                let qualified = FullyQualified::new(module, &fn_name.value);
+                matches!(
+                    self.fn_info(&qualified).map(|fn_info| &fn_info.kind),
+                    Some(FnKind::Native(func)) if func.is_hint
+                )
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the value of an expression known at compile time
+    /// (a literal or a module constant), if it fits in a `u32`.
+    fn const_u32(&self, expr: &Expr) -> Option<u32> {
+        self.const_value(expr)
+            .and_then(|value| u32::try_from(value).ok())
+    }
+
+    /// Returns the value of an expression known at compile time:
+    /// a literal, a module constant, or an addition, subtraction or multiplication of these
+    /// (as long as it doesn't underflow).
+    fn const_value(&self, expr: &Expr) -> Option<BigUint> {
+        match &expr.kind {
+            ExprKind::BigUInt(value) => Some(value.clone()),
+            ExprKind::Variable { module, name } => {
+                let qualified = FullyQualified::new(module, &name.value);
+                let cst_info = self.const_info(&qualified)?;
+                Some(cst_info.value[0].into())
+            }
+            ExprKind::BinaryOp { op, lhs, rhs, .. } => {
+                let lhs = self.const_value(lhs)?;
+                let rhs = self.const_value(rhs)?;
+                match op {
+                    Op2::Addition => Some(lhs + rhs),
+                    Op2::Subtraction if lhs >= rhs => Some(lhs - rhs),
+                    Op2::Multiplication => Some(lhs * rhs),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Checks that the constants (of type [TyKind::BigInt]) found where unsigned integers are expected
+    /// have a value known at compile time that fits in the unsigned integer type,
+    /// as they are not range checked in the circuit.
+    /// The expression is given when it is known (and `typ` has already been matched against `expected`).
+    fn check_uint_constants(
+        &self,
+        expr: Option<&Expr>,
+        typ: &TyKind,
+        expected: &TyKind,
+        span: Span,
+    ) -> Result<()> {
+        match (typ, expected) {
+            (TyKind::BigInt, expected) => {
+                let Some(num_bits) = expected.uint_bits() else {
+                    return Ok(());
+                };
+                let value = expr
+                    .and_then(|expr| self.const_value(expr))
+                    .ok_or_else(|| {
+                        self.error(ErrorKind::UnknownUintConstant(expected.clone()), span)
+                    })?;
+                if value.bits() > num_bits as u64 {
+                    return Err(self.error(
+                        ErrorKind::UintOverflow(value.to_string(), expected.clone()),
+                        span,
+                    ));
+                }
+            }
+            (TyKind::Array(typ, _), TyKind::Array(expected, _)) => {
+                match expr.map(|expr| &expr.kind) {
+                    Some(ExprKind::ArrayDeclaration(items)) => {
+                        for item in items {
+                            self.check_uint_constants(Some(item), typ, expected, item.span)?;
+                        }
+                    }
+                    _ => self.check_uint_constants(None, typ, expected, span)?,
+                }
+            }
+            (TyKind::Tuple(types), TyKind::Tuple(expected)) => match expr.map(|expr| &expr.kind) {
+                Some(ExprKind::TupleDeclaration(items)) => {
+                    for ((item, typ), expected) in items.iter().zip(types).zip(expected) {
+                        self.check_uint_constants(Some(item), typ, expected, item.span)?;
+                    }
+                }
+                _ => {
+                    for (typ, expected) in types.iter().zip(expected) {
+                        self.check_uint_constants(None, typ, expected, span)?;
+                    }
+                }
+            },
+            _ => (),
+        }
+
+        Ok(())
+    }
+}
+
+/// Evaluates what can be evaluated in the ranges of the `for` loops of a block.
+fn resolve_ranges(stmts: &mut [Stmt], lookup: &impl Fn(&str) -> Option<ConstExpr>) {
+    for stmt in stmts {
+        match &mut stmt.kind {
+            StmtKind::ForLoop { argument, body, .. } => {
+                if let ForLoopArgument::Range(range) = argument {
+                    range.start = range.start.substitute(lookup);
+                    range.end = range.end.substitute(lookup);
+                }
+                resolve_ranges(body, lookup);
+            }
+            StmtKind::If { then_, else_, .. } => {
+                resolve_ranges(then_, lookup);
+                resolve_ranges(else_, lookup);
+            }
+            _ => (),
+        }
     }
 }
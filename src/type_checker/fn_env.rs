@@ -1,6 +1,6 @@
 //! This module defines the context (or environment) that gets created when type checking a function.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::{
     constants::Span,
@@ -60,6 +60,27 @@ pub struct TypedFnEnv {
     /// This needs to be garbage collected when we exit a scope.
     // TODO: there's an output_type field that's a reserved keyword?
     vars: HashMap<String, (usize, TypeInfo)>,
+
+    /// The generic array sizes and `const` arguments of the function,
+    /// which are known at compile time and can be used as range bounds.
+    generics: HashSet<String>,
+
+    /// The number of `if` statements we're in.
+    conditional_depth: usize,
+
+    /// Whether we're in a hint function, which is not compiled in the circuit.
+    hint: bool,
+
+    /// The variables holding the output of a hint that haven't been checked in an assertion yet,
+    /// along with the span of their declaration.
+    unused_hint_outputs: Vec<(String, Span)>,
+
+    /// The number of assertions (`assert` or `assert_eq`) whose arguments we're in.
+    assertion_depth: usize,
+
+    /// Whether the next function call is allowed to be a call to a hint,
+    /// which is only the case of the right-hand side of a `let` statement.
+    hint_call_allowed: bool,
 }
 
 impl TypedFnEnv {
@@ -83,6 +104,80 @@ impl TypedFnEnv {
             .retain(|_name, (scope, _type_info)| *scope <= current_scope);
     }
 
+    /// Enters the block of an `if` statement.
+    pub fn enter_conditional(&mut self) {
+        self.conditional_depth += 1;
+    }
+
+    /// Exits the block of an `if` statement.
+    pub fn exit_conditional(&mut self) {
+        self.conditional_depth = self
+            .conditional_depth
+            .checked_sub(1)
+            .expect("conditional bug");
+    }
+
+    /// Returns true if we're in the block of an `if` statement,
+    /// in which constraints must be made conditional.
+    pub fn is_conditional(&self) -> bool {
+        self.conditional_depth > 0
+    }
+
+    /// Marks the environment as the one of a hint function.
+    pub fn set_hint(&mut self) {
+        self.hint = true;
+    }
+
+    /// Returns true if we're in a hint function,
+    /// which is computed outside of the circuit (so no constraints are created).
+    pub fn is_hint(&self) -> bool {
+        self.hint
+    }
+
+    /// Keeps track of a variable holding the output of a hint, which must be used.
+    pub fn store_hint_output(&mut self, ident: String, span: Span) {
+        self.unused_hint_outputs.push((ident, span));
+    }
+
+    /// Enters the arguments of an assertion.
+    pub fn enter_assertion(&mut self) {
+        self.assertion_depth += 1;
+    }
+
+    /// Exits the arguments of an assertion.
+    pub fn exit_assertion(&mut self) {
+        self.assertion_depth = self.assertion_depth.checked_sub(1).expect("assertion bug");
+    }
+
+    /// Allows the next function call to be a call to a hint.
+    pub fn allow_hint_call(&mut self) {
+        self.hint_call_allowed = true;
+    }
+
+    /// Returns true if the current function call is allowed to be a call to a hint,
+    /// and resets the permission for the calls that follow (including the ones in its arguments).
+    pub fn take_hint_call_allowed(&mut self) -> bool {
+        std::mem::take(&mut self.hint_call_allowed)
+    }
+
+    /// Marks a variable as used, if it holds the output of a hint and is read in an assertion.
+    /// (Reading it anywhere else does not constrain it.)
+    pub fn use_var(&mut self, ident: &str) {
+        if self.assertion_depth == 0 {
+            return;
+        }
+
+        if let Some(span) = self.get_type_info(ident).map(|type_info| type_info.span) {
+            self.unused_hint_outputs
+                .retain(|(name, decl)| !(name == ident && *decl == span));
+        }
+    }
+
+    /// Returns the first variable holding the output of a hint that was never checked in an assertion.
+    pub fn unused_hint_output(&self) -> Option<&(String, Span)> {
+        self.unused_hint_outputs.first()
+    }
+
     /// Returns true if a scope is a prefix of our scope.
     pub fn is_in_scope(&self, prefix_scope: usize) -> bool {
         self.current_scope >= prefix_scope
@@ -104,6 +199,16 @@ impl TypedFnEnv {
         }
     }
 
+    /// Stores the name of a generic array size, or of a `const` argument, of the function.
+    pub fn store_generic(&mut self, name: String) {
+        self.generics.insert(name);
+    }
+
+    /// Returns true if the name is a generic array size, or a `const` argument, of the function.
+    pub fn is_generic(&self, name: &str) -> bool {
+        self.generics.contains(name)
+    }
+
     pub fn get_type(&self, ident: &str) -> Option<&TyKind> {
         self.get_type_info(ident).map(|type_info| &type_info.typ)
     }
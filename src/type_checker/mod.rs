@@ -1,21 +1,22 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::{
     backends::Backend,
-    cli::packages::UserRepo,
+    cli::packages::{QualifiedModule, UserRepo},
     constants::Span,
     error::{Error, ErrorKind, Result},
     imports::FnKind,
+    inputs::{EnumVariants, StructFields},
     name_resolution::NAST,
     parser::{
         types::{FuncOrMethod, FunctionDef, ModulePath, RootKind, Ty, TyKind},
-        CustomType, Expr, StructDef,
+        CustomType, EnumDef, Expr, StructDef,
     },
-    stdlib::{builtin_fns, crypto::crypto_fns, QUALIFIED_BUILTINS},
+    stdlib::{builtin_fns, crypto::crypto_fns, ec::ec_fns, QUALIFIED_BUILTINS},
 };
 
 use ark_ff::Field;
-pub use checker::{FnInfo, StructInfo};
+pub use checker::{EnumInfo, FnInfo, StructInfo};
 pub use fn_env::{TypeInfo, TypedFnEnv};
 
 use serde::{Deserialize, Serialize};
@@ -40,7 +41,7 @@ where
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct FullyQualified {
     /// Set to `None` if the function is defined in the main module.
-    pub module: Option<UserRepo>,
+    pub module: Option<QualifiedModule>,
     pub name: String,
 }
 
@@ -53,7 +54,7 @@ impl FullyQualified {
         let module = match module {
             ModulePath::Local => None,
             ModulePath::Alias(_) => unreachable!(),
-            ModulePath::Absolute(user_repo) => Some(user_repo.clone()),
+            ModulePath::Absolute(qualified) => Some(qualified.clone()),
         };
         Self {
             module,
@@ -75,6 +76,9 @@ where
     /// Custom structs type information and ASTs for methods.
     structs: HashMap<FullyQualified, StructInfo>,
 
+    /// Custom enums type information.
+    enums: HashMap<FullyQualified, EnumInfo>,
+
     /// Constants declared in this module.
     constants: HashMap<FullyQualified, ConstInfo<B::Field>>,
 
@@ -82,6 +86,9 @@ where
     /// This can be used by the circuit-writer when it needs type information.
     // TODO: I think we should get rid of this if we can
     node_types: HashMap<usize, TyKind>,
+
+    /// The modules of the standard library written in noname that have been type checked.
+    std_modules: HashSet<String>,
 }
 
 impl<B: Backend> TypeChecker<B> {
@@ -98,6 +105,10 @@ impl<B: Backend> TypeChecker<B> {
         self.structs.get(qualified)
     }
 
+    pub(crate) fn enum_info(&self, qualified: &FullyQualified) -> Option<&EnumInfo> {
+        self.enums.get(qualified)
+    }
+
     pub(crate) fn fn_info(&self, qualified: &FullyQualified) -> Option<&FnInfo<B>> {
         self.functions.get(qualified)
     }
@@ -109,26 +120,70 @@ impl<B: Backend> TypeChecker<B> {
     /// Returns the number of field elements contained in the given type.
     // TODO: might want to memoize that at some point
     pub(crate) fn size_of(&self, typ: &TyKind) -> usize {
-        match typ {
-            TyKind::Field => 1,
-            TyKind::Custom { module, name } => {
-                let qualified = FullyQualified::new(&module, &name);
-                let struct_info = self
-                    .struct_info(&qualified)
-                    .expect("bug in the type checker: cannot find struct info");
-
-                let mut sum = 0;
-
-                for (_, t) in &struct_info.fields {
-                    sum += self.size_of(t);
-                }
+        size_of(
+            typ,
+            &|qualified| {
+                self.struct_info(qualified)
+                    .map(|struct_info| struct_info.fields.as_slice())
+            },
+            &|qualified| {
+                self.enum_info(qualified)
+                    .map(|enum_info| enum_info.variants.as_slice())
+            },
+        )
+    }
+}
+
+/// Returns the number of field elements contained in the given type,
+/// looking up the fields of structs and the variants of enums with the given functions.
+/// This is also used outside of the type checker, to parse the public inputs of a verifier.
+pub(crate) fn size_of<'a>(
+    typ: &TyKind,
+    struct_fields: &StructFields<'a>,
+    enum_variants: &EnumVariants<'a>,
+) -> usize {
+    match typ {
+        TyKind::Field => 1,
+        TyKind::Custom { module, name } => {
+            let qualified = FullyQualified::new(module, name);
+
+            // an enum is a tag, followed by enough room for the values of any variant
+            if let Some(variants) = enum_variants(&qualified) {
+                let payload = variants
+                    .iter()
+                    .map(|(_, types)| {
+                        types
+                            .iter()
+                            .map(|typ| size_of(typ, struct_fields, enum_variants))
+                            .sum()
+                    })
+                    .max()
+                    .unwrap_or(0);
+
+                return 1 + payload;
+            }
+
+            let fields = struct_fields(&qualified)
+                .expect("bug in the type checker: cannot find struct info");
 
-                sum
+            let mut sum = 0;
+
+            for (_, t) in fields {
+                sum += size_of(t, struct_fields, enum_variants);
             }
-            TyKind::BigInt => 1,
-            TyKind::Array(typ, len) => (*len as usize) * self.size_of(typ),
-            TyKind::Bool => 1,
+
+            sum
+        }
+        TyKind::BigInt => 1,
+        TyKind::Array(typ, len) => (*len as usize) * size_of(typ, struct_fields, enum_variants),
+        TyKind::GenericSizedArray(..) => {
+            unreachable!("generic array sizes should have been resolved")
         }
+        TyKind::Bool | TyKind::U8 | TyKind::U16 | TyKind::U32 | TyKind::U64 => 1,
+        TyKind::Tuple(types) => types
+            .iter()
+            .map(|typ| size_of(typ, struct_fields, enum_variants))
+            .sum(),
     }
 }
 
@@ -138,12 +193,14 @@ impl<B: Backend> TypeChecker<B> {
         let mut type_checker = Self {
             functions: HashMap::new(),
             structs: HashMap::new(),
+            enums: HashMap::new(),
             constants: HashMap::new(),
             node_types: HashMap::new(),
+            std_modules: HashSet::new(),
         };
 
         // initialize it with the builtins
-        let builtin_module = ModulePath::Absolute(UserRepo::new(QUALIFIED_BUILTINS));
+        let builtin_module = ModulePath::Absolute(UserRepo::new(QUALIFIED_BUILTINS).into());
         for fn_info in builtin_fns() {
             let qualified = FullyQualified::new(&builtin_module, &fn_info.sig().name.value);
             if type_checker
@@ -156,7 +213,7 @@ impl<B: Backend> TypeChecker<B> {
         }
 
         // initialize it with the standard library
-        let crypto_module = ModulePath::Absolute(UserRepo::new("std/crypto"));
+        let crypto_module = ModulePath::Absolute(UserRepo::new("std/crypto").into());
         for fn_info in crypto_fns() {
             let qualified = FullyQualified::new(&crypto_module, &fn_info.sig().name.value);
             if type_checker
@@ -168,10 +225,28 @@ impl<B: Backend> TypeChecker<B> {
             }
         }
 
+        let ec_module = ModulePath::Absolute(UserRepo::new("std/ec").into());
+        for fn_info in ec_fns() {
+            let qualified = FullyQualified::new(&ec_module, &fn_info.sig().name.value);
+            if type_checker
+                .functions
+                .insert(qualified, fn_info.clone())
+                .is_some()
+            {
+                panic!("type-checker bug: global imports conflict");
+            }
+        }
+
         //
         type_checker
     }
 
+    /// Marks a module of the standard library written in noname as type checked.
+    /// Returns `false` if it was already marked.
+    pub(crate) fn add_std_module(&mut self, name: &str) -> bool {
+        self.std_modules.insert(name.to_string())
+    }
+
     pub fn error(&self, kind: ErrorKind, span: Span) -> Error {
         Error::new("type-checker", kind, span)
     }
@@ -224,14 +299,15 @@ impl<B: Backend> TypeChecker<B> {
                 }
 
                 RootKind::FunctionDef(FunctionDef { span, .. })
-                | RootKind::StructDef(StructDef { span, .. }) => abort = Some(*span),
+                | RootKind::StructDef(StructDef { span, .. })
+                | RootKind::EnumDef(EnumDef { span, .. }) => abort = Some(*span),
 
                 RootKind::Use(_) | RootKind::Comment(_) => (),
             }
         }
 
         //
-        // Type check structs
+        // Type check structs and enums
         //
 
         for root in &nast.ast.0 {
@@ -245,13 +321,23 @@ impl<B: Backend> TypeChecker<B> {
                         ..
                     } = struct_def;
 
-                    let fields: Vec<_> = fields
-                        .iter()
-                        .map(|field| {
-                            let (name, typ) = field;
-                            (name.value.clone(), typ.kind.clone())
-                        })
-                        .collect();
+                    // struct fields can't have generic sizes,
+                    // but they can use the constants of the module
+                    let mut resolved_fields = vec![];
+                    for (name, typ) in fields {
+                        let kind = typ
+                            .kind
+                            .resolve_sizes(&|size| self.module_const(module, size));
+                        if kind.is_generic() {
+                            return Err(Error::new(
+                                "type-checker",
+                                ErrorKind::UnexpectedGenericSize,
+                                typ.span,
+                            ));
+                        }
+                        resolved_fields.push((name.value.clone(), kind));
+                    }
+                    let fields = resolved_fields;
 
                     let struct_info = StructInfo {
                         name: name.name.clone(),
@@ -263,6 +349,63 @@ impl<B: Backend> TypeChecker<B> {
                     self.structs.insert(qualified, struct_info);
                 }
 
+                // `enum Foo { Bar(Field), Baz }`
+                RootKind::EnumDef(enum_def) => {
+                    let EnumDef {
+                        module,
+                        name,
+                        variants,
+                        ..
+                    } = enum_def;
+
+                    let qualified = FullyQualified::new(module, &name.name);
+                    if self.structs.contains_key(&qualified) || self.enums.contains_key(&qualified)
+                    {
+                        return Err(Error::new(
+                            "type-checker",
+                            ErrorKind::DuplicateDefinition(name.name.clone()),
+                            name.span,
+                        ));
+                    }
+
+                    // like struct fields, the values held by variants can't have generic sizes
+                    let mut resolved_variants: Vec<(String, Vec<TyKind>)> = vec![];
+                    for (variant, types) in variants {
+                        if resolved_variants
+                            .iter()
+                            .any(|(seen, _)| seen == &variant.value)
+                        {
+                            return Err(Error::new(
+                                "type-checker",
+                                ErrorKind::DuplicateDefinition(variant.value.clone()),
+                                variant.span,
+                            ));
+                        }
+
+                        let mut resolved_types = vec![];
+                        for typ in types {
+                            let kind = typ
+                                .kind
+                                .resolve_sizes(&|size| self.module_const(module, size));
+                            if kind.is_generic() {
+                                return Err(Error::new(
+                                    "type-checker",
+                                    ErrorKind::UnexpectedGenericSize,
+                                    typ.span,
+                                ));
+                            }
+                            resolved_types.push(kind);
+                        }
+                        resolved_variants.push((variant.value.clone(), resolved_types));
+                    }
+
+                    let enum_info = EnumInfo {
+                        name: name.name.clone(),
+                        variants: resolved_variants,
+                    };
+                    self.enums.insert(qualified, enum_info);
+                }
+
                 RootKind::ConstDef(_)
                 | RootKind::Use(_)
                 | RootKind::FunctionDef(_)
@@ -278,8 +421,14 @@ impl<B: Backend> TypeChecker<B> {
             match &root.kind {
                 // `fn main() { ... }`
                 RootKind::FunctionDef(function) => {
+                    // evaluate the constants of the module used in array sizes and ranges
+                    let function = &self.resolve_module_consts(function);
+
                     // create a new typed fn environment to type check the function
                     let mut typed_fn_env = TypedFnEnv::default();
+                    if function.is_hint {
+                        typed_fn_env.set_hint();
+                    }
 
                     // if we're expecting a library, this should not be the main function
                     let is_main = function.is_main();
@@ -291,6 +440,51 @@ impl<B: Backend> TypeChecker<B> {
                         ));
                     }
 
+                    // the inputs and output of the main function must have a known size
+                    if is_main {
+                        let generic_typ = function
+                            .sig
+                            .arguments
+                            .iter()
+                            .map(|arg| &arg.typ)
+                            .chain(&function.sig.return_type)
+                            .find(|typ| typ.kind.is_generic());
+                        if let Some(typ) = generic_typ {
+                            return Err(Error::new(
+                                "type-checker",
+                                ErrorKind::UnexpectedGenericSize,
+                                typ.span,
+                            ));
+                        }
+                    }
+
+                    // the generic array sizes are inferred from the arguments at each call site,
+                    // and so are the values of `const` arguments
+                    for arg in &function.sig.arguments {
+                        if arg.is_constant() {
+                            typed_fn_env.store_generic(arg.name.value.clone());
+                        }
+                        for name in arg.typ.kind.generic_names() {
+                            typed_fn_env.store_generic(name);
+                        }
+                    }
+
+                    // which means that the return type can't introduce new generic sizes
+                    if let Some(typ) = &function.sig.return_type {
+                        let unbound = typ
+                            .kind
+                            .generic_names()
+                            .into_iter()
+                            .find(|name| !typed_fn_env.is_generic(name));
+                        if let Some(name) = unbound {
+                            return Err(Error::new(
+                                "type-checker",
+                                ErrorKind::UndefinedGenericSize(name),
+                                typ.span,
+                            ));
+                        }
+                    }
+
                     // if this is the main function check that it has arguments
                     if is_main && function.sig.arguments.is_empty() {
                         return Err(Error::new(
@@ -383,13 +577,18 @@ impl<B: Backend> TypeChecker<B> {
                                 TyKind::Field
                                 | TyKind::Custom { .. }
                                 | TyKind::Array(_, _)
-                                | TyKind::Bool => {
+                                | TyKind::Bool
+                                | TyKind::U8
+                                | TyKind::U16
+                                | TyKind::U32
+                                | TyKind::U64
+                                | TyKind::Tuple(_) => {
                                     typed_fn_env.store_type(
                                         "public_output".to_string(),
                                         TypeInfo::new_mut(typ.kind.clone(), typ.span),
                                     )?;
                                 }
-                                TyKind::BigInt => unreachable!(),
+                                TyKind::BigInt | TyKind::GenericSizedArray(..) => unreachable!(),
                             }
                         }
                     }
@@ -400,11 +599,22 @@ impl<B: Backend> TypeChecker<B> {
                         &function.body,
                         function.sig.return_type.as_ref(),
                     )?;
+
+                    // the values computed by hints are not constrained,
+                    // so they must be checked by an assertion
+                    if let Some((name, span)) = typed_fn_env.unused_hint_output() {
+                        return Err(Error::new(
+                            "type-checker",
+                            ErrorKind::UnusedHintOutput(name.clone()),
+                            *span,
+                        ));
+                    }
                 }
 
                 RootKind::Use(_)
                 | RootKind::ConstDef(_)
                 | RootKind::StructDef(_)
+                | RootKind::EnumDef(_)
                 | RootKind::Comment(_) => (),
             };
         }
@@ -46,6 +46,10 @@ where
     /// Note that it will potentially return 0 if the given variable is 0.
     Inverse(B::Var),
 
+    /// Returns `len` bits of the given variable, starting at bit `start` (in little-endian order).
+    /// The result is at most 64 bits.
+    Bits(B::Var, usize /* start */, usize /* len */),
+
     /// A public or private input to the function
     /// There's an index associated to a variable name, as the variable could be composed of several field elements.
     External(String, usize),
@@ -63,6 +67,7 @@ impl<B: Backend> std::fmt::Debug for Value<B> {
             Value::LinearCombination(..) => write!(f, "LinearCombination"),
             Value::Mul(..) => write!(f, "Mul"),
             Value::Inverse(_) => write!(f, "Inverse"),
+            Value::Bits(..) => write!(f, "Bits"),
             Value::External(..) => write!(f, "External"),
             Value::PublicOutput(..) => write!(f, "PublicOutput"),
             Value::Scale(..) => write!(f, "Scaling"),
@@ -71,7 +76,7 @@ impl<B: Backend> std::fmt::Debug for Value<B> {
 }
 
 /// Represents a cell in the execution trace.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum ConstOrCell<F, C>
 where
     F: Field,
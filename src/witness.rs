@@ -21,6 +21,10 @@ where
     pub var_values: HashMap<String, Vec<F>>,
 
     pub cached_values: HashMap<usize, F>,
+
+    /// The outputs of the calls to hint functions, indexed by call,
+    /// as they are computed all at once.
+    pub hint_outputs: HashMap<usize, Vec<F>>,
 }
 
 impl<F: Field> WitnessEnv<F> {
@@ -109,4 +113,16 @@ impl<B: Backend> CompiledCircuit<B> {
 
         self.circuit.generate_witness(&mut env)
     }
+
+    /// Returns the full public input expected by a verifier:
+    /// the public output (if any) followed by the public inputs,
+    /// in the order in which they appear in the main function.
+    pub fn full_public_inputs(
+        &self,
+        public_inputs: JsonInputs,
+        public_output: Option<serde_json::Value>,
+    ) -> Result<Vec<B::Field>> {
+        self.public_io()
+            .full_public_inputs(public_inputs, public_output)
+    }
 }